@@ -0,0 +1,22 @@
+use std::process::Command;
+
+// Build information for `fbtoggl version --verbose`, crates.io builds
+// have no git checkout and report the sha as unknown
+fn main() {
+  let git_sha = Command::new("git")
+    .args(["rev-parse", "--short", "HEAD"])
+    .output()
+    .ok()
+    .filter(|output| output.status.success())
+    .and_then(|output| String::from_utf8(output.stdout).ok())
+    .map(|sha| sha.trim().to_string())
+    .unwrap_or_else(|| "unknown".to_string());
+
+  println!("cargo:rustc-env=FBTOGGL_GIT_SHA={git_sha}");
+  println!(
+    "cargo:rustc-env=FBTOGGL_TARGET={}",
+    std::env::var("TARGET").unwrap_or_default()
+  );
+  println!("cargo:rerun-if-changed=.git/HEAD");
+  println!("cargo:rerun-if-changed=.git/refs");
+}
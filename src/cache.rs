@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::paths;
+
+/// Lifetime of cached responses without 'cache_ttl' in settings.toml
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Responses of rarely changing resources (workspaces, projects, clients),
+/// one JSON file per request which expires `ttl` after it was written
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+  dir: PathBuf,
+  ttl: Duration,
+}
+
+pub fn cache_dir() -> anyhow::Result<PathBuf> {
+  Ok(paths::cache_dir()?.join("responses"))
+}
+
+/// Forgets every cached response, e.g. for --refresh
+pub fn clear() -> anyhow::Result<()> {
+  ResponseCache::new(cache_dir()?, Duration::ZERO).clear()
+}
+
+impl ResponseCache {
+  pub fn new(dir: PathBuf, ttl: Duration) -> ResponseCache {
+    ResponseCache { dir, ttl }
+  }
+
+  /// Cached value of the key, unless it is expired or unreadable
+  pub fn get<D: DeserializeOwned>(&self, key: &str) -> Option<D> {
+    let path = self.dir.join(key);
+
+    let age = SystemTime::now()
+      .duration_since(std::fs::metadata(&path).ok()?.modified().ok()?)
+      .unwrap_or_default();
+
+    if age >= self.ttl {
+      return None;
+    }
+
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+  }
+
+  pub fn put<S: Serialize>(&self, key: &str, value: &S) -> anyhow::Result<()> {
+    std::fs::create_dir_all(&self.dir)?;
+    std::fs::write(self.dir.join(key), serde_json::to_string(value)?)?;
+
+    Ok(())
+  }
+
+  pub fn clear(&self) -> anyhow::Result<()> {
+    if self.dir.exists() {
+      std::fs::remove_dir_all(&self.dir)?;
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::io::BufReader;
+
+use anyhow::anyhow;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::NaiveDateTime;
+use chrono::TimeZone;
+use chrono::Utc;
+use chrono_tz::Tz;
+use ical::parser::ical::component::IcalEvent;
+use ical::property::Property;
+use ical::IcalParser;
+
+#[derive(Debug, PartialEq)]
+pub struct CalendarEvent {
+  pub summary: String,
+  pub start: DateTime<Local>,
+  pub end: DateTime<Local>,
+}
+
+/// Calendar feed from an URL (http, https, webcal) or a local file
+pub fn read_calendar(source: &str) -> anyhow::Result<String> {
+  let url = source.replacen("webcal://", "https://", 1);
+
+  if url.starts_with("http://") || url.starts_with("https://") {
+    let response = minreq::get(url).send()?;
+
+    match response.status_code {
+      200 => Ok(response.as_str()?.to_string()),
+      status => Err(anyhow!("{} - Cannot fetch calendar '{source}'", status)),
+    }
+  } else {
+    Ok(std::fs::read_to_string(source)?)
+  }
+}
+
+/// Events with start and end time, all-day events are left out and
+/// recurring events are not expanded (only the first occurrence is known)
+pub fn parse_events(ics: &str) -> anyhow::Result<Vec<CalendarEvent>> {
+  let mut events = vec![];
+
+  for calendar in IcalParser::new(BufReader::new(ics.as_bytes())) {
+    for event in calendar?.events {
+      if let Some(event) = calendar_event(&event)? {
+        events.push(event);
+      }
+    }
+  }
+
+  Ok(events)
+}
+
+/// The latest started event, if several events overlap
+pub fn ongoing_event(
+  events: &[CalendarEvent],
+  now: DateTime<Local>,
+) -> Option<&CalendarEvent> {
+  events
+    .iter()
+    .filter(|event| event.start <= now && now < event.end)
+    .max_by_key(|event| event.start)
+}
+
+/// Project of the longest keyword contained in the event summary (case-insensitive)
+pub fn mapped_project<'a>(
+  projects: &'a HashMap<String, String>,
+  summary: &str,
+) -> Option<&'a str> {
+  let summary = summary.to_lowercase();
+
+  projects
+    .iter()
+    .filter(|(keyword, _)| summary.contains(&keyword.to_lowercase()))
+    .max_by_key(|(keyword, _)| keyword.len())
+    .map(|(_, project)| project.as_str())
+}
+
+fn calendar_event(event: &IcalEvent) -> anyhow::Result<Option<CalendarEvent>> {
+  let property = |name: &str| {
+    event
+      .properties
+      .iter()
+      .find(|property| property.name == name)
+  };
+
+  let (Some(start), Some(end)) = (property("DTSTART"), property("DTEND"))
+  else {
+    return Ok(None);
+  };
+
+  let (Some(start), Some(end)) = (parse_datetime(start)?, parse_datetime(end)?)
+  else {
+    return Ok(None);
+  };
+
+  let summary = property("SUMMARY")
+    .and_then(|summary| summary.value.to_owned())
+    .unwrap_or_default();
+
+  Ok(Some(CalendarEvent {
+    summary: unescape(&summary),
+    start,
+    end,
+  }))
+}
+
+// "20211122T080000Z" (UTC), "20211122T080000" with TZID parameter or floating (local time),
+// dates without time (all-day events) return None
+fn parse_datetime(
+  property: &Property,
+) -> anyhow::Result<Option<DateTime<Local>>> {
+  let value = property.value.as_deref().unwrap_or_default();
+
+  if !value.contains('T') {
+    return Ok(None);
+  }
+
+  if let Some(value) = value.strip_suffix('Z') {
+    let datetime = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")?;
+
+    return Ok(Some(Utc.from_utc_datetime(&datetime).with_timezone(&Local)));
+  }
+
+  let datetime = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")?;
+
+  let tzid = property.params.as_ref().and_then(|params| {
+    params
+      .iter()
+      .find(|(key, _)| key == "TZID")
+      .and_then(|(_, values)| values.first())
+  });
+
+  let datetime = match tzid {
+    Some(tzid) => {
+      let tz = tzid
+        .parse::<Tz>()
+        .map_err(|err| anyhow!("Unknown TZID '{tzid}': {err}"))?;
+
+      tz.from_local_datetime(&datetime)
+        .earliest()
+        .map(|datetime| datetime.with_timezone(&Local))
+    }
+    None => Local.from_local_datetime(&datetime).earliest(),
+  };
+
+  Ok(datetime)
+}
+
+fn unescape(text: &str) -> String {
+  text
+    .replace("\\n", " ")
+    .replace("\\N", " ")
+    .replace("\\,", ",")
+    .replace("\\;", ";")
+    .replace("\\\\", "\\")
+}
@@ -0,0 +1,86 @@
+use crate::calendar::{mapped_project, ongoing_event, parse_events};
+use chrono::{DateTime, Local};
+use pretty_assertions::assert_eq;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const ICS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//fkbr//EN\r
+BEGIN:VEVENT\r
+UID:1\r
+SUMMARY:Daily Standup\r
+DTSTART:20211122T080000Z\r
+DTEND:20211122T081500Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:2\r
+SUMMARY:Sprint Review\\, beta male gmbh\r
+DTSTART;TZID=Europe/Berlin:20211122T091000\r
+DTEND;TZID=Europe/Berlin:20211122T103000\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:3\r
+SUMMARY:Holiday\r
+DTSTART;VALUE=DATE:20211122\r
+DTEND;VALUE=DATE:20211123\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+#[test]
+fn test_parse_events() -> anyhow::Result<()> {
+  let events = parse_events(ICS)?;
+
+  assert_eq!(events.len(), 2);
+  assert_eq!(events[0].summary, "Daily Standup");
+  assert_eq!(
+    events[0].start,
+    DateTime::<Local>::from_str("2021-11-22T08:00:00Z")?
+  );
+  assert_eq!(events[1].summary, "Sprint Review, beta male gmbh");
+  assert_eq!(
+    events[1].end,
+    DateTime::<Local>::from_str("2021-11-22T10:30:00+01:00")?
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_ongoing_event() -> anyhow::Result<()> {
+  let events = parse_events(ICS)?;
+  let at = |datetime| DateTime::<Local>::from_str(datetime).unwrap();
+
+  assert_eq!(
+    ongoing_event(&events, at("2021-11-22T09:05:00+01:00"))
+      .map(|event| event.summary.as_str()),
+    Some("Daily Standup")
+  );
+  assert_eq!(
+    ongoing_event(&events, at("2021-11-22T09:12:00+01:00"))
+      .map(|event| event.summary.as_str()),
+    Some("Sprint Review, beta male gmbh")
+  );
+  assert_eq!(
+    ongoing_event(&events, at("2021-11-22T11:00:00+01:00")),
+    None
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_mapped_project() {
+  let projects = HashMap::from([
+    ("review".to_string(), "Meetings".to_string()),
+    ("beta male".to_string(), "beta male gmbh".to_string()),
+  ]);
+
+  assert_eq!(
+    mapped_project(&projects, "Sprint Review, beta male gmbh"),
+    Some("beta male gmbh")
+  );
+  assert_eq!(mapped_project(&projects, "Sprint Review"), Some("Meetings"));
+  assert_eq!(mapped_project(&projects, "Lunch"), None);
+}
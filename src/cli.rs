@@ -1,32 +1,323 @@
 use crate::model::Range;
-use chrono::{DateTime, Duration, Local};
-use clap::{Parser, Subcommand, ValueEnum};
+use crate::model::TimeEntryId;
+use anyhow::anyhow;
+use chrono::format::StrftimeItems;
+use chrono::{
+  DateTime, Duration, Local, Locale, NaiveDate, NaiveTime, SecondsFormat,
+  TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use clap::{
+  builder::ValueParser, value_parser, ArgGroup, Command, CommandFactory,
+  FromArgMatches, Parser, Subcommand, ValueEnum,
+};
 use jackdauer::duration;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use terminal_size::{terminal_size, Width};
 
 pub const APP_NAME: &str = "fbtoggl";
 
+const DEFAULT_STALE_AFTER_HOURS: i64 = 12;
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Format of dates in table and raw output (--date-format and --locale),
+/// JSON keeps ISO 8601 dates
+#[derive(Debug, Clone)]
+pub struct DateFormat {
+  format: String,
+  locale: Option<Locale>,
+}
+
+impl Default for DateFormat {
+  fn default() -> Self {
+    DateFormat {
+      format: DEFAULT_DATE_FORMAT.to_string(),
+      locale: None,
+    }
+  }
+}
+
+impl DateFormat {
+  pub fn new(
+    format: Option<String>,
+    locale: Option<String>,
+  ) -> anyhow::Result<DateFormat> {
+    let format = format.unwrap_or_else(|| DEFAULT_DATE_FORMAT.to_string());
+
+    StrftimeItems::new(&format)
+      .parse()
+      .map_err(|_| anyhow!("Invalid date format '{format}'"))?;
+
+    let locale = locale
+      .map(|locale| {
+        Locale::try_from(locale.as_str())
+          .map_err(|_| anyhow!("Unknown locale '{locale}'"))
+      })
+      .transpose()?;
+
+    Ok(DateFormat { format, locale })
+  }
+
+  pub(crate) fn date(&self, date: &NaiveDate) -> String {
+    self.format_with(date, &self.format)
+  }
+
+  /// Name of the weekday in the selected locale
+  pub(crate) fn weekday(&self, date: &NaiveDate) -> String {
+    self.format_with(date, "%A")
+  }
+
+  /// Abbreviated name of the weekday in the selected locale
+  pub(crate) fn short_weekday(&self, date: &NaiveDate) -> String {
+    self.format_with(date, "%a")
+  }
+
+  fn format_with(&self, date: &NaiveDate, format: &str) -> String {
+    match self.locale {
+      Some(locale) => date.format_localized(format, locale).to_string(),
+      None => date.format(format).to_string(),
+    }
+  }
+}
+
+/// Output format and how values are rendered, set up once in main from the
+/// global options and settings.toml
+#[derive(Debug)]
+pub struct Output {
+  pub format: Format,
+
+  /// Tables are not limited to the terminal width (--wide)
+  pub wide: bool,
+
+  /// Timestamps in UTC instead of local time (--utc)
+  pub utc: bool,
+
+  /// Selected via --billable-marker, otherwise the default of the output
+  pub billable_marker: Option<BillableMarker>,
+
+  pub date_format: DateFormat,
+
+  /// Running time entries older than this were probably forgotten
+  pub stale_after_hours: i64,
+
+  /// JSON output collected for an external formatter instead of printed
+  json: Option<RefCell<String>>,
+}
+
+impl Output {
+  /// Output with the default options
+  pub fn new(format: Format) -> Output {
+    Output {
+      format,
+      wide: false,
+      utc: false,
+      billable_marker: None,
+      date_format: DateFormat::default(),
+      stale_after_hours: DEFAULT_STALE_AFTER_HOURS,
+      json: None,
+    }
+  }
+
+  /// JSON output which is collected for an external formatter
+  pub fn collected() -> Output {
+    Output {
+      json: Some(RefCell::default()),
+      ..Output::new(Format::Json)
+    }
+  }
+
+  /// JSON output collected so far
+  pub fn collected_json(&self) -> String {
+    self
+      .json
+      .as_ref()
+      .map(|json| json.borrow().to_owned())
+      .unwrap_or_default()
+  }
+
+  /// Whether a time entry running since `start` was probably forgotten
+  pub(crate) fn is_stale(
+    &self,
+    start: DateTime<Utc>,
+    now: DateTime<Utc>,
+  ) -> bool {
+    now - start > Duration::hours(self.stale_after_hours)
+  }
+
+  pub(crate) fn billable_marker(
+    &self,
+    default: BillableMarker,
+  ) -> BillableMarker {
+    self.billable_marker.unwrap_or(default)
+  }
+
+  /// ISO 8601 timestamp, UTC ('Z') with --utc, otherwise local time with offset
+  pub(crate) fn format_timestamp<Tz: TimeZone>(
+    &self,
+    timestamp: &DateTime<Tz>,
+  ) -> String {
+    format_timestamp_with(timestamp, self.utc)
+  }
+
+  pub(crate) fn format_date(&self, date: &NaiveDate) -> String {
+    self.date_format.date(date)
+  }
+
+  /// Width available for tables, `None` if tables are not limited (--wide or no terminal)
+  pub(crate) fn table_width(&self) -> Option<usize> {
+    if self.wide {
+      return None;
+    }
+
+    terminal_size().map(|(Width(width), _)| width as usize)
+  }
+}
+
 #[derive(Parser)]
 #[command(author, about, version)]
 pub struct Options {
-  #[arg(long, value_enum, default_value_t = Format::Raw)]
-  pub format: Format,
+  /// Output format: 'json', 'raw', 'table' or 'ext:<name>' to pipe the JSON output through a formatter of [formatters] in settings.toml
+  #[arg(long, default_value = "raw", value_parser = parse_format)]
+  pub format: OutputFormat,
 
   /// Show debug information -> log HTTP requests and responses
   #[arg(long)]
   pub debug: bool,
 
+  /// Workspace id or name (default: default workspace). A part of the name prompts a selection, if multiple workspaces match
+  #[arg(long)]
+  pub workspace: Option<String>,
+
+  /// Do not shorten tables to the terminal width
+  #[arg(long)]
+  pub wide: bool,
+
+  /// Render timestamps of JSON and raw output in UTC (default: local time with offset)
+  #[arg(long)]
+  pub utc: bool,
+
+  /// Progress of long operations: 'human' (messages and bars) or 'json' (one event per line on stderr, for wrappers)
+  #[arg(long, value_enum, default_value_t = ProgressFormat::Human)]
+  pub progress: ProgressFormat,
+
+  /// Marker of billable entries: 'words' (BILLABLE/NON_BILLABLE), 'symbol' ($/-, colored in tables) or 'bool' (true/false) (default: words in raw, symbol in tables, also 'billable_marker' in settings.toml)
+  #[arg(long)]
+  pub billable_marker: Option<BillableMarker>,
+
+  /// strftime format of dates in table and raw output, e.g. '%d.%m.%Y' (default: '%Y-%m-%d', also 'date_format' in settings.toml)
+  #[arg(long)]
+  pub date_format: Option<String>,
+
+  /// Locale of week and month names, e.g. 'de_DE' (default: 'en_US', also 'locale' in settings.toml)
+  #[arg(long)]
+  pub locale: Option<String>,
+
+  /// Ignore cached workspaces, projects and clients and fetch them again (lifetime: 'cache_ttl' in settings.toml)
+  #[arg(long)]
+  pub refresh: bool,
+
+  /// Save every (redacted) response into the directory, e.g. to attach it to a bug report
+  #[arg(long, value_name = "DIR", conflicts_with = "replay")]
+  pub record: Option<PathBuf>,
+
+  /// Serve responses recorded via --record from the directory instead of hitting the network
+  #[arg(long, value_name = "DIR")]
+  pub replay: Option<PathBuf>,
+
   #[clap(subcommand)]
   pub subcommand: SubCommand,
 }
 
-#[derive(Debug, Clone, ValueEnum)]
+impl Options {
+  /// Parses the command line, 'this-billing-month' and 'last-billing-month'
+  /// start at `billing_cycle_start_day`
+  pub fn parse_with_billing_cycle(billing_cycle_start_day: u32) -> Options {
+    let matches =
+      with_billing_cycle(Options::command(), billing_cycle_start_day)
+        .get_matches();
+
+    Options::from_arg_matches(&matches).unwrap_or_else(|err| err.exit())
+  }
+}
+
+// every range argument of the command and its subcommands
+pub(crate) fn with_billing_cycle(command: Command, start_day: u32) -> Command {
+  let range = ValueParser::from(value_parser!(Range)).type_id();
+
+  let mut command = command.mut_args(|arg| {
+    if arg.get_value_parser().type_id() != range {
+      return arg;
+    }
+
+    arg.value_parser(move |value: &str| {
+      value
+        .parse::<Range>()
+        .map(|range| range.with_billing_cycle_start_day(start_day))
+    })
+  });
+
+  for subcommand in command.get_subcommands_mut() {
+    *subcommand = with_billing_cycle(std::mem::take(subcommand), start_day);
+  }
+
+  command
+}
+
+#[derive(Debug, Clone, PartialEq, ValueEnum)]
 pub enum Format {
   Json,
   Raw,
   Table,
 }
 
+#[derive(Debug, Clone, PartialEq, ValueEnum)]
+pub enum ProgressFormat {
+  Human,
+  Json,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputFormat {
+  Builtin(Format),
+  /// Name of an external formatter
+  External(String),
+}
+
+pub(crate) fn parse_format(format: &str) -> anyhow::Result<OutputFormat> {
+  match format.strip_prefix("ext:") {
+    Some("") => Err(anyhow!("Name of the formatter is missing, e.g. 'ext:md'")),
+    Some(name) => Ok(OutputFormat::External(name.to_string())),
+    None => Format::from_str(format, true)
+      .map(OutputFormat::Builtin)
+      .map_err(|_| {
+        anyhow!("Unknown format '{format}', use json, raw, table or ext:<name>")
+      }),
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BillableMarker {
+  Words,
+  Symbol,
+  Bool,
+}
+
+#[derive(
+  Debug, Clone, Copy, Default, PartialEq, ValueEnum, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum RoundingMode {
+  #[default]
+  Nearest,
+  Up,
+  Down,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum SubCommand {
   /// (deprecated: use 'fbtoggl settings init') Initialize settings
@@ -47,27 +338,373 @@ pub enum SubCommand {
   #[command(subcommand, about = "Clients (default workspace)")]
   Clients(Clients),
 
+  #[command(subcommand, about = "Tags (default workspace)")]
+  Tags(Tags),
+
+  #[command(subcommand, about = "Tasks of a project (default workspace)")]
+  Tasks(Tasks),
+
   #[command(subcommand, about = "Reports")]
   Reports(Reports),
+
+  #[command(subcommand, about = "Export")]
+  Export(Export),
+
+  /// Logged time per project of two ranges and the difference
+  Compare(Compare),
+
+  /// Time entries of a week with one column per day (Monday to Sunday)
+  Week(Week),
+
+  /// Calendar of a month with every day colored by the logged time
+  Month(Month),
+
+  /// Logged time, billable share and number of time entries per project or client
+  Stats(Stats),
+
+  /// Untracked gaps between consecutive time entries of a workday, --fill creates time entries for them
+  Gaps(Gaps),
+
+  /// Previous descriptions of a time entry, kept by 'time-entries edit --keep-history'
+  History(History),
+
+  /// Replay operations which were done without network connection, lists the ones which failed
+  Sync,
+
+  /// Locations of settings, cache and state (e.g. the offline journal)
+  Paths,
+
+  #[command(subcommand, about = "Shell completions")]
+  Completions(Completions),
+
+  /// Man pages of fbtoggl and every subcommand, e.g. for distribution packages
+  Mangen(Mangen),
+
+  /// Check for problems, e.g. time entries running for more than a day on any device
+  Doctor,
+
+  /// Version of fbtoggl, --verbose adds build information for bug reports
+  Version(Version),
+
+  #[command(
+    subcommand,
+    about = "Breaks (non-billable entries in the break project)"
+  )]
+  Break(Break),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Export {
+  /// Printable timesheet with one row per day and signature lines. Employee and company are read from the [timesheet] section of settings.toml
+  Pdf(ExportPdf),
+
+  /// Backup of the workspace as one JSON document with clients, projects, tags and time entries
+  All(ExportAll),
+}
+
+#[derive(Parser, Debug)]
+pub struct ExportAll {
+  /// Range of the time entries ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  #[arg(long, default_value = "all")]
+  pub range: Range,
+
+  /// Output file
+  #[arg(long, default_value = "fbtoggl-export.json")]
+  pub output: PathBuf,
+
+  /// Comma separated resource kinds to export (default: all)
+  #[arg(long, value_delimiter = ',', conflicts_with = "exclude")]
+  pub include: Vec<ExportResource>,
+
+  /// Comma separated resource kinds to leave out, e.g. 'time-entries' for the structure only
+  #[arg(long, value_delimiter = ',')]
+  pub exclude: Vec<ExportResource>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ExportResource {
+  Clients,
+  Projects,
+  Tags,
+  #[value(alias = "entries")]
+  TimeEntries,
+}
+
+#[derive(Parser, Debug)]
+pub struct ExportPdf {
+  /// Start ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  #[arg(long, default_value = "last-month")]
+  pub range: Range,
+
+  /// Output file
+  #[arg(long, default_value = "timesheet.pdf")]
+  pub output: PathBuf,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Settings {
   /// Initialize settings
   Init,
+
+  /// Report unknown keys (with the closest known key), type errors and syntax errors of settings.toml
+  Validate,
+
+  /// Reset the API token at Toggl, replace it in settings.toml and verify the new one (the old token stops working)
+  RotateToken(RotateToken),
+
+  /// Set a key of settings.toml, e.g. 'workspace_id 1234567' or 'tokens.read <token>', the other keys and comments are kept
+  Set(SetSetting),
+}
+
+#[derive(Parser, Debug)]
+pub struct SetSetting {
+  /// Key, nested keys separated by '.'
+  pub key: String,
+
+  /// Value, numbers and booleans are written as such, everything else as string
+  pub value: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct RotateToken {
+  /// Rotate without confirmation
+  #[arg(long)]
+  pub yes: bool,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Reports {
-  /// Detailed report with violations: more than 10 hours, start before 6am, end after 10pm and pause violations (Arbeitszeitgesetz (ArbZG) § 4 Ruhepausen)
+  /// Detailed report with violations: more than 10 hours, start before 6am, end after 10pm and pause violations (Arbeitszeitgesetz (ArbZG) § 4 Ruhepausen). Rules are configurable in the [compliance] section of settings.toml
   Detailed(Detailed),
+
+  /// Total and billable hours per user
+  Summary(Summary),
+}
+
+#[derive(Parser, Debug)]
+pub struct Summary {
+  /// Start ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  #[arg(long, default_value = "this-week")]
+  pub range: Range,
+
+  /// All members of the workspace instead of yourself (workspace admins only)
+  #[arg(long)]
+  pub team: bool,
+
+  /// Comma separated fields of the output (default: all), without 'billable' the billable hours are not requested at all, 'projects' lists the logged time per project
+  #[arg(long, value_delimiter = ',')]
+  pub fields: Vec<SummaryField>,
+
+  /// Logged time per project, client, tag, day or weekday (with the average per day) instead of per user
+  #[arg(long, value_enum, conflicts_with = "fields")]
+  pub group_by: Option<SummaryGrouping>,
+
+  /// Same as --group-by weekday, kept for existing scripts
+  #[arg(long, hide = true, conflicts_with_all = ["fields", "group_by"])]
+  pub by_weekday: bool,
+
+  /// Amount of the billable hours per project, rate of the project (only with --group-by project)
+  #[arg(long)]
+  pub amounts: bool,
+
+  /// Convert the amounts into this currency, e.g. 'EUR', for a single total instead of one per currency (rates: 'exchange_rates' in settings.toml)
+  #[arg(long, requires = "amounts")]
+  pub convert_to: Option<String>,
+
+  /// Round the total and billable hours to multiples of this increment, e.g. '15m' (default: 'increment' of [rounding] in settings.toml, not with --group-by)
+  #[arg(long, value_parser = parse_duration)]
+  pub round: Option<Duration>,
+
+  /// How durations are rounded (default: 'mode' of [rounding] in settings.toml or nearest)
+  #[arg(long, value_enum)]
+  pub round_mode: Option<RoundingMode>,
+}
+
+impl Summary {
+  /// Selected grouping, --by-weekday included
+  pub fn grouping(&self) -> Option<SummaryGrouping> {
+    if self.by_weekday {
+      Some(SummaryGrouping::Weekday)
+    } else {
+      self.group_by
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum SummaryGrouping {
+  Project,
+  Client,
+  Tag,
+  Day,
+  Weekday,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum SummaryField {
+  User,
+  Total,
+  Billable,
+  Projects,
+}
+
+#[derive(Parser, Debug)]
+pub enum Completions {
+  /// Print the completion script of the shell
+  Script(CompletionScript),
+
+  /// Candidates of the last word (subcommands, options or values like project names), used by the completion scripts
+  #[command(hide = true)]
+  Dynamic(DynamicCompletion),
+}
+
+#[derive(Parser, Debug)]
+pub struct Mangen {
+  /// Directory of the man pages, created if missing
+  #[arg(long, default_value = "man")]
+  pub out_dir: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+pub struct CompletionScript {
+  pub shell: Shell,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum Shell {
+  Bash,
+  Zsh,
+  Fish,
+}
+
+#[derive(Parser, Debug)]
+pub struct DynamicCompletion {
+  /// Words after 'fbtoggl' up to the cursor, the last one is completed
+  #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+  pub words: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct Version {
+  /// Print git sha, target and enabled features as well
+  #[arg(long)]
+  pub verbose: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct Compare {
+  /// Range ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  #[arg(long, default_value = "this-week")]
+  pub range: Range,
+
+  /// Range to compare against, same formats as --range
+  #[arg(long, default_value = "last-week")]
+  pub against: Range,
+}
+
+#[derive(Parser, Debug)]
+pub struct Week {
+  /// Week which contains the start of the range ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', ISO 8601 date '2021-11-01')
+  #[arg(long, default_value = "this-week")]
+  pub range: Range,
+}
+
+#[derive(Parser, Debug)]
+pub struct Gaps {
+  /// Range ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  #[arg(long, default_value = "today")]
+  pub range: Range,
+
+  /// Shortest gap to list, e.g. '10m' or '1h 30m'
+  #[arg(long, value_parser = parse_duration, default_value = "10m")]
+  pub min: Duration,
+
+  /// Ask for a project and description of every gap and create a time entry for it
+  #[arg(long)]
+  pub fill: bool,
+
+  /// Show gaps on saturdays and sundays as well
+  #[arg(long)]
+  pub include_weekends: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct History {
+  /// Id or GUID of the time entry
+  pub id: TimeEntryId,
+}
+
+#[derive(Parser, Debug)]
+pub struct Stats {
+  /// Range ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  #[arg(long, default_value = "this-month")]
+  pub range: Range,
+
+  /// Group by project (with its client) or by client only
+  #[arg(long, value_enum, default_value_t = StatsGrouping::Project)]
+  pub by: StatsGrouping,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum StatsGrouping {
+  Project,
+  Client,
+}
+
+#[derive(Parser, Debug)]
+pub struct Month {
+  /// Month which contains the start of the range ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', ISO 8601 date '2021-11-01')
+  #[arg(long, default_value = "this-month")]
+  pub range: Range,
 }
 
 #[derive(Parser, Debug)]
 pub struct Detailed {
-  /// Start ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  /// Start ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
   #[arg(long, default_value = "today")]
   pub range: Range,
+
+  /// Export one row per user and day (date, start, end, work, break, violations), 'toggl-csv' and 'pdf' are the exports of Toggl (paid plans only)
+  #[arg(long)]
+  pub export: Option<ReportExport>,
+
+  /// Output file of --export (default: stdout, required by 'pdf')
+  #[arg(long, requires = "export")]
+  pub output: Option<PathBuf>,
+
+  /// Continue the last incomplete report of the range instead of fetching every page again
+  #[arg(long)]
+  pub resume: bool,
+
+  /// Comma separated columns of --export (default: all)
+  #[arg(long, value_delimiter = ',', requires = "export")]
+  pub fields: Vec<ReportField>,
+
+  /// Timezone of days, start and end times (IANA name like 'America/New_York', default: local timezone), e.g. the one of the reviewed employee. Not applied to the exports of Toggl
+  #[arg(long)]
+  pub display_tz: Option<Tz>,
+
+  /// Explain every violation with the rule of settings.toml and the threshold which triggered it
+  #[arg(long, conflicts_with = "export")]
+  pub explain: bool,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum ReportExport {
+  Csv,
+  TogglCsv,
+  Pdf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ReportField {
+  User,
+  Date,
+  Start,
+  End,
+  Work,
+  Break,
+  Violations,
 }
 
 #[derive(Subcommand, Debug)]
@@ -81,12 +718,31 @@ pub struct ListProjects {
   /// Include archived projects
   #[arg(long, default_value_t = false)]
   pub include_archived: bool,
+
+  /// Embed the client of each project into the JSON output
+  #[arg(long)]
+  pub resolve: bool,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Projects {
   /// List all projects (default workspace)
   List(ListProjects),
+
+  /// Create a project (default workspace)
+  Create(CreateProject),
+
+  /// List the project templates (default workspace)
+  Templates,
+
+  /// Edit a project (default workspace)
+  Edit(EditProject),
+
+  /// Archive a project (default workspace)
+  Archive(ArchiveProject),
+
+  /// Delete a project (default workspace) after a confirmation
+  Delete(DeleteProject),
 }
 
 #[derive(Parser, Debug)]
@@ -105,17 +761,81 @@ pub enum TimeEntries {
 
   /// Delete time entry
   Delete(DeleteTimeEntry),
+
+  /// Show details of a time entry
+  Details(TimeEntryDetails),
+
+  /// Edit a time entry
+  Edit(EditTimeEntry),
+
+  /// Show the running time entry
+  Current(CurrentTimeEntry),
+
+  /// Start a time entry with project, task, description and tags of an earlier one
+  Continue(ContinueTimeEntry),
+
+  /// Split a time entry at a time of day, the remainder becomes a new time entry with the same project, task, tags and billable flag
+  Split(SplitTimeEntry),
+
+  /// Merge adjacent time entries of the same project into the earliest one and delete the others
+  Merge(MergeTimeEntries),
+
+  /// Change project, tags or billable flag of all matching time entries at once
+  BulkEdit(BulkEditTimeEntries),
+
+  /// Delete all matching time entries after listing them, running ones are kept
+  BulkDelete(BulkDeleteTimeEntries),
 }
 
 #[derive(Parser, Debug)]
 pub struct ListTimeEntries {
-  /// Start ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  /// Start ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
   #[arg(long, default_value = "today")]
   pub range: Range,
 
   /// Show days which have no entry (monday, tuesday, wednesday, thursday and friday only)
   #[arg(long)]
   pub missing: bool,
+
+  /// Show saturdays and sundays without entry as well
+  #[arg(long, requires = "missing")]
+  pub include_weekends: bool,
+
+  /// Show days logged below the expected hours (e.g. 7.5) as well
+  #[arg(long, requires = "missing")]
+  pub expected_hours: Option<f64>,
+
+  /// Only time entries with this tag (case-insensitive), repeatable to list entries with any of the tags
+  #[arg(long = "tag", value_name = "TAG", conflicts_with = "missing")]
+  pub tags: Vec<String>,
+
+  /// Only time entries whose description contains the text (case-insensitive)
+  #[arg(long, conflicts_with = "missing")]
+  pub search: Option<String>,
+
+  /// Treat --search as regular expression
+  #[arg(long, requires = "search")]
+  pub regex: bool,
+
+  /// Only time entries which overlap another one (running ones until now), every overlap is listed below
+  #[arg(long, conflicts_with = "missing")]
+  pub overlaps: bool,
+
+  /// Round the durations to multiples of this increment, e.g. '15m' (default: 'increment' of [rounding] in settings.toml)
+  #[arg(long, value_parser = parse_duration, conflicts_with = "missing")]
+  pub round: Option<Duration>,
+
+  /// How durations are rounded (default: 'mode' of [rounding] in settings.toml or nearest)
+  #[arg(long, value_enum, conflicts_with = "missing")]
+  pub round_mode: Option<RoundingMode>,
+
+  /// Change the durations of the listed time entries to the rounded ones (same start), after a confirmation
+  #[arg(long, conflicts_with = "missing")]
+  pub apply: bool,
+
+  /// Apply the rounding without confirmation
+  #[arg(long, requires = "apply")]
+  pub yes: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -123,6 +843,20 @@ pub struct CreateClient {
   /// Name of the client
   #[arg(long)]
   pub name: String,
+
+  /// Behavior if a client with the name exists already
+  #[arg(long, value_enum, default_value_t = IfExists::Error)]
+  pub if_exists: IfExists,
+}
+
+#[derive(Debug, Clone, PartialEq, ValueEnum)]
+pub enum IfExists {
+  /// Fail and show the existing client
+  Error,
+  /// Show the existing client instead of creating one
+  Ignore,
+  /// Create the client with a numbered suffix, e.g. 'name (2)'
+  Suffix,
 }
 
 fn parse_duration(duration_to_parse: &str) -> anyhow::Result<Duration> {
@@ -130,6 +864,22 @@ fn parse_duration(duration_to_parse: &str) -> anyhow::Result<Duration> {
   Ok(Duration::from_std(duration)?)
 }
 
+pub(crate) fn parse_color(color_to_parse: &str) -> anyhow::Result<String> {
+  let hex = color_to_parse.strip_prefix('#').unwrap_or(color_to_parse);
+
+  if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+    return Err(anyhow!("'{color_to_parse}' is no hex color like '#06aaf5'"));
+  }
+
+  Ok(format!("#{}", hex.to_lowercase()))
+}
+
+fn parse_time_of_day(time_to_parse: &str) -> anyhow::Result<NaiveTime> {
+  NaiveTime::parse_from_str(time_to_parse, "%H:%M:%S")
+    .or_else(|_| NaiveTime::parse_from_str(time_to_parse, "%H:%M"))
+    .map_err(|_| anyhow!("'{time_to_parse}' is no time of day like '12:30'"))
+}
+
 fn parse_time(time_to_parse: &str) -> anyhow::Result<DateTime<Local>> {
   let now = Local::now();
   Ok(htp::parse(time_to_parse, now)?)
@@ -137,11 +887,15 @@ fn parse_time(time_to_parse: &str) -> anyhow::Result<DateTime<Local>> {
 
 #[derive(Parser, Debug)]
 pub struct CreateTimeEntry {
-  /// Name of the project
+  /// Name of the project (default: 'default_project' of settings.toml)
   #[arg(long)]
-  pub project: String,
+  pub project: Option<String>,
+
+  /// Id or name of a task of the project
+  #[arg(long)]
+  pub task: Option<String>,
 
-  /// Description of the timer
+  /// Description of the timer, placeholders {date}, {weekday}, {week} and {branch} are expanded
   #[arg(long)]
   pub description: Option<String>,
 
@@ -180,18 +934,95 @@ pub struct CreateTimeEntry {
   /// Time entry is non-billable
   #[arg(long)]
   pub non_billable: bool,
+
+  /// Time entry is billable, even with 'default_billable = false' in settings.toml
+  #[arg(long, conflicts_with = "non_billable")]
+  pub billable: bool,
+
+  /// Leave out the 'default_tags' of settings.toml
+  #[arg(long)]
+  pub no_default_tags: bool,
+
+  /// If --start and --end are on different days, create one entry per weekday from the start time until the end time
+  #[arg(long, requires = "end")]
+  pub split_by_days: bool,
+
+  /// Create the project if it does not exist yet (always with 'create_missing_projects' in settings.toml)
+  #[arg(long)]
+  pub create_missing_project: bool,
+
+  /// Client (id or name) of a project created by --create-missing-project
+  #[arg(long)]
+  pub client: Option<String>,
+
+  /// Create the time entries of a JSON array (file or '-' for stdin) in the shape of the JSON output, 'project' (name) can replace 'pid'
+  #[arg(
+    long,
+    value_name = "FILE",
+    conflicts_with_all = [
+      "project",
+      "task",
+      "description",
+      "tags",
+      "duration",
+      "end",
+      "lunch_break",
+      "non_billable",
+      "billable",
+      "no_default_tags",
+      "split_by_days",
+      "create_missing_project",
+      "client",
+    ]
+  )]
+  pub from_json: Option<PathBuf>,
+
+  /// Create the time entries of a detailed report CSV exported by the Toggl web app (file or '-' for stdin), e.g. to replay them into another workspace
+  #[arg(
+    long,
+    value_name = "FILE",
+    conflicts_with_all = [
+      "from_json",
+      "project",
+      "task",
+      "description",
+      "tags",
+      "duration",
+      "end",
+      "lunch_break",
+      "non_billable",
+      "billable",
+      "no_default_tags",
+      "split_by_days",
+      "create_missing_project",
+      "client",
+    ]
+  )]
+  pub from_toggl_csv: Option<PathBuf>,
 }
 
 #[derive(Parser, Debug)]
 pub struct StartTimeEntry {
-  /// Name of the project
+  /// Name of the project (default: the first matching [[project_rules]], otherwise 'default_project' of settings.toml)
   #[arg(long)]
-  pub project: String,
+  pub project: Option<String>,
+
+  /// Id or name of a task of the project
+  #[arg(long)]
+  pub task: Option<String>,
 
-  /// Description of the timer
+  /// Description of the timer, placeholders {date}, {weekday}, {week} and {branch} are expanded
   #[arg(long)]
   pub description: Option<String>,
 
+  /// Calendar feed (URL or file), the currently ongoing event sets the description and the project ([calendar.projects] in settings.toml, otherwise --project)
+  #[arg(long, conflicts_with = "description")]
+  pub from_ical: Option<String>,
+
+  /// Select one of the 10 most recent project and description pairs to start it again, --task and --tags replace the ones of the selection
+  #[arg(long, conflicts_with_all = ["project", "description", "from_ical"])]
+  pub recent: bool,
+
   /// Tags
   #[arg(long)]
   pub tags: Option<Vec<String>>,
@@ -199,27 +1030,421 @@ pub struct StartTimeEntry {
   /// Time entry is non-billable
   #[arg(long)]
   pub non_billable: bool,
+
+  /// Time entry is billable, even with 'default_billable = false' in settings.toml
+  #[arg(long, conflicts_with = "non_billable")]
+  pub billable: bool,
+
+  /// Leave out the 'default_tags' of settings.toml
+  #[arg(long)]
+  pub no_default_tags: bool,
+
+  /// Create the project if it does not exist yet (always with 'create_missing_projects' in settings.toml)
+  #[arg(long)]
+  pub create_missing_project: bool,
+
+  /// Client (id or name) of a project created by --create-missing-project
+  #[arg(long)]
+  pub client: Option<String>,
+
+  /// Id or GUID of a time entry, the new one starts when it stopped (a running one is stopped first) for contiguous timesheets
+  #[arg(long)]
+  pub after: Option<TimeEntryId>,
+
+  /// Wait in the foreground and notify once the time entry runs longer than the duration ('45 minutes', '1 hour')
+  #[arg(long, value_parser = parse_duration)]
+  pub alarm: Option<Duration>,
+
+  /// Stop the time entry when the alarm goes off
+  #[arg(long, requires = "alarm")]
+  pub auto_stop: bool,
+
+  /// Count down the interval in the foreground and stop the time entry afterwards (default: '25 minutes')
+  #[arg(
+    long,
+    value_parser = parse_duration,
+    num_args = 0..=1,
+    default_missing_value = "25 minutes",
+    conflicts_with = "alarm"
+  )]
+  pub pomodoro: Option<Duration>,
+
+  /// Show a desktop notification when the pomodoro is over (notify-send on Linux, osascript on macOS)
+  #[arg(long, requires = "pomodoro")]
+  pub notify: bool,
 }
 
 #[derive(Parser, Debug)]
 pub struct StopTimeEntry {
-  /// Id of the time entry
+  /// Id or GUID of the time entry (default: the running time entry, exits with 3 if none is running)
+  #[arg(long)]
+  pub id: Option<TimeEntryId>,
+}
+
+#[derive(Parser, Debug)]
+pub struct CurrentTimeEntry {
+  /// Print nothing, exit with 0 if a time entry is running, 1 if not (2 on errors)
   #[arg(long)]
-  pub id: u64,
+  pub check: bool,
+
+  /// Show the elapsed time, project and description in place every second until interrupted
+  #[arg(long, conflicts_with = "check")]
+  pub watch: bool,
 }
 
 #[derive(Parser, Debug)]
 pub struct DeleteTimeEntry {
-  /// Id of the time entry
+  /// Id or GUID of the time entry
+  #[arg(long, required_unless_present = "interactive")]
+  pub id: Option<TimeEntryId>,
+
+  /// Select the time entries to delete from a list
+  #[arg(long, conflicts_with = "id")]
+  pub interactive: bool,
+
+  /// Range of the selectable time entries ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  #[arg(long, default_value = "today", requires = "interactive")]
+  pub range: Range,
+
+  /// Delete the running time entry as well, its elapsed time is lost
+  #[arg(long, conflicts_with = "stop_first")]
+  pub force: bool,
+
+  /// Stop the running time entry (showing its duration) before deleting it
   #[arg(long)]
-  pub id: u64,
+  pub stop_first: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct ContinueTimeEntry {
+  /// Id or GUID of the time entry (default: select one of the last time entries)
+  #[arg(long)]
+  pub id: Option<TimeEntryId>,
+
+  /// Number of time entries to select from
+  #[arg(long, default_value_t = 10, conflicts_with = "id")]
+  pub last: usize,
 }
 
 #[derive(Parser, Debug)]
 pub struct TimeEntryDetails {
-  /// Id of the time entry
+  /// Id or GUID of the time entry
+  #[arg(long)]
+  pub id: TimeEntryId,
+
+  /// Show more details (e.g. the GUID)
+  #[arg(long)]
+  pub verbose: bool,
+
+  /// Also show the previous and next time entry of the same day and the ones overlapping this one
+  #[arg(long)]
+  pub related: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct EditTimeEntry {
+  /// Id or GUID of the time entry
+  #[arg(long)]
+  pub id: TimeEntryId,
+
+  /// Name of the project
+  #[arg(long)]
+  pub project: Option<String>,
+
+  /// Description of the timer, placeholders {date}, {weekday}, {week} and {branch} are expanded
+  #[arg(long)]
+  pub description: Option<String>,
+
+  /// Tags
+  #[arg(long)]
+  pub tags: Option<Vec<String>>,
+
+  /// Toggle billable flag of the time entry
+  #[arg(long, conflicts_with_all = ["billable", "non_billable"])]
+  pub toggle_billable: bool,
+
+  /// Mark the time entry as billable, regardless of its current state
+  #[arg(long, conflicts_with = "non_billable")]
+  pub billable: bool,
+
+  /// Mark the time entry as non-billable, regardless of its current state
+  #[arg(long)]
+  pub non_billable: bool,
+
+  /// Billable rate override of the time entry (per hour)
+  #[arg(long)]
+  pub rate: Option<f64>,
+
+  /// Keep the previous description in the local history (see 'fbtoggl history'), default of 'description_history' in settings.toml
   #[arg(long)]
-  pub id: u64,
+  pub keep_history: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct SplitTimeEntry {
+  /// Id or GUID of the time entry
+  pub id: TimeEntryId,
+
+  /// Time of day to split at ('12:30' or '12:30:15')
+  #[arg(long, value_parser = parse_time_of_day)]
+  pub at: NaiveTime,
+}
+
+#[derive(Parser, Debug)]
+pub struct MergeTimeEntries {
+  /// Ids or GUIDs of the time entries
+  #[arg(required = true, num_args = 2..)]
+  pub ids: Vec<TimeEntryId>,
+
+  /// Longest gap between two time entries which are still adjacent ('1 minute', '5 minutes')
+  #[arg(long, value_parser = parse_duration, default_value = "1 minute")]
+  pub tolerance: Duration,
+}
+
+#[derive(Parser, Debug)]
+#[command(group(
+  ArgGroup::new("changes")
+    .required(true)
+    .multiple(true)
+    .args(["set_project", "add_tag", "remove_tag", "set_billable", "set_non_billable"])
+))]
+pub struct BulkEditTimeEntries {
+  /// Start ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  #[arg(long)]
+  pub range: Range,
+
+  /// Only time entries of this project
+  #[arg(long)]
+  pub project: Option<String>,
+
+  /// Only time entries with this tag (case-insensitive), repeatable to change entries with any of the tags
+  #[arg(long, value_name = "TAG")]
+  pub filter_tag: Vec<String>,
+
+  /// Move the time entries to this project
+  #[arg(long)]
+  pub set_project: Option<String>,
+
+  /// Add the tag, repeatable
+  #[arg(long, value_name = "TAG")]
+  pub add_tag: Vec<String>,
+
+  /// Remove the tag, repeatable
+  #[arg(long, value_name = "TAG")]
+  pub remove_tag: Vec<String>,
+
+  /// Mark the time entries as billable (except those of 'non_billable_projects')
+  #[arg(long, conflicts_with = "set_non_billable")]
+  pub set_billable: bool,
+
+  /// Mark the time entries as non-billable
+  #[arg(long)]
+  pub set_non_billable: bool,
+
+  /// Change without confirmation
+  #[arg(long)]
+  pub yes: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct BulkDeleteTimeEntries {
+  /// Start ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  #[arg(long)]
+  pub range: Range,
+
+  /// Only time entries of this project
+  #[arg(long)]
+  pub project: Option<String>,
+
+  /// Only time entries with this tag (case-insensitive), repeatable to delete entries with any of the tags
+  #[arg(long, value_name = "TAG")]
+  pub filter_tag: Vec<String>,
+
+  /// Only duplicates (same start, duration, project and description) of an earlier time entry, e.g. of an import run twice
+  #[arg(long)]
+  pub duplicates: bool,
+
+  /// Delete without confirmation
+  #[arg(long)]
+  pub yes: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct CreateProject {
+  /// Name of the project
+  #[arg(long)]
+  pub name: String,
+
+  /// Id or name of the client the project belongs to
+  #[arg(long)]
+  pub client: Option<String>,
+
+  /// Id or name of a project template (see 'projects templates') to copy the settings and tasks from
+  #[arg(long)]
+  pub from_template: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct EditProject {
+  /// Id or name of the project
+  #[arg(long)]
+  pub id: String,
+
+  /// New name of the project
+  #[arg(long)]
+  pub name: Option<String>,
+
+  /// Id or name of the client the project belongs to
+  #[arg(long)]
+  pub client: Option<String>,
+
+  /// Project is billable (true or false)
+  #[arg(long)]
+  pub billable: Option<bool>,
+
+  /// Color of the project as hex code (e.g. '#06aaf5')
+  #[arg(long, value_parser = parse_color)]
+  pub color: Option<String>,
+
+  /// Archive the project
+  #[arg(long)]
+  pub archive: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct ArchiveProject {
+  /// Id or name of the project
+  #[arg(long, visible_alias = "name")]
+  pub id: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct DeleteProject {
+  /// Id or name of the project (archived projects included)
+  #[arg(long, visible_alias = "name")]
+  pub id: String,
+
+  /// Delete without confirmation
+  #[arg(long)]
+  pub yes: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct ClientDetails {
+  /// Id or name of the client
+  #[arg(long, visible_alias = "name")]
+  pub id: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct DeleteClient {
+  /// Id or name of the client
+  #[arg(long, visible_alias = "name")]
+  pub id: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct EditClient {
+  /// Id or name of the client
+  #[arg(long)]
+  pub id: String,
+
+  /// New name of the client
+  #[arg(long)]
+  pub name: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct ArchiveClient {
+  /// Id or name of the client
+  #[arg(long, visible_alias = "name")]
+  pub id: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct RestoreClient {
+  /// Id or name of the archived client
+  #[arg(long, visible_alias = "name")]
+  pub id: String,
+
+  /// Restore the projects of the client as well
+  #[arg(long)]
+  pub with_projects: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Tags {
+  /// List all tags (default workspace)
+  List,
+
+  /// Create tag (in default workspace)
+  Create(CreateTag),
+
+  /// Rename tag (in default workspace)
+  Rename(RenameTag),
+
+  /// Delete tag (in default workspace), time entries lose the tag
+  Delete(DeleteTag),
+}
+
+#[derive(Parser, Debug)]
+pub struct CreateTag {
+  /// Name of the tag
+  #[arg(long)]
+  pub name: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct RenameTag {
+  /// Id or name of the tag
+  #[arg(long, visible_aliases = ["name", "from"])]
+  pub id: String,
+
+  /// New name of the tag
+  #[arg(long)]
+  pub to: String,
+
+  /// Move the time entries of --range to the new tag, also if it exists already. The old tag is deleted if the range is 'all'
+  #[arg(long)]
+  pub migrate: bool,
+
+  /// Time entries to migrate ('today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', 'this-billing-month', 'last-billing-month', 'all', ISO 8601 date '2021-11-01'), ISO 8601 date range '2021-11-01|2021-11-02')
+  #[arg(long, default_value = "all", requires = "migrate")]
+  pub range: Range,
+}
+
+#[derive(Parser, Debug)]
+pub struct DeleteTag {
+  /// Id or name of the tag
+  #[arg(long, visible_alias = "name")]
+  pub id: String,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Tasks {
+  /// List the active tasks of a project (default workspace)
+  List(ListTasks),
+
+  /// Create task in a project (default workspace)
+  Create(CreateTask),
+}
+
+#[derive(Parser, Debug)]
+pub struct ListTasks {
+  /// Id or name of the project
+  #[arg(long)]
+  pub project: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct CreateTask {
+  /// Id or name of the project
+  #[arg(long)]
+  pub project: String,
+
+  /// Name of the task
+  #[arg(long)]
+  pub name: String,
 }
 
 #[derive(Parser, Debug)]
@@ -229,6 +1454,22 @@ pub struct ListClients {
   pub include_archived: bool,
 }
 
+#[derive(Parser, Debug)]
+pub enum Break {
+  /// Stop the running time entry and start a break
+  Start,
+
+  /// Stop the running break
+  Stop(StopBreak),
+}
+
+#[derive(Parser, Debug)]
+pub struct StopBreak {
+  /// Continue the last time entry before the break
+  #[arg(long)]
+  pub resume: bool,
+}
+
 #[derive(Parser, Debug)]
 pub enum Clients {
   /// List all clients (default workspace)
@@ -236,12 +1477,119 @@ pub enum Clients {
 
   /// Create client (in default workspace)
   Create(CreateClient),
+
+  /// Show client (in default workspace)
+  Show(ClientDetails),
+
+  /// Delete client (in default workspace)
+  Delete(DeleteClient),
+
+  /// Rename client (in default workspace)
+  Edit(EditClient),
+
+  /// Archive client and its projects (in default workspace)
+  Archive(ArchiveClient),
+
+  /// Restore archived client (in default workspace)
+  Restore(RestoreClient),
 }
 
-pub(crate) fn output_values_json<T: Serialize>(values: &[T]) {
+pub(crate) fn output_values_json<T: Serialize>(output: &Output, values: &[T]) {
   for value in values {
-    if let Ok(output) = serde_json::to_string_pretty(&value) {
-      println!("{output}");
+    let Ok(mut value) = serde_json::to_value(value) else {
+      continue;
+    };
+
+    normalize_timestamps(&mut value, output.utc);
+
+    if let Ok(json) = serde_json::to_string_pretty(&value) {
+      match &output.json {
+        Some(collected) => {
+          let mut collected = collected.borrow_mut();
+          collected.push_str(&json);
+          collected.push('\n');
+        }
+        None => println!("{json}"),
+      }
+    }
+  }
+}
+
+/// Keys of the RFC 3339 timestamps in the JSON output
+const TIMESTAMP_KEYS: [&str; 7] = [
+  "start",
+  "stop",
+  "end",
+  "at",
+  "created_at",
+  "changed_at",
+  "server_deleted_at",
+];
+
+/// API fields are UTC while computed fields are local time, every timestamp
+/// field is rendered the same way, other strings are left alone
+pub(crate) fn normalize_timestamps(value: &mut Value, utc: bool) {
+  match value {
+    Value::Array(values) => values
+      .iter_mut()
+      .for_each(|value| normalize_timestamps(value, utc)),
+    Value::Object(values) => {
+      for (key, value) in values.iter_mut() {
+        match value {
+          Value::String(text) if TIMESTAMP_KEYS.contains(&key.as_str()) => {
+            if let Ok(timestamp) = DateTime::parse_from_rfc3339(text) {
+              *text = format_timestamp_with(&timestamp, utc);
+            }
+          }
+          _ => normalize_timestamps(value, utc),
+        }
+      }
     }
+    _ => {}
+  }
+}
+
+/// Colorless billable marker
+pub(crate) fn format_billable(
+  billable: bool,
+  marker: BillableMarker,
+) -> String {
+  match (marker, billable) {
+    (BillableMarker::Words, true) => "BILLABLE",
+    (BillableMarker::Words, false) => "NON_BILLABLE",
+    (BillableMarker::Symbol, true) => "$",
+    (BillableMarker::Symbol, false) => "-",
+    (BillableMarker::Bool, true) => "true",
+    (BillableMarker::Bool, false) => "false",
+  }
+  .to_string()
+}
+
+fn format_timestamp_with<Tz: TimeZone>(
+  timestamp: &DateTime<Tz>,
+  utc: bool,
+) -> String {
+  if utc {
+    timestamp
+      .with_timezone(&Utc)
+      .to_rfc3339_opts(SecondsFormat::Secs, true)
+  } else {
+    timestamp
+      .with_timezone(&Local)
+      .to_rfc3339_opts(SecondsFormat::Secs, false)
+  }
+}
+
+/// Shortens text to `max_width` characters, marking the cut with an ellipsis
+pub(crate) fn truncate(text: &str, max_width: usize) -> String {
+  if text.chars().count() <= max_width {
+    text.to_string()
+  } else {
+    let mut truncated = text
+      .chars()
+      .take(max_width.saturating_sub(1))
+      .collect::<String>();
+    truncated.push('…');
+    truncated
   }
 }
@@ -0,0 +1,137 @@
+use crate::cli::{
+  format_billable, normalize_timestamps, parse_color, with_billing_cycle,
+  BillableMarker, DateFormat, Options, Reports, SubCommand, SummaryGrouping,
+  TimeEntries,
+};
+use crate::model::Range;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test]
+fn test_normalize_timestamps_to_utc() {
+  let mut value = json!({
+    "id": 1,
+    "start": "2021-11-22T08:00:00+01:00",
+    "stop": "2021-11-22T09:30:00Z",
+    "date": "2021-11-22",
+    "description": "fkbr"
+  });
+
+  normalize_timestamps(&mut value, true);
+
+  assert_eq!(
+    value,
+    json!({
+      "id": 1,
+      "start": "2021-11-22T07:00:00Z",
+      "stop": "2021-11-22T09:30:00Z",
+      "date": "2021-11-22",
+      "description": "fkbr"
+    })
+  );
+}
+
+#[test]
+fn test_normalize_timestamps_keeps_other_strings() {
+  let mut value = json!([{
+    "start": "2024-01-01T11:00:00+01:00",
+    "description": "2024-01-01T10:00:00Z",
+    "tags": ["2024-01-01T10:00:00+01:00"]
+  }]);
+
+  normalize_timestamps(&mut value, true);
+
+  assert_eq!(
+    value,
+    json!([{
+      "start": "2024-01-01T10:00:00Z",
+      "description": "2024-01-01T10:00:00Z",
+      "tags": ["2024-01-01T10:00:00+01:00"]
+    }])
+  );
+}
+
+#[test]
+fn test_format_billable() {
+  assert_eq!(format_billable(true, BillableMarker::Words), "BILLABLE");
+  assert_eq!(
+    format_billable(false, BillableMarker::Words),
+    "NON_BILLABLE"
+  );
+  assert_eq!(format_billable(true, BillableMarker::Symbol), "$");
+  assert_eq!(format_billable(false, BillableMarker::Symbol), "-");
+  assert_eq!(format_billable(true, BillableMarker::Bool), "true");
+  assert_eq!(format_billable(false, BillableMarker::Bool), "false");
+}
+
+#[test]
+fn test_parse_color() -> anyhow::Result<()> {
+  assert_eq!(parse_color("#06AAF5")?, "#06aaf5");
+  assert_eq!(parse_color("06aaf5")?, "#06aaf5");
+
+  assert!(parse_color("#06aaf").is_err());
+  assert!(parse_color("blue").is_err());
+
+  Ok(())
+}
+
+fn summary_grouping(args: &[&str]) -> anyhow::Result<Option<SummaryGrouping>> {
+  let options = Options::try_parse_from(
+    ["fbtoggl", "reports", "summary"].iter().chain(args),
+  )?;
+
+  match options.subcommand {
+    SubCommand::Reports(Reports::Summary(summary)) => Ok(summary.grouping()),
+    subcommand => panic!("Unexpected subcommand {subcommand:?}"),
+  }
+}
+
+#[test]
+fn test_summary_by_weekday() -> anyhow::Result<()> {
+  assert_eq!(summary_grouping(&[])?, None);
+  assert_eq!(
+    summary_grouping(&["--group-by", "tag"])?,
+    Some(SummaryGrouping::Tag)
+  );
+  assert_eq!(
+    summary_grouping(&["--by-weekday"])?,
+    Some(SummaryGrouping::Weekday)
+  );
+  assert!(summary_grouping(&["--by-weekday", "--group-by", "tag"]).is_err());
+
+  Ok(())
+}
+
+#[test]
+fn test_billing_cycle_of_ranges() -> anyhow::Result<()> {
+  let matches = with_billing_cycle(Options::command(), 15)
+    .try_get_matches_from([
+      "fbtoggl",
+      "time-entries",
+      "list",
+      "--range",
+      "last-billing-month",
+    ])?;
+
+  match Options::from_arg_matches(&matches)?.subcommand {
+    SubCommand::TimeEntries(TimeEntries::List(list)) => {
+      assert!(matches!(list.range, Range::LastBillingMonth(15)))
+    }
+    subcommand => panic!("Unexpected subcommand {subcommand:?}"),
+  }
+
+  Ok(())
+}
+
+#[test]
+fn test_localized_weekdays() -> anyhow::Result<()> {
+  let monday = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+  let date_format = DateFormat::new(None, Some("de_DE".to_string()))?;
+
+  assert_eq!(date_format.weekday(&monday), "Montag");
+  assert_eq!(date_format.short_weekday(&monday), "Mo");
+  assert_eq!(DateFormat::default().short_weekday(&monday), "Mon");
+
+  Ok(())
+}
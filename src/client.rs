@@ -1,49 +1,332 @@
 use std::fmt::Debug;
+use std::rc::Rc;
 
-use crate::config::read_settings;
+use crate::cache::cache_dir;
+use crate::cache::ResponseCache;
+use crate::config::Settings;
+use crate::http_client::api_tokens;
+use crate::http_client::empty_response;
+use crate::http_client::is_network_error;
+use crate::http_client::json_response;
+use crate::http_client::ApiTokens;
+use crate::http_client::Fixtures;
+use crate::http_client::HttpClient;
+use crate::model::BulkEditResult;
 use crate::model::Client;
 use crate::model::Me;
+use crate::model::PatchOperation;
 use crate::model::Project;
+use crate::model::ProjectUpdate;
 use crate::model::Range;
+use crate::model::Tag;
+use crate::model::Task;
 use crate::model::TimeEntry;
 use crate::model::Workspace;
-use anyhow::anyhow;
-use base64::engine::general_purpose::STANDARD;
-use base64::Engine;
+use crate::model::WorkspaceUser;
+use crate::resolver::Resolver;
 use chrono::DateTime;
+use chrono::Datelike;
 use chrono::Duration;
 use chrono::Local;
+use chrono::NaiveDate;
+use chronoutil::shift_months;
 use colored::Colorize;
+use indicatif::ProgressBar;
+use indicatif::ProgressStyle;
+use itertools::Itertools;
 use minreq::Method;
-use minreq::Request;
-use minreq::Response;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::json;
+#[cfg(test)]
 use url::Url;
 
 pub struct TogglClient {
-  base_url: Url,
-  api_token: String,
+  http_client: HttpClient,
+  workspace: Option<String>,
+  cache: Option<ResponseCache>,
+  settings: Rc<Settings>,
 }
 
-pub const CREATED_WITH: &str = "fbtoggl (https://github.com/icepuma/fbtoggl)";
+pub const CREATED_WITH: &str = concat!(
+  "fbtoggl/",
+  env!("CARGO_PKG_VERSION"),
+  " (https://github.com/icepuma/fbtoggl)"
+);
 
-const AUTHORIZATION: &str = "Authorization";
+/// Time entries per bulk edit request of the API
+const BULK_EDIT_LIMIT: usize = 100;
 
-pub fn init_client() -> anyhow::Result<TogglClient> {
-  let settings = read_settings()?;
+pub fn init_client(
+  workspace: &Option<String>,
+  fixtures: &Fixtures,
+  settings: Rc<Settings>,
+) -> anyhow::Result<TogglClient> {
+  let api_tokens = api_tokens(&settings);
 
-  TogglClient::new(settings.api_token)
+  let mut client = TogglClient::new(api_tokens, fixtures.to_owned())?;
+  client.workspace = workspace.to_owned();
+
+  // recording and replaying fixtures needs every request to be sent
+  if let Fixtures::None = fixtures {
+    let ttl = settings.cache_ttl()?;
+
+    if !ttl.is_zero() {
+      client.cache = Some(ResponseCache::new(cache_dir()?, ttl));
+    }
+  }
+
+  client.settings = settings;
+
+  Ok(client)
+}
+
+/// Toggl Track API as used by the commands, allows to swap the HTTP client
+/// for other backends (e.g. fakes in tests)
+pub trait TogglApi {
+  /// Workspace (id or name) selected via --workspace
+  fn workspace(&self) -> Option<&str>;
+
+  /// Same client, but with another workspace selection
+  fn with_workspace(&self, workspace: Option<String>) -> Box<dyn TogglApi>;
+
+  /// Settings of the run, e.g. aliases and the default workspace
+  fn settings(&self) -> Rc<Settings>;
+
+  fn get_workspace_clients(
+    &self,
+    debug: bool,
+    include_archived: bool,
+    workspace_id: u64,
+  ) -> anyhow::Result<Option<Vec<Client>>>;
+
+  fn get_workspace_tags(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+  ) -> anyhow::Result<Vec<Tag>>;
+
+  fn create_tag(
+    &self,
+    debug: bool,
+    name: &str,
+    workspace_id: u64,
+  ) -> anyhow::Result<Tag>;
+
+  fn update_tag(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    tag_id: u64,
+    name: &str,
+  ) -> anyhow::Result<Tag>;
+
+  fn delete_tag(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    tag_id: u64,
+  ) -> anyhow::Result<()>;
+
+  fn get_project_tasks(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    project_id: u64,
+  ) -> anyhow::Result<Vec<Task>>;
+
+  fn create_task(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    project_id: u64,
+    name: &str,
+  ) -> anyhow::Result<Task>;
+
+  fn get_time_entries(
+    &self,
+    debug: bool,
+    range: &Range,
+  ) -> anyhow::Result<Vec<TimeEntry>>;
+
+  fn get_workspaces(&self, debug: bool) -> anyhow::Result<Vec<Workspace>>;
+
+  fn get_me(&self, debug: bool) -> anyhow::Result<Me>;
+
+  /// Invalidates the API token of the request and returns a new one
+  fn reset_api_token(&self, debug: bool) -> anyhow::Result<String>;
+
+  fn get_workspace_users(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+  ) -> anyhow::Result<Vec<WorkspaceUser>>;
+
+  fn get_workspace_projects(
+    &self,
+    debug: bool,
+    include_archived: bool,
+    workspace_id: u64,
+  ) -> anyhow::Result<Vec<Project>>;
+
+  /// Active project templates of the workspace
+  fn get_project_templates(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+  ) -> anyhow::Result<Vec<Project>>;
+
+  fn create_project(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    name: &str,
+    client_id: Option<u64>,
+    template_id: Option<u64>,
+  ) -> anyhow::Result<Project>;
+
+  fn update_project(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    project_id: u64,
+    project: &ProjectUpdate,
+  ) -> anyhow::Result<Project>;
+
+  fn delete_project(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    project_id: u64,
+  ) -> anyhow::Result<()>;
+
+  #[allow(clippy::too_many_arguments)]
+  fn create_time_entry(
+    &self,
+    debug: bool,
+    description: &Option<String>,
+    workspace_id: u64,
+    tags: &Option<Vec<String>>,
+    duration: Duration,
+    start: DateTime<Local>,
+    project_id: u64,
+    task_id: Option<u64>,
+    non_billable: bool,
+  ) -> anyhow::Result<TimeEntry>;
+
+  fn create_client(
+    &self,
+    debug: bool,
+    name: &str,
+    workspace_id: u64,
+  ) -> anyhow::Result<Client>;
+
+  fn get_client(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    client_id: u64,
+  ) -> anyhow::Result<Client>;
+
+  fn delete_client(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    client_id: u64,
+  ) -> anyhow::Result<()>;
+
+  fn update_client(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    client_id: u64,
+    name: &str,
+  ) -> anyhow::Result<Client>;
+
+  /// Archives the client and its projects
+  fn archive_client(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    client_id: u64,
+  ) -> anyhow::Result<()>;
+
+  /// Restores the client, its projects only with `restore_projects`
+  fn restore_client(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    client_id: u64,
+    restore_projects: bool,
+  ) -> anyhow::Result<Client>;
+
+  #[allow(clippy::too_many_arguments)]
+  fn start_time_entry(
+    &self,
+    debug: bool,
+    start: DateTime<Local>,
+    workspace_id: u64,
+    description: &Option<String>,
+    tags: &Option<Vec<String>>,
+    project_id: u64,
+    task_id: Option<u64>,
+    non_billable: bool,
+  ) -> anyhow::Result<TimeEntry>;
+
+  fn stop_time_entry(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    time_entry_id: u64,
+  ) -> anyhow::Result<TimeEntry>;
+
+  fn delete_time_entry(
+    &self,
+    debug: bool,
+    time_entry_id: u64,
+  ) -> anyhow::Result<()>;
+
+  fn get_time_entry(
+    &self,
+    debug: bool,
+    time_entry_id: u64,
+  ) -> anyhow::Result<TimeEntry>;
+
+  /// Running time entry, if any
+  fn get_current_time_entry(
+    &self,
+    debug: bool,
+  ) -> anyhow::Result<Option<TimeEntry>>;
+
+  fn update_time_entry(
+    &self,
+    debug: bool,
+    time_entry: &TimeEntry,
+  ) -> anyhow::Result<TimeEntry>;
+
+  /// Applies the operations to every time entry, in requests of at most
+  /// 100 time entries
+  fn bulk_edit_time_entries(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    time_entry_ids: &[u64],
+    operations: &[PatchOperation],
+  ) -> anyhow::Result<BulkEditResult>;
 }
 
 impl TogglClient {
-  pub fn new(api_token: String) -> anyhow::Result<TogglClient> {
+  pub fn new(
+    api_tokens: ApiTokens,
+    fixtures: Fixtures,
+  ) -> anyhow::Result<TogglClient> {
     let base_url = "https://api.track.toggl.com/api/v9/".parse()?;
 
     Ok(TogglClient {
-      base_url,
-      api_token,
+      http_client: HttpClient::new(base_url, api_tokens, fixtures),
+      workspace: None,
+      cache: None,
+      settings: Rc::default(),
     })
   }
 
@@ -53,46 +336,111 @@ impl TogglClient {
     base_url: Url,
   ) -> anyhow::Result<TogglClient> {
     Ok(TogglClient {
-      base_url,
-      api_token,
+      http_client: HttpClient::new(base_url, api_token.into(), Fixtures::None)
+        .with_retry_delay(std::time::Duration::ZERO),
+      workspace: None,
+      cache: None,
+      settings: Rc::default(),
     })
   }
 
-  fn basic_auth(&self) -> (String, String) {
-    (
-      AUTHORIZATION.to_string(),
-      format!(
-        "Basic {}",
-        STANDARD.encode(format!("{}:api_token", &self.api_token))
-      ),
-    )
+  #[cfg(test)]
+  pub fn new_with_fixtures(
+    api_token: String,
+    base_url: Url,
+    fixtures: Fixtures,
+  ) -> anyhow::Result<TogglClient> {
+    Ok(TogglClient {
+      http_client: HttpClient::new(base_url, api_token.into(), fixtures)
+        .with_retry_delay(std::time::Duration::ZERO),
+      workspace: None,
+      cache: None,
+      settings: Rc::default(),
+    })
+  }
+
+  #[cfg(test)]
+  pub fn with_cache(mut self, cache: ResponseCache) -> TogglClient {
+    self.cache = Some(cache);
+    self
+  }
+
+  #[cfg(test)]
+  pub fn with_selected_workspace(mut self, workspace: &str) -> TogglClient {
+    self.workspace = Some(workspace.to_string());
+    self
   }
 
-  fn base_request(&self, method: Method, uri: &str) -> anyhow::Result<Request> {
-    let url = self.base_url.join(uri)?;
+  /// Resolves --workspace once to its id, so later lookups of the same
+  /// command do not prompt again for an ambiguous name
+  pub fn pin_workspace(mut self, debug: bool) -> anyhow::Result<TogglClient> {
+    if self.workspace.is_none() {
+      return Ok(self);
+    }
 
-    let (key, value) = self.basic_auth();
+    match Resolver::new(debug, &self).workspace_id() {
+      Ok(workspace_id) => self.workspace = Some(workspace_id.to_string()),
+      // offline the selector is journaled and resolved on replay
+      Err(err) if is_network_error(&err) => {}
+      Err(err) => return Err(err),
+    }
 
-    Ok(minreq::Request::new(method, url).with_header(key, value))
+    Ok(self)
   }
 
-  fn request<D: DeserializeOwned + Debug>(
+  /// GET request which is answered from the cache while it is fresh
+  fn cached_request<D: DeserializeOwned + Serialize + Debug>(
     &self,
     debug: bool,
-    method: Method,
     uri: &str,
   ) -> anyhow::Result<D> {
-    let request = self.base_request(method, uri)?;
+    let Some(cache) = &self.cache else {
+      return self.request(debug, Method::Get, uri);
+    };
 
-    if debug {
-      println!("{}", "Request:".bold().underline());
-      println!("{request:?}");
-      println!();
+    let key = self.http_client.cache_key(uri);
+
+    if let Some(value) = cache.get(&key) {
+      if debug {
+        println!("{} {uri}", "Cached response:".bold().underline());
+        println!();
+      }
+
+      return Ok(value);
+    }
+
+    let value = self.request(debug, Method::Get, uri)?;
+
+    // a broken cache must not break the command, the next run asks again
+    let _ = cache.put(&key, &value);
+
+    Ok(value)
+  }
+
+  // changed projects or clients make every cached response stale
+  fn invalidate_cache(&self, method: &Method, uri: &str) {
+    if *method == Method::Get {
+      return;
+    }
+
+    if let Some(cache) = &self.cache {
+      if uri.contains("/projects") || uri.contains("/clients") {
+        let _ = cache.clear();
+      }
     }
+  }
+
+  fn request<D: DeserializeOwned + Debug>(
+    &self,
+    debug: bool,
+    method: Method,
+    uri: &str,
+  ) -> anyhow::Result<D> {
+    self.invalidate_cache(&method, uri);
 
-    let response = request.send()?;
+    let response = self.http_client.send(debug, method, uri, None)?;
 
-    self.response(debug, response)
+    json_response(debug, response)
   }
 
   fn empty_request(
@@ -101,17 +449,11 @@ impl TogglClient {
     method: Method,
     uri: &str,
   ) -> anyhow::Result<()> {
-    let request = self.base_request(method, uri)?;
+    self.invalidate_cache(&method, uri);
 
-    if debug {
-      println!("{}", "Request:".bold().underline());
-      println!("{request:?}");
-      println!();
-    }
-
-    let response = request.send()?;
+    let response = self.http_client.send(debug, method, uri, None)?;
 
-    self.empty_response(response)
+    empty_response(response)
   }
 
   fn request_with_body<D: DeserializeOwned + Debug, S: Serialize + Debug>(
@@ -121,62 +463,89 @@ impl TogglClient {
     uri: &str,
     body: S,
   ) -> anyhow::Result<D> {
-    let request = self.base_request(method, uri)?.with_json(&body)?;
-
-    if debug {
-      println!("{}", "Request:".bold().underline());
-      println!("{request:?}");
-      println!();
-      println!("{:?}", &body);
-      println!();
-    }
+    self.invalidate_cache(&method, uri);
 
-    let response = request.send()?;
+    let body = serde_json::to_value(&body)?;
+    let response = self.http_client.send(debug, method, uri, Some(body))?;
 
-    self.response(debug, response)
+    json_response(debug, response)
   }
 
-  fn response<D: DeserializeOwned + Debug>(
+  fn get_all_time_entries(
     &self,
     debug: bool,
-    response: Response,
-  ) -> anyhow::Result<D> {
-    if debug {
-      println!("{}", "Response:".bold().underline());
-      println!("{response:?}");
-      println!();
-    }
+  ) -> anyhow::Result<Vec<TimeEntry>> {
+    let me = self.get_me(debug)?;
+    let (start, end) = Range::All.as_range()?;
+
+    let start_date = me
+      .created_at
+      .map(|created_at| DateTime::<Local>::from(created_at).date_naive())
+      .unwrap_or_else(|| start.date_naive());
+
+    let chunks = monthly_chunks(start_date, end.date_naive());
+
+    let progress_bar = ProgressBar::new(chunks.len() as u64).with_style(
+      ProgressStyle::with_template("{bar:40} {pos}/{len} months {msg}")?,
+    );
 
-    match response.status_code {
-      200 | 201 if debug => match response.json() {
-        Ok(json) => {
-          println!("{}", "Received JSON response:".bold().underline());
-          println!("{json:?}");
-          println!();
-
-          Ok(json)
-        }
-        Err(err) => Err(anyhow!("Failed to deserialize JSON: {}", err)),
-      },
-      200 | 201 => Ok(response.json()?),
-      status => match response.as_str() {
-        Ok(text) => Err(anyhow!("{} - {}", status, text)),
-        Err(_) => Err(anyhow!("{}", status)),
-      },
+    let mut time_entries = vec![];
+
+    for (chunk_start, chunk_end) in chunks {
+      progress_bar.set_message(chunk_start.format("%Y-%m").to_string());
+
+      time_entries.extend(self.get_time_entries_between(
+        debug,
+        chunk_start,
+        chunk_end,
+      )?);
+
+      progress_bar.inc(1);
     }
+
+    progress_bar.finish_and_clear();
+
+    Ok(time_entries)
   }
 
-  fn empty_response(&self, response: Response) -> anyhow::Result<()> {
-    match response.status_code {
-      200 | 201 => Ok(()),
-      status => match response.as_str() {
-        Ok(text) => Err(anyhow!("{} - {}", status, text)),
-        Err(_) => Err(anyhow!("{}", status)),
-      },
-    }
+  fn get_time_entries_between(
+    &self,
+    debug: bool,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+  ) -> anyhow::Result<Vec<TimeEntry>> {
+    let start_date = start_date.format("%Y-%m-%d").to_string();
+    let end_date = end_date.format("%Y-%m-%d").to_string();
+
+    let uri = format!(
+      "me/time_entries?start_date={}&end_date={}",
+      urlencoding::encode(&start_date),
+      urlencoding::encode(&end_date),
+    );
+
+    self.request::<Vec<TimeEntry>>(debug, Method::Get, &uri)
+  }
+}
+
+impl TogglApi for TogglClient {
+  fn workspace(&self) -> Option<&str> {
+    self.workspace.as_deref()
+  }
+
+  fn with_workspace(&self, workspace: Option<String>) -> Box<dyn TogglApi> {
+    Box::new(TogglClient {
+      http_client: self.http_client.clone(),
+      workspace,
+      cache: self.cache.clone(),
+      settings: self.settings.clone(),
+    })
+  }
+
+  fn settings(&self) -> Rc<Settings> {
+    self.settings.clone()
   }
 
-  pub fn get_workspace_clients(
+  fn get_workspace_clients(
     &self,
     debug: bool,
     include_archived: bool,
@@ -188,40 +557,153 @@ impl TogglClient {
       format!("workspaces/{workspace_id}/clients?status=active")
     };
 
-    self.request(debug, Method::Get, &uri)
+    self.cached_request(debug, &uri)
   }
 
-  pub fn get_time_entries(
+  fn get_workspace_tags(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+  ) -> anyhow::Result<Vec<Tag>> {
+    // like clients, no tags may come back as null instead of an empty list
+    let tags: Option<Vec<Tag>> = self.request(
+      debug,
+      Method::Get,
+      &format!("workspaces/{workspace_id}/tags"),
+    )?;
+
+    Ok(tags.unwrap_or_default())
+  }
+
+  fn create_tag(
+    &self,
+    debug: bool,
+    name: &str,
+    workspace_id: u64,
+  ) -> anyhow::Result<Tag> {
+    let body = json!({
+      "name": name,
+      "workspace_id": workspace_id,
+    });
+
+    let uri = format!("workspaces/{workspace_id}/tags");
+
+    self.request_with_body(debug, Method::Post, &uri, body)
+  }
+
+  fn update_tag(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    tag_id: u64,
+    name: &str,
+  ) -> anyhow::Result<Tag> {
+    let body = json!({
+      "name": name,
+      "workspace_id": workspace_id,
+    });
+
+    let uri = format!("workspaces/{workspace_id}/tags/{tag_id}");
+
+    self.request_with_body(debug, Method::Put, &uri, body)
+  }
+
+  fn delete_tag(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    tag_id: u64,
+  ) -> anyhow::Result<()> {
+    self.empty_request(
+      debug,
+      Method::Delete,
+      &format!("workspaces/{workspace_id}/tags/{tag_id}"),
+    )
+  }
+
+  fn get_project_tasks(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    project_id: u64,
+  ) -> anyhow::Result<Vec<Task>> {
+    // like tags, no tasks may come back as null instead of an empty list
+    let tasks: Option<Vec<Task>> = self.request(
+      debug,
+      Method::Get,
+      &format!("workspaces/{workspace_id}/projects/{project_id}/tasks"),
+    )?;
+
+    Ok(tasks.unwrap_or_default())
+  }
+
+  fn create_task(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    project_id: u64,
+    name: &str,
+  ) -> anyhow::Result<Task> {
+    let body = json!({
+      "active": true,
+      "name": name,
+    });
+
+    let uri = format!("workspaces/{workspace_id}/projects/{project_id}/tasks");
+
+    self.request_with_body(debug, Method::Post, &uri, body)
+  }
+
+  fn get_time_entries(
     &self,
     debug: bool,
     range: &Range,
   ) -> anyhow::Result<Vec<TimeEntry>> {
+    if let Range::All = range {
+      return self.get_all_time_entries(debug);
+    }
+
     let (start, end) = range.as_range()?;
-    let start_date = start.format("%Y-%m-%d").to_string();
 
     // End date is not inclusive, therefore we add one day
-    let end_date = (end + Duration::try_days(1).unwrap())
-      .format("%Y-%m-%d")
-      .to_string();
+    self.get_time_entries_between(
+      debug,
+      start.date_naive(),
+      end.date_naive() + Duration::try_days(1).unwrap(),
+    )
+  }
 
-    let uri = format!(
-      "me/time_entries?start_date={}&end_date={}",
-      urlencoding::encode(&start_date),
-      urlencoding::encode(&end_date),
-    );
+  fn get_workspaces(&self, debug: bool) -> anyhow::Result<Vec<Workspace>> {
+    self.cached_request::<Vec<Workspace>>(debug, "workspaces")
+  }
 
-    self.request::<Vec<TimeEntry>>(debug, Method::Get, &uri)
+  fn get_me(&self, debug: bool) -> anyhow::Result<Me> {
+    self.cached_request::<Me>(debug, "me")
   }
 
-  pub fn get_workspaces(&self, debug: bool) -> anyhow::Result<Vec<Workspace>> {
-    self.request::<Vec<Workspace>>(debug, Method::Get, "workspaces")
+  fn reset_api_token(&self, debug: bool) -> anyhow::Result<String> {
+    let response =
+      self
+        .http_client
+        .send(debug, Method::Post, "me/reset_token", None)?;
+
+    // the response is the new token, which must not end up in the debug output
+    json_response(false, response)
   }
 
-  pub fn get_me(&self, debug: bool) -> anyhow::Result<Me> {
-    self.request::<Me>(debug, Method::Get, "me")
+  fn get_workspace_users(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+  ) -> anyhow::Result<Vec<WorkspaceUser>> {
+    self.request(
+      debug,
+      Method::Get,
+      &format!("workspaces/{workspace_id}/users"),
+    )
   }
 
-  pub fn get_workspace_projects(
+  fn get_workspace_projects(
     &self,
     debug: bool,
     include_archived: bool,
@@ -233,11 +715,76 @@ impl TogglClient {
       format!("workspaces/{workspace_id}/projects?active=true")
     };
 
-    self.request::<Vec<Project>>(debug, Method::Get, &uri)
+    self.cached_request::<Vec<Project>>(debug, &uri)
   }
 
-  #[allow(clippy::too_many_arguments)]
-  pub fn create_time_entry(
+  fn get_project_templates(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+  ) -> anyhow::Result<Vec<Project>> {
+    let projects = self.get_workspace_projects(debug, false, workspace_id)?;
+
+    Ok(
+      projects
+        .into_iter()
+        .filter(|project| project.template == Some(true))
+        .collect(),
+    )
+  }
+
+  fn create_project(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    name: &str,
+    client_id: Option<u64>,
+    template_id: Option<u64>,
+  ) -> anyhow::Result<Project> {
+    let mut body = json!({
+      "active": true,
+      "name": name,
+    });
+
+    if let Some(client_id) = client_id {
+      body["client_id"] = json!(client_id);
+    }
+
+    if let Some(template_id) = template_id {
+      body["template_id"] = json!(template_id);
+    }
+
+    let uri = format!("workspaces/{workspace_id}/projects");
+
+    self.request_with_body(debug, Method::Post, &uri, body)
+  }
+
+  fn update_project(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    project_id: u64,
+    project: &ProjectUpdate,
+  ) -> anyhow::Result<Project> {
+    let uri = format!("workspaces/{workspace_id}/projects/{project_id}");
+
+    self.request_with_body(debug, Method::Put, &uri, project)
+  }
+
+  fn delete_project(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    project_id: u64,
+  ) -> anyhow::Result<()> {
+    self.empty_request(
+      debug,
+      Method::Delete,
+      &format!("workspaces/{workspace_id}/projects/{project_id}"),
+    )
+  }
+
+  fn create_time_entry(
     &self,
     debug: bool,
     description: &Option<String>,
@@ -246,11 +793,12 @@ impl TogglClient {
     duration: Duration,
     start: DateTime<Local>,
     project_id: u64,
+    task_id: Option<u64>,
     non_billable: bool,
   ) -> anyhow::Result<TimeEntry> {
     let billable = !non_billable;
 
-    let body = json!({
+    let mut body = json!({
       "description": description,
       "workspace_id": workspace_id,
       "tags": tags,
@@ -261,12 +809,16 @@ impl TogglClient {
       "billable": billable,
     });
 
+    if let Some(task_id) = task_id {
+      body["task_id"] = json!(task_id);
+    }
+
     let uri = format!("workspaces/{workspace_id}/time_entries");
 
     self.request_with_body(debug, Method::Post, &uri, body)
   }
 
-  pub fn create_client(
+  fn create_client(
     &self,
     debug: bool,
     name: &str,
@@ -283,8 +835,79 @@ impl TogglClient {
     self.request_with_body(debug, Method::Post, &uri, body)
   }
 
-  #[allow(clippy::too_many_arguments)]
-  pub fn start_time_entry(
+  fn get_client(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    client_id: u64,
+  ) -> anyhow::Result<Client> {
+    self.request(
+      debug,
+      Method::Get,
+      &format!("workspaces/{workspace_id}/clients/{client_id}"),
+    )
+  }
+
+  fn delete_client(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    client_id: u64,
+  ) -> anyhow::Result<()> {
+    self.empty_request(
+      debug,
+      Method::Delete,
+      &format!("workspaces/{workspace_id}/clients/{client_id}"),
+    )
+  }
+
+  fn update_client(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    client_id: u64,
+    name: &str,
+  ) -> anyhow::Result<Client> {
+    let body = json!({
+      "name": name,
+      "wid": workspace_id,
+    });
+
+    let uri = format!("workspaces/{workspace_id}/clients/{client_id}");
+
+    self.request_with_body(debug, Method::Put, &uri, body)
+  }
+
+  fn archive_client(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    client_id: u64,
+  ) -> anyhow::Result<()> {
+    self.empty_request(
+      debug,
+      Method::Post,
+      &format!("workspaces/{workspace_id}/clients/{client_id}/archive"),
+    )
+  }
+
+  fn restore_client(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    client_id: u64,
+    restore_projects: bool,
+  ) -> anyhow::Result<Client> {
+    let body = json!({
+      "restore_all_projects": restore_projects,
+    });
+
+    let uri = format!("workspaces/{workspace_id}/clients/{client_id}/restore");
+
+    self.request_with_body(debug, Method::Post, &uri, body)
+  }
+
+  fn start_time_entry(
     &self,
     debug: bool,
     start: DateTime<Local>,
@@ -292,12 +915,13 @@ impl TogglClient {
     description: &Option<String>,
     tags: &Option<Vec<String>>,
     project_id: u64,
+    task_id: Option<u64>,
     non_billable: bool,
   ) -> anyhow::Result<TimeEntry> {
     let billable = !non_billable;
     let duration = -start.timestamp();
 
-    let body = json!({
+    let mut body = json!({
       "at": start,
       "billable": billable,
       "created_with": CREATED_WITH,
@@ -309,12 +933,16 @@ impl TogglClient {
       "wid": workspace_id
     });
 
+    if let Some(task_id) = task_id {
+      body["task_id"] = json!(task_id);
+    }
+
     let uri = "time_entries".to_string();
 
     self.request_with_body(debug, Method::Post, &uri, body)
   }
 
-  pub fn stop_time_entry(
+  fn stop_time_entry(
     &self,
     debug: bool,
     workspace_id: u64,
@@ -327,7 +955,7 @@ impl TogglClient {
     )
   }
 
-  pub fn delete_time_entry(
+  fn delete_time_entry(
     &self,
     debug: bool,
     time_entry_id: u64,
@@ -338,4 +966,92 @@ impl TogglClient {
       &format!("time_entries/{time_entry_id}"),
     )
   }
+
+  fn get_time_entry(
+    &self,
+    debug: bool,
+    time_entry_id: u64,
+  ) -> anyhow::Result<TimeEntry> {
+    self.request(
+      debug,
+      Method::Get,
+      &format!("me/time_entries/{time_entry_id}"),
+    )
+  }
+
+  fn get_current_time_entry(
+    &self,
+    debug: bool,
+  ) -> anyhow::Result<Option<TimeEntry>> {
+    self.request(debug, Method::Get, "me/time_entries/current")
+  }
+
+  fn update_time_entry(
+    &self,
+    debug: bool,
+    time_entry: &TimeEntry,
+  ) -> anyhow::Result<TimeEntry> {
+    let body = json!({
+      "billable": time_entry.billable,
+      "description": time_entry.description,
+      "duration": time_entry.duration,
+      "project_id": time_entry.pid,
+      "rate": time_entry.rate,
+      "start": time_entry.start,
+      "stop": time_entry.stop,
+      "tags": time_entry.tags,
+      "workspace_id": time_entry.wid,
+    });
+
+    let uri = format!(
+      "workspaces/{}/time_entries/{}",
+      time_entry.wid, time_entry.id
+    );
+
+    self.request_with_body(debug, Method::Put, &uri, body)
+  }
+
+  fn bulk_edit_time_entries(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    time_entry_ids: &[u64],
+    operations: &[PatchOperation],
+  ) -> anyhow::Result<BulkEditResult> {
+    let mut result = BulkEditResult::default();
+
+    for chunk in time_entry_ids.chunks(BULK_EDIT_LIMIT) {
+      let uri = format!(
+        "workspaces/{workspace_id}/time_entries/{}",
+        chunk.iter().join(",")
+      );
+
+      let chunk_result: BulkEditResult =
+        self.request_with_body(debug, Method::Patch, &uri, operations)?;
+
+      result.success.extend(chunk_result.success);
+      result.failure.extend(chunk_result.failure);
+    }
+
+    Ok(result)
+  }
+}
+
+// Consecutive month-sized chunks, the end date of each chunk is exclusive
+pub(crate) fn monthly_chunks(
+  start_date: NaiveDate,
+  end_date: NaiveDate,
+) -> Vec<(NaiveDate, NaiveDate)> {
+  let mut chunks = vec![];
+  let mut chunk_start = start_date.with_day(1).unwrap_or(start_date);
+
+  while chunk_start <= end_date {
+    let chunk_end = shift_months(chunk_start, 1);
+
+    chunks.push((chunk_start, chunk_end));
+
+    chunk_start = chunk_end;
+  }
+
+  chunks
 }
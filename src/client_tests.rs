@@ -1,6 +1,7 @@
 use crate::{
-  client::{TogglClient, CREATED_WITH},
-  model::Range,
+  cache::ResponseCache,
+  client::{monthly_chunks, TogglApi, TogglClient, CREATED_WITH},
+  model::{PatchOperation, ProjectUpdate, Range},
 };
 use chrono::{DateTime, Duration, Local, NaiveDate};
 use mockito::Matcher;
@@ -59,6 +60,37 @@ fn get_me() -> anyhow::Result<()> {
   Ok(())
 }
 
+#[test]
+fn reset_api_token() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("POST", "/me/reset_token")
+    .with_header(
+      "Authorization",
+      "Basic Y2I3YmY3ZWZhNmQ2NTIwNDZhYmQyZjdkODRlZTE4YzE6YXBpX3Rva2Vu",
+    )
+    .with_status(200)
+    .with_body(json!("d41d8cd98f00b204e9800998ecf8427e").to_string())
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let api_token = client.reset_api_token(false)?;
+
+    assert_eq!(api_token, "d41d8cd98f00b204e9800998ecf8427e");
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
 #[test]
 fn get_workspaces() -> anyhow::Result<()> {
   let body = json!(
@@ -395,6 +427,7 @@ fn create_time_entry() -> anyhow::Result<()> {
       Duration::try_seconds(200).unwrap(),
       DateTime::<Local>::from_str("2021-11-21T23:58:09+01:00")?,
       123456789,
+      None,
       false,
     )?;
 
@@ -519,6 +552,7 @@ fn test_start_time_entry() -> anyhow::Result<()> {
       &Some("fkbr".to_string()),
       &Some(vec!["a".to_string(), "b".to_string()]),
       123,
+      None,
       true,
     )?;
 
@@ -574,6 +608,85 @@ fn test_stop_time_entry() -> anyhow::Result<()> {
   Ok(())
 }
 
+#[test]
+fn test_bulk_edit_time_entries() -> anyhow::Result<()> {
+  let operations = vec![PatchOperation {
+    op: "add".to_string(),
+    path: "/tags".to_string(),
+    value: json!(["a"]),
+  }];
+
+  let ids = (1..=150).collect::<Vec<u64>>();
+
+  let mut server = mockito::Server::new();
+
+  // the ids are separated by commas, which are percent-encoded in the path
+  let first_mock = server
+    .mock(
+      "PATCH",
+      format!(
+        "/workspaces/456/time_entries/{}",
+        (1..=100)
+          .map(|id| id.to_string())
+          .collect::<Vec<_>>()
+          .join("%2C")
+      )
+      .as_str(),
+    )
+    .match_body(Matcher::Json(json!([
+      { "op": "add", "path": "/tags", "value": ["a"] }
+    ])))
+    .with_status(200)
+    .with_body(
+      json!({ "success": (1..=100).collect::<Vec<u64>>(), "failure": [] })
+        .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let second_mock = server
+    .mock(
+      "PATCH",
+      format!(
+        "/workspaces/456/time_entries/{}",
+        (101..=150)
+          .map(|id| id.to_string())
+          .collect::<Vec<_>>()
+          .join("%2C")
+      )
+      .as_str(),
+    )
+    .with_status(200)
+    .with_body(
+      json!({
+        "success": (101..=149).collect::<Vec<u64>>(),
+        "failure": [{ "id": 150, "message": "Time entry is locked" }]
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let result =
+      client.bulk_edit_time_entries(false, 456, &ids, &operations)?;
+
+    assert_eq!(result.success.len(), 149);
+    assert_eq!(result.failure.len(), 1);
+    assert_eq!(result.failure[0].id, 150);
+  }
+
+  first_mock.assert();
+  second_mock.assert();
+
+  Ok(())
+}
+
 #[test]
 fn test_delete_time_entry() -> anyhow::Result<()> {
   let mut server = mockito::Server::new();
@@ -603,3 +716,647 @@ fn test_delete_time_entry() -> anyhow::Result<()> {
 
   Ok(())
 }
+
+#[test]
+fn test_get_time_entry() -> anyhow::Result<()> {
+  let response_body = json!(
+    {
+      "id": 123,
+      "pid": 123,
+      "wid": 456,
+      "billable": true,
+      "start": "2013-03-05T07:58:58.000Z",
+      "stop": "2013-03-05T09:58:58.000Z",
+      "duration": 7200,
+      "description": "fkbr",
+      "tags": ["a", "b"],
+      "rate": 120.5
+    }
+  );
+
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("GET", "/me/time_entries/123")
+    .with_header(
+      "Authorization",
+      "Basic Y2I3YmY3ZWZhNmQ2NTIwNDZhYmQyZjdkODRlZTE4YzE6YXBpX3Rva2Vu",
+    )
+    .with_status(200)
+    .with_body(response_body.to_string())
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let time_entry = client.get_time_entry(false, 123)?;
+
+    assert_eq!(time_entry.id, 123);
+    assert_eq!(time_entry.rate, Some(120.5));
+    assert_eq!(time_entry.amount(None), Some(241.0));
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_update_time_entry() -> anyhow::Result<()> {
+  let time_entry_body = json!(
+    {
+      "id": 123,
+      "pid": 789,
+      "wid": 456,
+      "billable": true,
+      "start": "2013-03-05T07:58:58Z",
+      "stop": "2013-03-05T09:58:58Z",
+      "duration": 7200,
+      "description": "fkbr",
+      "tags": ["a", "b"],
+      "rate": 90.0
+    }
+  );
+
+  let request_body = json!(
+    {
+      "billable": true,
+      "description": "fkbr",
+      "duration": 7200,
+      "project_id": 789,
+      "rate": 90.0,
+      "start": "2013-03-05T07:58:58Z",
+      "stop": "2013-03-05T09:58:58Z",
+      "tags": ["a", "b"],
+      "workspace_id": 456
+    }
+  );
+
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("PUT", "/workspaces/456/time_entries/123")
+    .with_header(
+      "Authorization",
+      "Basic Y2I3YmY3ZWZhNmQ2NTIwNDZhYmQyZjdkODRlZTE4YzE6YXBpX3Rva2Vu",
+    )
+    .with_status(200)
+    .match_body(Matcher::Json(request_body))
+    .with_body(time_entry_body.to_string())
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let time_entry = serde_json::from_value(time_entry_body)?;
+    let updated_time_entry = client.update_time_entry(false, &time_entry)?;
+
+    assert_eq!(updated_time_entry.rate, Some(90.0));
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_get_client() -> anyhow::Result<()> {
+  let response_body = json!(
+    {
+      "id": 1234,
+      "wid": 12345678,
+      "name": "fkbr.org",
+      "at": "2021-11-16T09:30:21+00:00",
+      "archived": false
+    }
+  );
+
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("GET", "/workspaces/12345678/clients/1234")
+    .with_header(
+      "Authorization",
+      "Basic Y2I3YmY3ZWZhNmQ2NTIwNDZhYmQyZjdkODRlZTE4YzE6YXBpX3Rva2Vu",
+    )
+    .with_status(200)
+    .with_body(response_body.to_string())
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let workspace_client = client.get_client(false, 12345678, 1234)?;
+
+    assert_eq!(workspace_client.id, 1234);
+    assert_eq!(workspace_client.name, "fkbr.org");
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_delete_client() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("DELETE", "/workspaces/12345678/clients/1234")
+    .with_header(
+      "Authorization",
+      "Basic Y2I3YmY3ZWZhNmQ2NTIwNDZhYmQyZjdkODRlZTE4YzE6YXBpX3Rva2Vu",
+    )
+    .with_status(200)
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let deleted_client = client.delete_client(false, 12345678, 1234);
+
+    assert_eq!(deleted_client.is_ok(), true);
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_update_tag() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("PUT", "/workspaces/12345678/tags/1234")
+    .with_header(
+      "Authorization",
+      "Basic Y2I3YmY3ZWZhNmQ2NTIwNDZhYmQyZjdkODRlZTE4YzE6YXBpX3Rva2Vu",
+    )
+    .match_body(Matcher::Json(json!({
+      "name": "Meeting",
+      "workspace_id": 12345678
+    })))
+    .with_status(200)
+    .with_body(json!({ "id": 1234, "name": "Meeting" }).to_string())
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let tag = client.update_tag(false, 12345678, 1234, "Meeting")?;
+
+    assert_eq!(tag.name, "Meeting");
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_delete_tag() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("DELETE", "/workspaces/12345678/tags/1234")
+    .with_status(200)
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    client.delete_tag(false, 12345678, 1234)?;
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_project_templates_and_create_from_template() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let projects_mock = server
+    .mock("GET", "/workspaces/12345678/projects?active=true")
+    .with_status(200)
+    .with_body(
+      json!([
+        {
+          "id": 1,
+          "name": "fkbr.org",
+          "wid": 12345678,
+          "status": "active",
+          "cid": null,
+          "template": null
+        },
+        {
+          "id": 2,
+          "name": "Retainer",
+          "wid": 12345678,
+          "status": "active",
+          "cid": null,
+          "template": true
+        }
+      ])
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let create_mock = server
+    .mock("POST", "/workspaces/12345678/projects")
+    .match_body(Matcher::Json(json!({
+      "active": true,
+      "name": "fkbr.com",
+      "client_id": 42,
+      "template_id": 2
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 3,
+        "name": "fkbr.com",
+        "wid": 12345678,
+        "status": "active",
+        "cid": 42
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let templates = client.get_project_templates(false, 12345678)?;
+
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].name, "Retainer");
+
+    let project =
+      client.create_project(false, 12345678, "fkbr.com", Some(42), Some(2))?;
+
+    assert_eq!(project.cid, Some(42));
+  }
+
+  projects_mock.assert();
+  create_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_cached_projects() -> anyhow::Result<()> {
+  let dir =
+    std::env::temp_dir().join(format!("fbtoggl-cache-{}", std::process::id()));
+
+  let mut server = mockito::Server::new();
+
+  let projects = json!([
+    {
+      "id": 123,
+      "wid": 456,
+      "name": "fkbr",
+      "billable": false,
+      "is_private": false,
+      "active": true,
+      "at": "2021-11-16T08:52:59+00:00",
+      "created_at": "2021-11-16T08:52:59+00:00",
+      "color": "5",
+      "auto_estimates": false,
+      "actual_hours": 1,
+      "hex_color": "#2da608",
+      "status": "active"
+    }
+  ]);
+
+  let projects_mock = server
+    .mock("GET", "/workspaces/456/projects?active=true")
+    .with_status(200)
+    .with_body(projects.to_string())
+    .expect(2)
+    .create();
+
+  let create_mock = server
+    .mock("POST", "/workspaces/456/projects")
+    .with_status(200)
+    .with_body(projects[0].to_string())
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?
+  .with_cache(ResponseCache::new(
+    dir.clone(),
+    std::time::Duration::from_secs(60),
+  ));
+
+  // the second request is answered from the cache
+  assert_eq!(client.get_workspace_projects(false, false, 456)?.len(), 1);
+  assert_eq!(client.get_workspace_projects(false, false, 456)?.len(), 1);
+
+  // creating a project makes the cached projects stale
+  client.create_project(false, 456, "fkbr", None, None)?;
+
+  assert_eq!(client.get_workspace_projects(false, false, 456)?.len(), 1);
+
+  projects_mock.assert();
+  create_mock.assert();
+
+  std::fs::remove_dir_all(&dir)?;
+
+  Ok(())
+}
+
+#[test]
+fn test_update_project() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("PUT", "/workspaces/12345678/projects/123")
+    .match_body(Matcher::Json(json!({
+      "name": "fkbr.com",
+      "active": false
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 123,
+        "name": "fkbr.com",
+        "wid": 12345678,
+        "status": "archived",
+        "cid": null
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let project = client.update_project(
+      false,
+      12345678,
+      123,
+      &ProjectUpdate {
+        name: Some("fkbr.com".to_string()),
+        active: Some(false),
+        ..ProjectUpdate::default()
+      },
+    )?;
+
+    assert_eq!(project.name, "fkbr.com");
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_delete_project() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("DELETE", "/workspaces/12345678/projects/123")
+    .with_status(200)
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    client.delete_project(false, 12345678, 123)?;
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_archive_client() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("POST", "/workspaces/12345678/clients/42/archive")
+    .with_status(200)
+    .with_body("[123, 124]")
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    client.archive_client(false, 12345678, 42)?;
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_restore_client() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("POST", "/workspaces/12345678/clients/42/restore")
+    .match_body(Matcher::Json(json!({ "restore_all_projects": true })))
+    .with_status(200)
+    .with_body(
+      json!({ "id": 42, "name": "fkbr", "archived": false }).to_string(),
+    )
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let restored = client.restore_client(false, 12345678, 42, true)?;
+
+    assert!(!restored.archived);
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_create_task() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("POST", "/workspaces/12345678/projects/123/tasks")
+    .match_body(Matcher::Json(json!({ "active": true, "name": "Review" })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 42,
+        "name": "Review",
+        "project_id": 123,
+        "workspace_id": 12345678,
+        "active": true
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let task = client.create_task(false, 12345678, 123, "Review")?;
+
+    assert_eq!(task.id, 42);
+    assert_eq!(task.project_id, 123);
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_create_time_entry_with_task() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("POST", "/workspaces/12345678/time_entries")
+    .match_body(Matcher::PartialJson(json!({
+      "project_id": 123,
+      "task_id": 42
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 1,
+        "wid": 12345678,
+        "pid": 123,
+        "task_id": 42,
+        "start": "2021-11-21T23:58:09+01:00",
+        "duration": 200
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let time_entry = client.create_time_entry(
+      false,
+      &None,
+      12345678,
+      &None,
+      Duration::try_seconds(200).unwrap(),
+      DateTime::<Local>::from_str("2021-11-21T23:58:09+01:00")?,
+      123,
+      Some(42),
+      false,
+    )?;
+
+    assert_eq!(time_entry.task_id, Some(42));
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_pin_workspace() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("GET", "/workspaces")
+    .with_status(200)
+    .with_body(
+      json!([
+        { "id": 1, "name": "ACME Corp" },
+        { "id": 2, "name": "fkbr" }
+      ])
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?
+  .with_selected_workspace("FKBR")
+  .pin_workspace(false)?;
+
+  assert_eq!(client.workspace(), Some("2"));
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_monthly_chunks() {
+  let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+  assert_eq!(
+    monthly_chunks(date(2021, 11, 16), date(2022, 1, 10)),
+    vec![
+      (date(2021, 11, 1), date(2021, 12, 1)),
+      (date(2021, 12, 1), date(2022, 1, 1)),
+      (date(2022, 1, 1), date(2022, 2, 1)),
+    ]
+  );
+}
@@ -0,0 +1,111 @@
+use anyhow::anyhow;
+use chrono::Local;
+
+use crate::{
+  cli::{Output, StopBreak},
+  client::TogglApi,
+  commands::time_entries::output_time_entry,
+  model::{Range, TimeEntry},
+  resolver::Resolver,
+};
+
+pub fn start(
+  debug: bool,
+  output: &Output,
+  break_project: &str,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let break_project_id =
+    resolver.project_id(workspace_id, break_project).map_err(|_| {
+      anyhow!("Cannot find project='{break_project}', create it or set 'break_project' in settings.toml")
+    })?;
+
+  if let Some(current) = client.get_current_time_entry(debug)? {
+    if current.pid == Some(break_project_id) {
+      return Err(anyhow!("Break {} is running already", current.id));
+    }
+
+    client.stop_time_entry(debug, current.wid, current.id)?;
+
+    eprintln!("Stopped time entry {}", current.id);
+  }
+
+  let started_break = client.start_time_entry(
+    debug,
+    Local::now(),
+    workspace_id,
+    &None,
+    &None,
+    break_project_id,
+    None,
+    true,
+  )?;
+
+  output_time_entry(output, &started_break);
+
+  Ok(())
+}
+
+pub fn stop(
+  debug: bool,
+  output: &Output,
+  stop_break: &StopBreak,
+  break_project: &str,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let break_project_id = resolver.project_id(workspace_id, break_project)?;
+
+  let current = client
+    .get_current_time_entry(debug)?
+    .filter(|current| current.pid == Some(break_project_id))
+    .ok_or_else(|| anyhow!("No break is running"))?;
+
+  let stopped_break = client.stop_time_entry(debug, current.wid, current.id)?;
+
+  if !stop_break.resume {
+    output_time_entry(output, &stopped_break);
+
+    return Ok(());
+  }
+
+  eprintln!("Stopped break {}", stopped_break.id);
+
+  let time_entries = client.get_time_entries(debug, &Range::Today)?;
+
+  let previous = last_work_entry(&time_entries, break_project_id)
+    .ok_or_else(|| anyhow!("No time entry before the break to resume"))?;
+
+  let project_id = previous
+    .pid
+    .ok_or_else(|| anyhow!("Time entry {} has no project", previous.id))?;
+
+  let resumed = client.start_time_entry(
+    debug,
+    Local::now(),
+    previous.wid,
+    &previous.description,
+    &previous.tags,
+    project_id,
+    previous.task_id,
+    !previous.billable.unwrap_or_default(),
+  )?;
+
+  output_time_entry(output, &resumed);
+
+  Ok(())
+}
+
+/// Latest time entry which is no break
+pub(super) fn last_work_entry(
+  time_entries: &[TimeEntry],
+  break_project_id: u64,
+) -> Option<&TimeEntry> {
+  time_entries
+    .iter()
+    .filter(|time_entry| time_entry.pid != Some(break_project_id))
+    .max_by_key(|time_entry| time_entry.start)
+}
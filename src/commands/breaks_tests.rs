@@ -0,0 +1,41 @@
+use crate::{commands::breaks::last_work_entry, model::TimeEntry};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test]
+fn test_last_work_entry() -> anyhow::Result<()> {
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "pid": 123,
+      "start": "2021-11-22T08:00:00+01:00",
+      "duration": 14400
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "pid": 456,
+      "start": "2021-11-22T12:00:00+01:00",
+      "duration": 1800
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "pid": 123,
+      "start": "2021-11-22T07:00:00+01:00",
+      "duration": 3600
+    }
+  ]))?;
+
+  assert_eq!(
+    last_work_entry(&time_entries, 456).map(|time_entry| time_entry.id),
+    Some(1)
+  );
+  assert_eq!(
+    last_work_entry(&time_entries[1..2], 456).map(|time_entry| time_entry.id),
+    None
+  );
+
+  Ok(())
+}
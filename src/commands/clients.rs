@@ -1,28 +1,75 @@
+use anyhow::anyhow;
 use colored::Colorize;
 use term_table::{row::Row, table_cell::TableCell, Table, TableStyle};
 
 use crate::{
-  cli::{output_values_json, CreateClient, Format},
-  client::TogglClient,
+  cli::{
+    output_values_json, ArchiveClient, ClientDetails, CreateClient,
+    DeleteClient, EditClient, Format, IfExists, Output, RestoreClient,
+  },
+  client::TogglApi,
+  commands::workspaces::selected_workspace_id,
   model::Client,
+  resolver::Resolver,
 };
 
 pub fn create(
   debug: bool,
-  format: &Format,
+  output: &Output,
   create_client: &CreateClient,
-  client: &TogglClient,
+  client: &dyn TogglApi,
 ) -> anyhow::Result<()> {
-  let me = client.get_me(debug)?;
+  let workspace_id = selected_workspace_id(debug, client)?;
 
-  let data = client.create_client(
-    debug,
-    &create_client.name,
-    me.default_workspace_id,
-  )?;
+  // archived clients block the name as well
+  let clients = client
+    .get_workspace_clients(debug, true, workspace_id)?
+    .unwrap_or_default();
+
+  let existing = clients
+    .iter()
+    .find(|client| client.name.eq_ignore_ascii_case(&create_client.name));
+
+  let name = match (existing, &create_client.if_exists) {
+    (None, _) => create_client.name.to_owned(),
+    (Some(existing), IfExists::Error) => {
+      eprintln!("Client exists already:");
+      output_values_table(std::slice::from_ref(existing));
+
+      return Err(anyhow!(
+        "Client '{}' exists already (id={}), use --if-exists ignore|suffix",
+        existing.name,
+        existing.id
+      ));
+    }
+    (Some(existing), IfExists::Ignore) => {
+      eprintln!("Client '{}' exists already", existing.name);
+
+      match output.format {
+        Format::Json => {
+          output_values_json(output, std::slice::from_ref(existing))
+        }
+        Format::Raw => output_values_raw(std::slice::from_ref(existing)),
+        Format::Table => output_values_table(std::slice::from_ref(existing)),
+      }
+
+      return Ok(());
+    }
+    (Some(_), IfExists::Suffix) => {
+      let name = free_client_name(&create_client.name, &clients);
+      eprintln!(
+        "Client '{}' exists already, creating '{name}'",
+        create_client.name
+      );
 
-  match format {
-    Format::Json => output_values_json(&[data]),
+      name
+    }
+  };
+
+  let data = client.create_client(debug, &name, workspace_id)?;
+
+  match output.format {
+    Format::Json => output_values_json(output, &[data]),
     Format::Raw => output_values_raw(&[data]),
     Format::Table => output_values_table(&[data]),
   }
@@ -33,18 +80,16 @@ pub fn create(
 pub fn list(
   debug: bool,
   include_archived: bool,
-  format: &Format,
-  client: &TogglClient,
+  output: &Output,
+  client: &dyn TogglApi,
 ) -> anyhow::Result<()> {
-  let me = client.get_me(debug)?;
+  let workspace_id = selected_workspace_id(debug, client)?;
 
-  if let Ok(Some(clients)) = client.get_workspace_clients(
-    debug,
-    include_archived,
-    me.default_workspace_id,
-  ) {
-    match format {
-      Format::Json => output_values_json(&clients),
+  if let Ok(Some(clients)) =
+    client.get_workspace_clients(debug, include_archived, workspace_id)
+  {
+    match output.format {
+      Format::Json => output_values_json(output, &clients),
       Format::Raw => output_values_raw(&clients),
       Format::Table => output_values_table(&clients),
     }
@@ -55,6 +100,138 @@ pub fn list(
   Ok(())
 }
 
+pub fn show(
+  debug: bool,
+  output: &Output,
+  client_details: &ClientDetails,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let client_id = resolver.client_id(workspace_id, &client_details.id)?;
+
+  let data = client.get_client(debug, workspace_id, client_id)?;
+
+  match output.format {
+    Format::Json => output_values_json(output, &[data]),
+    Format::Raw => output_values_raw(&[data]),
+    Format::Table => output_values_table(&[data]),
+  }
+
+  Ok(())
+}
+
+pub fn delete(
+  debug: bool,
+  output: &Output,
+  delete_client: &DeleteClient,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let client_id = resolver.client_id(workspace_id, &delete_client.id)?;
+
+  client.delete_client(debug, workspace_id, client_id)?;
+
+  list(debug, false, output, client)?;
+
+  Ok(())
+}
+
+pub fn edit(
+  debug: bool,
+  output: &Output,
+  edit_client: &EditClient,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let client_id = resolver.any_client_id(workspace_id, &edit_client.id)?;
+
+  let clients = client
+    .get_workspace_clients(debug, true, workspace_id)?
+    .unwrap_or_default();
+
+  if let Some(existing) = clients.iter().find(|existing| {
+    existing.id != client_id
+      && existing.name.eq_ignore_ascii_case(&edit_client.name)
+  }) {
+    return Err(anyhow!(
+      "Client '{}' exists already (id={})",
+      existing.name,
+      existing.id
+    ));
+  }
+
+  let data =
+    client.update_client(debug, workspace_id, client_id, &edit_client.name)?;
+
+  output_clients(output, &[data]);
+
+  Ok(())
+}
+
+pub fn archive(
+  debug: bool,
+  output: &Output,
+  archive_client: &ArchiveClient,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let client_id = resolver.client_id(workspace_id, &archive_client.id)?;
+
+  client.archive_client(debug, workspace_id, client_id)?;
+
+  let data = client.get_client(debug, workspace_id, client_id)?;
+
+  output_clients(output, &[data]);
+
+  Ok(())
+}
+
+pub fn restore(
+  debug: bool,
+  output: &Output,
+  restore_client: &RestoreClient,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let client_id = resolver.any_client_id(workspace_id, &restore_client.id)?;
+
+  let data = client.restore_client(
+    debug,
+    workspace_id,
+    client_id,
+    restore_client.with_projects,
+  )?;
+
+  output_clients(output, &[data]);
+
+  Ok(())
+}
+
+/// First free name of the form 'name (2)', 'name (3)', ...
+pub(super) fn free_client_name(name: &str, clients: &[Client]) -> String {
+  (2..)
+    .map(|number| format!("{name} ({number})"))
+    .find(|candidate| {
+      !clients
+        .iter()
+        .any(|client| client.name.eq_ignore_ascii_case(candidate))
+    })
+    .unwrap_or_else(|| name.to_string())
+}
+
+fn output_clients(output: &Output, clients: &[Client]) {
+  match output.format {
+    Format::Json => output_values_json(output, clients),
+    Format::Raw => output_values_raw(clients),
+    Format::Table => output_values_table(clients),
+  }
+}
+
 fn output_values_raw(values: &[Client]) {
   for client in values {
     println!("\"{}\"", client.name);
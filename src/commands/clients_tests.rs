@@ -0,0 +1,23 @@
+use crate::{commands::clients::free_client_name, model::Client};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_free_client_name() {
+  let client = |id, name: &str| Client {
+    id,
+    name: name.to_string(),
+    archived: false,
+  };
+
+  let clients = vec![
+    client(1, "beta male gmbh"),
+    client(2, "beta male gmbh (2)"),
+    client(3, "fkbr"),
+  ];
+
+  assert_eq!(
+    free_client_name("beta male gmbh", &clients),
+    "beta male gmbh (3)"
+  );
+  assert_eq!(free_client_name("FKBR", &clients), "FKBR (2)");
+}
@@ -0,0 +1,219 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, Duration, Local};
+use colored::Colorize;
+use hhmmss::Hhmmss;
+use serde_json::{json, Value};
+use term_table::{row::Row, table_cell::TableCell, Table, TableStyle};
+
+use crate::{
+  cli::{output_values_json, Compare, Format, Output},
+  client::TogglApi,
+  model::{Project, TimeEntry},
+};
+
+const NO_PROJECT: &str = "(no project)";
+
+pub fn compare(
+  debug: bool,
+  output: &Output,
+  compare: &Compare,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let time_entries = client.get_time_entries(debug, &compare.range)?;
+  let against_time_entries =
+    client.get_time_entries(debug, &compare.against)?;
+
+  let workspace_ids = time_entries
+    .iter()
+    .chain(&against_time_entries)
+    .map(|time_entry| time_entry.wid)
+    .collect::<BTreeSet<u64>>();
+
+  let mut projects = vec![];
+
+  for workspace_id in workspace_ids {
+    projects.extend(client.get_workspace_projects(
+      debug,
+      true,
+      workspace_id,
+    )?);
+  }
+
+  let comparisons = project_comparisons(
+    &time_entries,
+    &against_time_entries,
+    &projects,
+    Local::now(),
+  );
+
+  if comparisons.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &comparisons
+        .iter()
+        .map(|comparison| {
+          json!({
+            "project": comparison.project,
+            "range": comparison.range.num_seconds(),
+            "against": comparison.against.num_seconds(),
+            "delta": comparison.delta().num_seconds(),
+          })
+        })
+        .collect::<Vec<Value>>(),
+    ),
+    Format::Raw => {
+      for comparison in &comparisons {
+        println!(
+          "{}\t{}\t{}\t{}",
+          comparison.project,
+          comparison.range.hhmmss(),
+          comparison.against.hhmmss(),
+          format_delta(comparison.delta())
+        );
+      }
+    }
+    Format::Table => output_comparisons_table(compare, &comparisons),
+  }
+
+  Ok(())
+}
+
+#[derive(Debug)]
+pub(super) struct ProjectComparison {
+  pub project: String,
+  pub range: Duration,
+  pub against: Duration,
+}
+
+impl ProjectComparison {
+  pub fn delta(&self) -> Duration {
+    self.range - self.against
+  }
+}
+
+/// Logged time per project in both ranges, sorted by project name,
+/// running time entries count until `now`
+pub(super) fn project_comparisons(
+  time_entries: &[TimeEntry],
+  against_time_entries: &[TimeEntry],
+  projects: &[Project],
+  now: DateTime<Local>,
+) -> Vec<ProjectComparison> {
+  let project_name = |time_entry: &TimeEntry| {
+    time_entry
+      .pid
+      .map(|pid| {
+        projects
+          .iter()
+          .find(|project| project.id == pid)
+          .map(|project| project.name.to_owned())
+          .unwrap_or_else(|| pid.to_string())
+      })
+      .unwrap_or_else(|| NO_PROJECT.to_string())
+  };
+
+  let duration = |time_entry: &TimeEntry| {
+    if time_entry.duration < 0 {
+      now.to_utc() - time_entry.start
+    } else {
+      Duration::try_seconds(time_entry.duration).unwrap_or_default()
+    }
+  };
+
+  let mut comparisons = HashMap::<String, ProjectComparison>::new();
+
+  for (time_entry, is_against) in time_entries
+    .iter()
+    .map(|time_entry| (time_entry, false))
+    .chain(
+      against_time_entries
+        .iter()
+        .map(|time_entry| (time_entry, true)),
+    )
+  {
+    let project = project_name(time_entry);
+
+    let comparison =
+      comparisons.entry(project.to_owned()).or_insert_with(|| {
+        ProjectComparison {
+          project,
+          range: Duration::zero(),
+          against: Duration::zero(),
+        }
+      });
+
+    if is_against {
+      comparison.against += duration(time_entry);
+    } else {
+      comparison.range += duration(time_entry);
+    }
+  }
+
+  let mut comparisons = comparisons.into_values().collect::<Vec<_>>();
+
+  comparisons.sort_by_key(|comparison| comparison.project.to_lowercase());
+
+  comparisons
+}
+
+pub(super) fn format_delta(delta: Duration) -> String {
+  let sign = if delta < Duration::zero() { "-" } else { "+" };
+
+  format!("{sign}{}", delta.abs().hhmmss())
+}
+
+fn output_comparisons_table(
+  compare: &Compare,
+  comparisons: &[ProjectComparison],
+) {
+  let mut table = Table::new();
+  table.style = TableStyle::thin();
+
+  table.add_row(Row::new(vec![
+    TableCell::new("Project".bold().white()),
+    TableCell::new(compare.range.to_string().bold().white()),
+    TableCell::new(compare.against.to_string().bold().white()),
+    TableCell::new("Delta".bold().white()),
+  ]));
+
+  let colored_delta = |delta: Duration| {
+    let text = format_delta(delta);
+
+    match delta.cmp(&Duration::zero()) {
+      std::cmp::Ordering::Greater => text.green(),
+      std::cmp::Ordering::Less => text.red(),
+      std::cmp::Ordering::Equal => text.normal(),
+    }
+  };
+
+  for comparison in comparisons {
+    table.add_row(Row::new(vec![
+      TableCell::new(&comparison.project),
+      TableCell::new(comparison.range.hhmmss()),
+      TableCell::new(comparison.against.hhmmss()),
+      TableCell::new(colored_delta(comparison.delta())),
+    ]));
+  }
+
+  let range = comparisons
+    .iter()
+    .fold(Duration::zero(), |sum, comparison| sum + comparison.range);
+  let against = comparisons
+    .iter()
+    .fold(Duration::zero(), |sum, comparison| sum + comparison.against);
+
+  table.add_row(Row::new(vec![
+    TableCell::new("Total".bold()),
+    TableCell::new(range.hhmmss().bold()),
+    TableCell::new(against.hhmmss().bold()),
+    TableCell::new(colored_delta(range - against).bold()),
+  ]));
+
+  println!("{}", table.render());
+}
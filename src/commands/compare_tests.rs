@@ -0,0 +1,97 @@
+use crate::{
+  commands::compare::{format_delta, project_comparisons},
+  model::{Project, TimeEntry},
+};
+use chrono::{DateTime, Duration, Local};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::str::FromStr;
+
+#[test]
+fn test_project_comparisons() -> anyhow::Result<()> {
+  let projects: Vec<Project> = serde_json::from_value(json!([
+    { "id": 1, "name": "fkbr.org", "wid": 1234567, "status": "active" },
+    { "id": 2, "name": "Internal", "wid": 1234567, "status": "archived" }
+  ]))?;
+
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T08:00:00+01:00",
+      "duration": 7200
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-22T10:00:00+01:00",
+      "duration": 1800
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-23T08:00:00+01:00",
+      "duration": -1637650800
+    }
+  ]))?;
+
+  let against_time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 4,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-15T08:00:00+01:00",
+      "duration": 14400
+    },
+    {
+      "id": 5,
+      "wid": 1234567,
+      "pid": 2,
+      "start": "2021-11-15T12:00:00+01:00",
+      "duration": 3600
+    }
+  ]))?;
+
+  let now = DateTime::<Local>::from_str("2021-11-23T09:00:00+01:00")?;
+
+  let comparisons =
+    project_comparisons(&time_entries, &against_time_entries, &projects, now);
+
+  let rows = comparisons
+    .iter()
+    .map(|comparison| {
+      (
+        comparison.project.as_str(),
+        comparison.range.num_minutes(),
+        comparison.against.num_minutes(),
+        comparison.delta().num_minutes(),
+      )
+    })
+    .collect::<Vec<_>>();
+
+  assert_eq!(
+    rows,
+    vec![
+      ("(no project)", 30, 0, 30),
+      ("fkbr.org", 180, 240, -60),
+      ("Internal", 0, 60, -60),
+    ]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_format_delta() {
+  assert_eq!(
+    format_delta(Duration::try_minutes(90).unwrap()),
+    "+01:30:00"
+  );
+  assert_eq!(
+    format_delta(Duration::try_minutes(-90).unwrap()),
+    "-01:30:00"
+  );
+  assert_eq!(format_delta(Duration::zero()), "+00:00:00");
+}
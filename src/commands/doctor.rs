@@ -0,0 +1,139 @@
+use chrono::{DateTime, Duration, Local, Utc};
+use colored::Colorize;
+use hhmmss::Hhmmss;
+use itertools::Itertools;
+use serde_json::json;
+
+use crate::{
+  cli::{output_values_json, Format, Output},
+  client::TogglApi,
+  config::is_non_billable_project,
+  model::{Project, Range, TimeEntry},
+};
+
+/// How far back time entries are checked for forgotten timers
+const DOCTOR_DAYS: i64 = 90;
+
+pub fn doctor(
+  debug: bool,
+  output: &Output,
+  non_billable_projects: &[String],
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let now = Utc::now();
+  let today = Local::now().date_naive();
+
+  let mut time_entries = client.get_time_entries(
+    debug,
+    &Range::FromTo(today - Duration::days(DOCTOR_DAYS), today),
+  )?;
+
+  let mut projects = vec![];
+
+  if !non_billable_projects.is_empty() {
+    for workspace_id in time_entries.iter().map(|e| e.wid).unique() {
+      projects.extend(client.get_workspace_projects(
+        debug,
+        true,
+        workspace_id,
+      )?);
+    }
+  }
+
+  let billable = billable_on_non_billable_projects(
+    &time_entries,
+    &projects,
+    non_billable_projects,
+  );
+
+  // the running time entry may be older than the checked range
+  time_entries.extend(client.get_current_time_entry(debug)?);
+
+  let forgotten = forgotten_time_entries(time_entries, now);
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &[json!({
+        "forgotten": forgotten,
+        "billable_on_non_billable_projects": billable,
+      })],
+    ),
+    Format::Raw | Format::Table
+      if forgotten.is_empty() && billable.is_empty() =>
+    {
+      println!("No problems found");
+    }
+    Format::Raw | Format::Table => {
+      let print = |line: String| {
+        if output.format == Format::Table {
+          println!("{}", line.red());
+        } else {
+          println!("{line}");
+        }
+      };
+
+      for time_entry in &billable {
+        print(format!(
+          "Time entry {} ({}) of {} is billable, but its project is one of 'non_billable_projects'",
+          time_entry.id,
+          time_entry.description.as_deref().unwrap_or("-"),
+          DateTime::<Local>::from(time_entry.start).format("%Y-%m-%d %H:%M"),
+        ));
+      }
+
+      for time_entry in &forgotten {
+        let line = format!(
+          "Time entry {} ({}) of workspace {} is running since {} ({})",
+          time_entry.id,
+          time_entry.description.as_deref().unwrap_or("-"),
+          time_entry.wid,
+          DateTime::<Local>::from(time_entry.start).format("%Y-%m-%d %H:%M"),
+          (now - time_entry.start).hhmmss()
+        );
+
+        print(line);
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Billable time entries of projects in 'non_billable_projects', oldest first
+pub(super) fn billable_on_non_billable_projects(
+  time_entries: &[TimeEntry],
+  projects: &[Project],
+  non_billable_projects: &[String],
+) -> Vec<TimeEntry> {
+  time_entries
+    .iter()
+    .filter(|time_entry| time_entry.billable.unwrap_or_default())
+    .filter(|time_entry| {
+      projects
+        .iter()
+        .find(|project| Some(project.id) == time_entry.pid)
+        .is_some_and(|project| {
+          is_non_billable_project(non_billable_projects, project)
+        })
+    })
+    .sorted_by_key(|time_entry| time_entry.start)
+    .cloned()
+    .collect()
+}
+
+/// Time entries of every workspace running for more than a day, oldest first
+pub(super) fn forgotten_time_entries(
+  time_entries: Vec<TimeEntry>,
+  now: DateTime<Utc>,
+) -> Vec<TimeEntry> {
+  time_entries
+    .into_iter()
+    .filter(|time_entry| {
+      time_entry.duration.is_negative()
+        && now - time_entry.start > Duration::days(1)
+    })
+    .unique_by(|time_entry| time_entry.id)
+    .sorted_by_key(|time_entry| time_entry.start)
+    .collect()
+}
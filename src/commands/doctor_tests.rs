@@ -0,0 +1,155 @@
+use crate::{
+  cli::{Format, Output},
+  commands::doctor::billable_on_non_billable_projects,
+  commands::doctor::forgotten_time_entries,
+  commands::time_entries::stale_warning,
+  model::{Project, TimeEntry},
+};
+use chrono::{DateTime, Utc};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::str::FromStr;
+
+#[test]
+fn test_forgotten_time_entries() -> anyhow::Result<()> {
+  let now = DateTime::<Utc>::from_str("2021-11-25T12:00:00Z")?;
+
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "start": "2021-11-23T08:00:00Z",
+      "duration": -1637654400
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-22T08:00:00Z",
+      "duration": 7200
+    },
+    {
+      "id": 3,
+      "wid": 7654321,
+      "start": "2021-11-25T08:00:00Z",
+      "duration": -1637827200
+    },
+    {
+      "id": 1,
+      "wid": 1234567,
+      "start": "2021-11-23T08:00:00Z",
+      "duration": -1637654400
+    }
+  ]))?;
+
+  assert_eq!(
+    forgotten_time_entries(time_entries, now)
+      .iter()
+      .map(|time_entry| time_entry.id)
+      .collect::<Vec<u64>>(),
+    vec![1]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_stale_warning() -> anyhow::Result<()> {
+  let now = DateTime::<Utc>::from_str("2021-11-25T12:00:00Z")?;
+
+  let time_entry = |start: &str, duration: i64| -> anyhow::Result<TimeEntry> {
+    Ok(serde_json::from_value(json!({
+      "id": 1,
+      "wid": 1234567,
+      "start": start,
+      "duration": duration
+    }))?)
+  };
+
+  assert_eq!(
+    stale_warning(
+      &Output::new(Format::Raw),
+      &time_entry("2021-11-25T08:00:00Z", -1637827200)?,
+      now
+    ),
+    None
+  );
+  assert_eq!(
+    stale_warning(
+      &Output::new(Format::Raw),
+      &time_entry("2021-11-24T08:00:00Z", 7200)?,
+      now
+    ),
+    None
+  );
+  assert!(stale_warning(
+    &Output::new(Format::Raw),
+    &time_entry("2021-11-24T08:00:00Z", -1637740800)?,
+    now
+  )
+  .is_some_and(|warning| warning.contains("28:00:00")));
+
+  Ok(())
+}
+
+#[test]
+fn test_billable_on_non_billable_projects() -> anyhow::Result<()> {
+  let projects: Vec<Project> = serde_json::from_value(json!([
+    { "id": 1, "name": "fkbr.org", "wid": 1234567, "status": "active" },
+    { "id": 2, "name": "Internal", "wid": 1234567, "status": "active" }
+  ]))?;
+
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "pid": 2,
+      "billable": true,
+      "start": "2021-11-23T08:00:00Z",
+      "duration": 3600
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "pid": 2,
+      "billable": false,
+      "start": "2021-11-22T08:00:00Z",
+      "duration": 3600
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "pid": 1,
+      "billable": true,
+      "start": "2021-11-22T09:00:00Z",
+      "duration": 3600
+    },
+    {
+      "id": 4,
+      "wid": 1234567,
+      "pid": 2,
+      "billable": true,
+      "start": "2021-11-22T10:00:00Z",
+      "duration": 3600
+    }
+  ]))?;
+
+  let billable = billable_on_non_billable_projects(
+    &time_entries,
+    &projects,
+    &["internal".to_string()],
+  );
+
+  assert_eq!(
+    billable
+      .iter()
+      .map(|time_entry| time_entry.id)
+      .collect::<Vec<u64>>(),
+    vec![4, 1]
+  );
+
+  assert!(
+    billable_on_non_billable_projects(&time_entries, &projects, &[]).is_empty()
+  );
+
+  Ok(())
+}
@@ -0,0 +1,364 @@
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use hhmmss::Hhmmss;
+use itertools::Itertools;
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+
+use clap::ValueEnum;
+use serde_json::{json, Map, Value};
+
+use crate::{
+  cli::{DateFormat, ExportAll, ExportPdf, ExportResource},
+  client::TogglApi,
+  commands::workspaces::selected_workspace_id,
+  config::Timesheet,
+  model::TimeEntry,
+};
+
+// A4 in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+
+const MARGIN: f32 = 50.0;
+const LINE_HEIGHT: f32 = 16.0;
+const FONT_SIZE: f32 = 10.0;
+
+const FONT: Name = Name(b"F1");
+const BOLD_FONT: Name = Name(b"F2");
+
+// x positions of the columns: Date, Weekday, Start, End, Break, Work
+const COLUMNS: [f32; 6] = [MARGIN, 130.0, 210.0, 280.0, 350.0, 430.0];
+
+#[derive(Debug, PartialEq)]
+pub struct TimesheetDay {
+  pub date: NaiveDate,
+  pub start: DateTime<Local>,
+  pub end: DateTime<Local>,
+  pub work: Duration,
+  pub r#break: Duration,
+}
+
+pub fn pdf(
+  debug: bool,
+  timesheet: &Timesheet,
+  date_format: &DateFormat,
+  export_pdf: &ExportPdf,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let time_entries = client.get_time_entries(debug, &export_pdf.range)?;
+  let days = timesheet_days(&time_entries);
+
+  let title = format!("Timesheet {}", export_pdf.range);
+
+  std::fs::write(
+    &export_pdf.output,
+    render_pdf(&title, timesheet, date_format, &days),
+  )?;
+
+  println!("Wrote timesheet to {:?}", export_pdf.output);
+
+  Ok(())
+}
+
+/// Resources of the workspace as one JSON object, e.g. only clients, projects
+/// and tags for provisioning
+pub fn all(
+  debug: bool,
+  export_all: &ExportAll,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let workspace_id = selected_workspace_id(debug, client)?;
+  let resources = selected_resources(&export_all.include, &export_all.exclude);
+
+  let mut export = Map::new();
+  export.insert("workspace_id".to_string(), json!(workspace_id));
+
+  for resource in &resources {
+    let (key, value) = match resource {
+      ExportResource::Clients => (
+        "clients",
+        json!(client
+          .get_workspace_clients(debug, true, workspace_id)?
+          .unwrap_or_default()),
+      ),
+      ExportResource::Projects => (
+        "projects",
+        json!(client.get_workspace_projects(debug, true, workspace_id)?),
+      ),
+      ExportResource::Tags => (
+        "tags",
+        json!(client.get_workspace_tags(debug, workspace_id)?),
+      ),
+      ExportResource::TimeEntries => (
+        "time_entries",
+        json!(client
+          .get_time_entries(debug, &export_all.range)?
+          .into_iter()
+          .filter(|time_entry| time_entry.wid == workspace_id)
+          .collect::<Vec<TimeEntry>>()),
+      ),
+    };
+
+    export.insert(key.to_string(), value);
+  }
+
+  std::fs::write(
+    &export_all.output,
+    serde_json::to_string_pretty(&Value::Object(export))?,
+  )?;
+
+  println!(
+    "Wrote {} to {:?}",
+    resources
+      .iter()
+      .filter_map(|resource| resource.to_possible_value())
+      .map(|value| value.get_name().to_string())
+      .join(", "),
+    export_all.output
+  );
+
+  Ok(())
+}
+
+/// --include (default: every kind) without the kinds of --exclude
+pub(super) fn selected_resources(
+  include: &[ExportResource],
+  exclude: &[ExportResource],
+) -> Vec<ExportResource> {
+  let include = if include.is_empty() {
+    ExportResource::value_variants()
+  } else {
+    include
+  };
+
+  ExportResource::value_variants()
+    .iter()
+    .filter(|resource| include.contains(resource))
+    .filter(|resource| !exclude.contains(resource))
+    .copied()
+    .collect()
+}
+
+/// One row per day, running time entries are left out
+pub(super) fn timesheet_days(time_entries: &[TimeEntry]) -> Vec<TimesheetDay> {
+  let time_entries_by_date = time_entries
+    .iter()
+    .filter(|time_entry| !time_entry.duration.is_negative())
+    .filter_map(|time_entry| {
+      time_entry.stop.map(|stop| {
+        (
+          DateTime::<Local>::from(time_entry.start),
+          DateTime::<Local>::from(stop),
+          Duration::try_seconds(time_entry.duration).unwrap_or_default(),
+        )
+      })
+    })
+    .into_group_map_by(|(start, _, _)| start.date_naive());
+
+  time_entries_by_date
+    .into_iter()
+    .sorted_by_key(|(date, _)| *date)
+    .filter_map(|(date, entries)| {
+      let start = entries.iter().map(|(start, _, _)| *start).min()?;
+      let end = entries.iter().map(|(_, end, _)| *end).max()?;
+      let work = entries
+        .iter()
+        .fold(Duration::zero(), |sum, (_, _, duration)| sum + *duration);
+
+      Some(TimesheetDay {
+        date,
+        start,
+        end,
+        work,
+        r#break: (end - start - work).max(Duration::zero()),
+      })
+    })
+    .collect()
+}
+
+struct Page {
+  content: Content,
+  y: f32,
+}
+
+impl Page {
+  fn new() -> Page {
+    Page {
+      content: Content::new(),
+      y: PAGE_HEIGHT - MARGIN,
+    }
+  }
+
+  fn text(&mut self, font: Name, x: f32, text: &str) {
+    self
+      .content
+      .begin_text()
+      .set_font(font, FONT_SIZE)
+      .next_line(x, self.y)
+      .show(Str(&latin1(text)))
+      .end_text();
+  }
+
+  fn row(&mut self, font: Name, cells: &[String]) {
+    for (x, cell) in COLUMNS.iter().zip(cells) {
+      self.text(font, *x, cell);
+    }
+
+    self.y -= LINE_HEIGHT;
+  }
+
+  fn line(&mut self, x1: f32, x2: f32) {
+    self
+      .content
+      .set_line_width(0.5)
+      .move_to(x1, self.y)
+      .line_to(x2, self.y)
+      .stroke();
+  }
+}
+
+pub(super) fn render_pdf(
+  title: &str,
+  timesheet: &Timesheet,
+  date_format: &DateFormat,
+  days: &[TimesheetDay],
+) -> Vec<u8> {
+  let mut pages = vec![];
+  let mut page = Page::new();
+
+  page.text(BOLD_FONT, MARGIN, title);
+  page.y -= LINE_HEIGHT * 2.0;
+
+  page.text(FONT, MARGIN, "Employee:");
+  page.text(
+    FONT,
+    130.0,
+    timesheet.employee.as_deref().unwrap_or_default(),
+  );
+  page.y -= LINE_HEIGHT;
+
+  page.text(FONT, MARGIN, "Company:");
+  page.text(
+    FONT,
+    130.0,
+    timesheet.company.as_deref().unwrap_or_default(),
+  );
+  page.y -= LINE_HEIGHT * 2.0;
+
+  let header = ["Date", "Weekday", "Start", "End", "Break", "Work"]
+    .map(|header| header.to_string());
+
+  page.row(BOLD_FONT, &header);
+
+  for day in days {
+    if page.y < MARGIN + LINE_HEIGHT {
+      pages.push(page);
+      page = Page::new();
+      page.row(BOLD_FONT, &header);
+    }
+
+    page.row(
+      FONT,
+      &[
+        date_format.date(&day.date),
+        date_format.weekday(&day.date),
+        day.start.format("%H:%M").to_string(),
+        day.end.format("%H:%M").to_string(),
+        day.r#break.hhmmss(),
+        day.work.hhmmss(),
+      ],
+    );
+  }
+
+  let total = days
+    .iter()
+    .fold(Duration::zero(), |sum, day| sum + day.work);
+
+  page.line(MARGIN, PAGE_WIDTH - MARGIN);
+  page.y -= LINE_HEIGHT;
+  page.row(
+    BOLD_FONT,
+    &[
+      "Total".to_string(),
+      "".to_string(),
+      "".to_string(),
+      "".to_string(),
+      "".to_string(),
+      total.hhmmss(),
+    ],
+  );
+
+  // signature lines need some space, otherwise they go onto a new page
+  if page.y < MARGIN + LINE_HEIGHT * 5.0 {
+    pages.push(page);
+    page = Page::new();
+  }
+
+  page.y -= LINE_HEIGHT * 3.0;
+  page.line(MARGIN, 270.0);
+  page.line(325.0, PAGE_WIDTH - MARGIN);
+  page.y -= LINE_HEIGHT;
+  page.text(FONT, MARGIN, "Date, signature employee");
+  page.text(FONT, 325.0, "Date, signature employer");
+
+  pages.push(page);
+
+  write_pdf(pages)
+}
+
+fn write_pdf(pages: Vec<Page>) -> Vec<u8> {
+  let catalog_id = Ref::new(1);
+  let page_tree_id = Ref::new(2);
+  let font_id = Ref::new(3);
+  let bold_font_id = Ref::new(4);
+
+  let page_ids = (0..pages.len())
+    .map(|index| Ref::new(5 + index as i32 * 2))
+    .collect::<Vec<Ref>>();
+
+  let mut pdf = Pdf::new();
+
+  pdf.catalog(catalog_id).pages(page_tree_id);
+  pdf
+    .pages(page_tree_id)
+    .kids(page_ids.iter().copied())
+    .count(pages.len() as i32);
+
+  pdf
+    .type1_font(font_id)
+    .base_font(Name(b"Helvetica"))
+    .encoding_predefined(Name(b"WinAnsiEncoding"));
+  pdf
+    .type1_font(bold_font_id)
+    .base_font(Name(b"Helvetica-Bold"))
+    .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+  for (page_id, page) in page_ids.into_iter().zip(pages) {
+    let content_id = Ref::new(page_id.get() + 1);
+
+    let mut pdf_page = pdf.page(page_id);
+    pdf_page
+      .media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+      .parent(page_tree_id)
+      .contents(content_id);
+
+    let mut resources = pdf_page.resources();
+    let mut fonts = resources.fonts();
+    fonts.pair(FONT, font_id);
+    fonts.pair(BOLD_FONT, bold_font_id);
+    fonts.finish();
+    resources.finish();
+    pdf_page.finish();
+
+    pdf.stream(content_id, &page.content.finish());
+  }
+
+  pdf.finish()
+}
+
+// WinAnsiEncoding covers latin1, everything else is replaced
+fn latin1(text: &str) -> Vec<u8> {
+  text
+    .chars()
+    .map(|c| u8::try_from(c as u32).unwrap_or(b'?'))
+    .collect()
+}
@@ -0,0 +1,99 @@
+use crate::{
+  cli::{DateFormat, ExportResource},
+  commands::export::{render_pdf, selected_resources, timesheet_days},
+  config::Timesheet,
+  model::TimeEntry,
+};
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::str::FromStr;
+
+fn time_entries() -> anyhow::Result<Vec<TimeEntry>> {
+  Ok(serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "start": "2021-11-22T08:00:00+01:00",
+      "stop": "2021-11-22T12:00:00+01:00",
+      "duration": 14400
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-22T13:00:00+01:00",
+      "stop": "2021-11-22T17:30:00+01:00",
+      "duration": 16200
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "start": "2021-11-23T09:00:00+01:00",
+      "duration": -1637654400
+    }
+  ]))?)
+}
+
+#[test]
+fn test_timesheet_days() -> anyhow::Result<()> {
+  let days = timesheet_days(&time_entries()?);
+
+  assert_eq!(days.len(), 1);
+
+  let day = days.first().unwrap();
+
+  assert_eq!(day.date, NaiveDate::from_ymd_opt(2021, 11, 22).unwrap());
+  assert_eq!(
+    day.start,
+    DateTime::<Local>::from_str("2021-11-22T08:00:00+01:00")?
+  );
+  assert_eq!(
+    day.end,
+    DateTime::<Local>::from_str("2021-11-22T17:30:00+01:00")?
+  );
+  assert_eq!(day.work, Duration::try_minutes(510).unwrap());
+  assert_eq!(day.r#break, Duration::try_hours(1).unwrap());
+
+  Ok(())
+}
+
+#[test]
+fn test_render_pdf() -> anyhow::Result<()> {
+  let timesheet = Timesheet {
+    employee: Some("Ralph Bower".to_string()),
+    company: Some("beta male gmbh".to_string()),
+  };
+
+  let pdf = render_pdf(
+    "Timesheet 2021-11-01 - 2021-11-30",
+    &timesheet,
+    &DateFormat::default(),
+    &timesheet_days(&time_entries()?),
+  );
+
+  let content = String::from_utf8_lossy(&pdf);
+
+  assert_eq!(content.starts_with("%PDF-"), true);
+  assert_eq!(content.contains("(Ralph Bower)"), true);
+  assert_eq!(content.contains("(08:30:00)"), true);
+
+  Ok(())
+}
+
+#[test]
+fn test_selected_resources() {
+  use ExportResource::*;
+
+  assert_eq!(
+    selected_resources(&[], &[]),
+    vec![Clients, Projects, Tags, TimeEntries]
+  );
+  assert_eq!(
+    selected_resources(&[], &[TimeEntries]),
+    vec![Clients, Projects, Tags]
+  );
+  assert_eq!(
+    selected_resources(&[TimeEntries, Projects], &[]),
+    vec![Projects, TimeEntries]
+  );
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, Datelike, Duration, Local, Weekday};
+use colored::Colorize;
+use dialoguer::{Input, Select};
+use hhmmss::Hhmmss;
+use itertools::Itertools;
+use serde_json::{json, Value};
+use term_table::{
+  row::Row, table_cell::Alignment, table_cell::TableCell, Table, TableStyle,
+};
+
+use crate::{
+  cli::{output_values_json, Format, Gaps, Output},
+  client::TogglApi,
+  model::TimeEntry,
+  resolver::Resolver,
+};
+
+/// Untracked time between two consecutive time entries of a day
+#[derive(Debug, PartialEq)]
+pub(super) struct Gap {
+  pub start: DateTime<Local>,
+  pub end: DateTime<Local>,
+}
+
+impl Gap {
+  pub fn duration(&self) -> Duration {
+    self.end - self.start
+  }
+}
+
+pub fn gaps(
+  debug: bool,
+  output: &Output,
+  gaps: &Gaps,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let time_entries = client.get_time_entries(debug, &gaps.range)?;
+  let mut untracked = untracked_gaps(&time_entries, gaps.min, Local::now());
+
+  if !gaps.include_weekends {
+    untracked.retain(|gap| !is_weekend(gap.start));
+  }
+
+  if untracked.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  if gaps.fill {
+    return fill(debug, &untracked, client);
+  }
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &untracked
+        .iter()
+        .map(|gap| {
+          json!({
+            "start": gap.start,
+            "end": gap.end,
+            "duration": gap.duration().num_seconds(),
+          })
+        })
+        .collect::<Vec<Value>>(),
+    ),
+    Format::Raw => {
+      for gap in &untracked {
+        println!(
+          "{} - {} {}",
+          gap.start.format("%Y-%m-%d %H:%M"),
+          gap.end.format("%H:%M"),
+          gap.duration().hhmmss()
+        );
+      }
+    }
+    Format::Table => {
+      let mut table = Table::new();
+      table.style = TableStyle::thin();
+
+      table.add_row(Row::new(vec![
+        TableCell::new("Date".bold().white()),
+        TableCell::new("Start".bold().white()),
+        TableCell::new("End".bold().white()),
+        TableCell::new("Duration".bold().white()),
+      ]));
+
+      for gap in &untracked {
+        table.add_row(Row::new(vec![
+          TableCell::new(gap.start.format("%Y-%m-%d")),
+          TableCell::new(gap.start.format("%H:%M")),
+          TableCell::new(gap.end.format("%H:%M")),
+          TableCell::builder(gap.duration().hhmmss())
+            .alignment(Alignment::Right)
+            .build(),
+        ]));
+      }
+
+      let total = untracked
+        .iter()
+        .fold(Duration::zero(), |total, gap| total + gap.duration());
+
+      table.add_row(Row::new(vec![
+        TableCell::builder("Total".bold()).col_span(3).build(),
+        TableCell::builder(total.hhmmss().bold())
+          .alignment(Alignment::Right)
+          .build(),
+      ]));
+
+      println!("{}", table.render());
+    }
+  }
+
+  Ok(())
+}
+
+/// Asks for the project and description of every gap and creates a time
+/// entry for it, gaps without a project stay untracked
+fn fill(
+  debug: bool,
+  untracked: &[Gap],
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+
+  let projects = client.get_workspace_projects(debug, false, workspace_id)?;
+  let items = std::iter::once("(skip)".to_string())
+    .chain(projects.iter().map(|project| project.name.clone()))
+    .collect::<Vec<String>>();
+
+  for gap in untracked {
+    let selection = Select::new()
+      .with_prompt(format!(
+        "Project of {} - {} ({})",
+        gap.start.format("%Y-%m-%d %H:%M"),
+        gap.end.format("%H:%M"),
+        gap.duration().hhmmss()
+      ))
+      .items(&items)
+      .default(0)
+      .interact()?;
+
+    let Some(project) = selection.checked_sub(1).map(|index| &projects[index])
+    else {
+      continue;
+    };
+
+    let description: String = Input::new()
+      .with_prompt("Description")
+      .allow_empty(true)
+      .interact_text()?;
+
+    let project_id =
+      resolver.project_id(workspace_id, &project.id.to_string())?;
+
+    let time_entry = client.create_time_entry(
+      debug,
+      &Some(description).filter(|description| !description.is_empty()),
+      workspace_id,
+      &None,
+      gap.duration(),
+      gap.start,
+      project_id,
+      None,
+      resolver.is_non_billable_project(workspace_id, project_id),
+    )?;
+
+    println!("Created time entry {}", time_entry.id);
+  }
+
+  Ok(())
+}
+
+fn is_weekend(date_time: DateTime<Local>) -> bool {
+  matches!(date_time.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Gaps of at least `min` between consecutive time entries of the same day,
+/// overlapping time entries are merged and running ones end `now`
+pub(super) fn untracked_gaps(
+  time_entries: &[TimeEntry],
+  min: Duration,
+  now: DateTime<Local>,
+) -> Vec<Gap> {
+  let mut gaps = vec![];
+  let mut previous_end: Option<DateTime<Local>> = None;
+
+  for time_entry in time_entries.iter().sorted_by_key(|entry| entry.start) {
+    let start = DateTime::<Local>::from(time_entry.start);
+    let end = if time_entry.duration.is_negative() {
+      now
+    } else {
+      start + Duration::seconds(time_entry.duration)
+    };
+
+    if let Some(previous) = previous_end {
+      if previous.date_naive() == start.date_naive() && start - previous >= min
+      {
+        gaps.push(Gap {
+          start: previous,
+          end: start,
+        });
+      }
+    }
+
+    previous_end = Some(previous_end.map_or(end, |previous| previous.max(end)));
+  }
+
+  gaps
+}
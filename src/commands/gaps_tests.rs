@@ -0,0 +1,67 @@
+use crate::{
+  commands::gaps::{untracked_gaps, Gap},
+  model::TimeEntry,
+};
+use chrono::{DateTime, Duration, Local};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn local(date_time: &str) -> anyhow::Result<DateTime<Local>> {
+  Ok(DateTime::parse_from_rfc3339(date_time)?.with_timezone(&Local))
+}
+
+#[test]
+fn test_untracked_gaps() -> anyhow::Result<()> {
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "start": "2021-11-22T12:00:00Z",
+      "duration": 3600
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-22T10:00:00Z",
+      "duration": 3600
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "start": "2021-11-22T10:30:00Z",
+      "duration": 1800
+    },
+    {
+      "id": 4,
+      "wid": 1234567,
+      "start": "2021-11-22T13:05:00Z",
+      "duration": 1800
+    },
+    {
+      "id": 5,
+      "wid": 1234567,
+      "start": "2021-11-22T14:00:00Z",
+      "duration": -1
+    }
+  ]))?;
+
+  assert_eq!(
+    untracked_gaps(
+      &time_entries,
+      Duration::minutes(10),
+      local("2021-11-22T15:00:00Z")?
+    ),
+    vec![
+      Gap {
+        start: local("2021-11-22T11:00:00Z")?,
+        end: local("2021-11-22T12:00:00Z")?,
+      },
+      Gap {
+        start: local("2021-11-22T13:35:00Z")?,
+        end: local("2021-11-22T14:00:00Z")?,
+      },
+    ]
+  );
+
+  Ok(())
+}
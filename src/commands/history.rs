@@ -0,0 +1,85 @@
+use colored::Colorize;
+use serde_json::json;
+use term_table::{row::Row, table_cell::TableCell, Table, TableStyle};
+
+use crate::{
+  cli::{output_values_json, Format, History, Output},
+  client::TogglApi,
+  history::{self, DescriptionVersion},
+  resolver::Resolver,
+};
+
+pub fn history(
+  debug: bool,
+  output: &Output,
+  history: &History,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let time_entry_id =
+    Resolver::new(debug, client).time_entry_id(&history.id)?;
+  let time_entry = client.get_time_entry(debug, time_entry_id)?;
+
+  let versions = history::versions(time_entry_id)?;
+
+  if versions.is_empty() {
+    println!("No previous descriptions of time entry {time_entry_id} found!");
+    return Ok(());
+  }
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &[json!({
+        "id": time_entry_id,
+        "description": time_entry.description,
+        "versions": versions,
+      })],
+    ),
+    Format::Raw => {
+      for version in &versions {
+        println!(
+          "{}\t{}",
+          version.changed_at.format("%Y-%m-%d %H:%M:%S"),
+          version.description.as_deref().unwrap_or_default()
+        );
+      }
+
+      println!(
+        "current\t{}",
+        time_entry.description.as_deref().unwrap_or_default()
+      );
+    }
+    Format::Table => {
+      output_versions_table(&versions, time_entry.description.as_deref())
+    }
+  }
+
+  Ok(())
+}
+
+fn output_versions_table(
+  versions: &[DescriptionVersion],
+  current: Option<&str>,
+) {
+  let mut table = Table::new();
+  table.style = TableStyle::thin();
+
+  table.add_row(Row::new(vec![
+    TableCell::new("Replaced at".bold().white()),
+    TableCell::new("Description".bold().white()),
+  ]));
+
+  for version in versions {
+    table.add_row(Row::new(vec![
+      TableCell::new(version.changed_at.format("%Y-%m-%d %H:%M:%S")),
+      TableCell::new(version.description.as_deref().unwrap_or("-")),
+    ]));
+  }
+
+  table.add_row(Row::new(vec![
+    TableCell::new("current".bold()),
+    TableCell::new(current.unwrap_or("-").bold()),
+  ]));
+
+  println!("{}", table.render());
+}
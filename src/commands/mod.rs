@@ -1,8 +1,42 @@
+pub mod breaks;
 pub mod clients;
+pub mod compare;
+pub mod doctor;
+pub mod export;
+pub mod gaps;
+pub mod history;
 pub mod projects;
 pub mod reports;
+pub mod stats;
+pub mod tags;
+pub mod tasks;
 pub mod time_entries;
+pub mod version;
 pub mod workspaces;
 
+#[cfg(test)]
+pub mod breaks_tests;
+#[cfg(test)]
+pub mod clients_tests;
+#[cfg(test)]
+pub mod compare_tests;
+#[cfg(test)]
+pub mod doctor_tests;
+#[cfg(test)]
+pub mod export_tests;
+#[cfg(test)]
+pub mod gaps_tests;
+#[cfg(test)]
+pub mod projects_tests;
+#[cfg(test)]
+pub mod reports_tests;
+#[cfg(test)]
+pub mod stats_tests;
+#[cfg(test)]
+pub mod tags_tests;
 #[cfg(test)]
 pub mod time_entries_tests;
+#[cfg(test)]
+pub mod version_tests;
+#[cfg(test)]
+pub mod workspaces_tests;
@@ -1,30 +1,47 @@
+use anyhow::anyhow;
 use colored::Colorize;
+use dialoguer::Confirm;
+use serde_json::{json, Value};
 use term_table::{row::Row, table_cell::TableCell, Table, TableStyle};
 
 use crate::{
-  cli::{output_values_json, Format},
-  client::TogglClient,
-  model::Project,
+  cli::{
+    output_values_json, ArchiveProject, CreateProject, DeleteProject,
+    EditProject, Format, Output,
+  },
+  client::TogglApi,
+  commands::workspaces::selected_workspace_id,
+  model::{Client, Project, ProjectUpdate},
+  resolver::Resolver,
 };
 
 pub fn list(
   debug: bool,
   include_archived: bool,
-  format: &Format,
-  client: &TogglClient,
+  resolve: bool,
+  output: &Output,
+  client: &dyn TogglApi,
 ) -> anyhow::Result<()> {
-  let me = client.get_me(debug)?;
-  let workspace_projects = client.get_workspace_projects(
-    debug,
-    include_archived,
-    me.default_workspace_id,
-  )?;
+  let workspace_id = selected_workspace_id(debug, client)?;
+  let workspace_projects =
+    client.get_workspace_projects(debug, include_archived, workspace_id)?;
 
   if workspace_projects.is_empty() {
     println!("No entries found!");
   } else {
-    match format {
-      Format::Json => output_values_json(&workspace_projects),
+    match output.format {
+      Format::Json if resolve => {
+        // archived projects may belong to archived clients
+        let clients = client
+          .get_workspace_clients(debug, true, workspace_id)?
+          .unwrap_or_default();
+
+        output_values_json(
+          output,
+          &with_clients(&workspace_projects, &clients)?,
+        )
+      }
+      Format::Json => output_values_json(output, &workspace_projects),
       Format::Raw => output_values_raw(&workspace_projects),
       Format::Table => output_values_table(&workspace_projects),
     }
@@ -33,6 +50,180 @@ pub fn list(
   Ok(())
 }
 
+pub fn create(
+  debug: bool,
+  output: &Output,
+  create_project: &CreateProject,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+
+  let client_id = create_project
+    .client
+    .as_ref()
+    .map(|selector| resolver.client_id(workspace_id, selector))
+    .transpose()?;
+
+  let template_id = create_project
+    .from_template
+    .as_ref()
+    .map(|selector| resolver.project_template_id(workspace_id, selector))
+    .transpose()?;
+
+  let project = client.create_project(
+    debug,
+    workspace_id,
+    &create_project.name,
+    client_id,
+    template_id,
+  )?;
+
+  output_projects(output, &[project]);
+
+  Ok(())
+}
+
+pub fn templates(
+  debug: bool,
+  output: &Output,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let workspace_id = selected_workspace_id(debug, client)?;
+  let templates = client.get_project_templates(debug, workspace_id)?;
+
+  if templates.is_empty() {
+    println!("No entries found!");
+  } else {
+    output_projects(output, &templates);
+  }
+
+  Ok(())
+}
+
+pub fn edit(
+  debug: bool,
+  output: &Output,
+  edit_project: &EditProject,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let project_id = resolver.project_id(workspace_id, &edit_project.id)?;
+
+  let client_id = edit_project
+    .client
+    .as_ref()
+    .map(|selector| resolver.client_id(workspace_id, selector))
+    .transpose()?;
+
+  let update = project_update(edit_project, client_id);
+
+  if update == ProjectUpdate::default() {
+    return Err(anyhow!(
+      "Nothing to change, use --name, --client, --billable, --color or --archive"
+    ));
+  }
+
+  let project =
+    client.update_project(debug, workspace_id, project_id, &update)?;
+
+  output_projects(output, &[project]);
+
+  Ok(())
+}
+
+pub fn archive(
+  debug: bool,
+  output: &Output,
+  archive_project: &ArchiveProject,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let project_id = resolver.project_id(workspace_id, &archive_project.id)?;
+
+  let update = ProjectUpdate {
+    active: Some(false),
+    ..ProjectUpdate::default()
+  };
+
+  let project =
+    client.update_project(debug, workspace_id, project_id, &update)?;
+
+  output_projects(output, &[project]);
+
+  Ok(())
+}
+
+pub fn delete(
+  debug: bool,
+  output: &Output,
+  delete_project: &DeleteProject,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let project_id = resolver.any_project_id(workspace_id, &delete_project.id)?;
+
+  if !delete_project.yes
+    && !Confirm::new()
+      .with_prompt(format!(
+        "Delete project '{}' ({project_id})?",
+        delete_project.id
+      ))
+      .default(false)
+      .interact()?
+  {
+    println!("Do nothing!");
+    return Ok(());
+  }
+
+  client.delete_project(debug, workspace_id, project_id)?;
+
+  list(debug, false, false, output, client)
+}
+
+pub(super) fn project_update(
+  edit_project: &EditProject,
+  client_id: Option<u64>,
+) -> ProjectUpdate {
+  ProjectUpdate {
+    name: edit_project.name.to_owned(),
+    client_id,
+    billable: edit_project.billable,
+    color: edit_project.color.to_owned(),
+    active: edit_project.archive.then_some(false),
+  }
+}
+
+/// Projects with the embedded client object (null without client)
+pub(super) fn with_clients(
+  projects: &[Project],
+  clients: &[Client],
+) -> anyhow::Result<Vec<Value>> {
+  projects
+    .iter()
+    .map(|project| {
+      let mut value = serde_json::to_value(project)?;
+
+      value["client"] = json!(project
+        .cid
+        .and_then(|cid| clients.iter().find(|client| client.id == cid)));
+
+      Ok(value)
+    })
+    .collect()
+}
+
+fn output_projects(output: &Output, projects: &[Project]) {
+  match output.format {
+    Format::Json => output_values_json(output, projects),
+    Format::Raw => output_values_raw(projects),
+    Format::Table => output_values_table(projects),
+  }
+}
+
 fn output_values_raw(values: &[Project]) {
   for project in values {
     println!("\"{}\"", project.name);
@@ -0,0 +1,81 @@
+use crate::{
+  cli::EditProject,
+  commands::projects::{project_update, with_clients},
+  model::{Client, Project, ProjectUpdate},
+};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test]
+fn test_with_clients() -> anyhow::Result<()> {
+  let projects: Vec<Project> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "name": "fkbr.org",
+      "wid": 1234567,
+      "status": "active",
+      "cid": 42
+    },
+    {
+      "id": 2,
+      "name": "Internal",
+      "wid": 1234567,
+      "status": "active",
+      "cid": null
+    }
+  ]))?;
+
+  let clients = vec![Client {
+    id: 42,
+    name: "ACME".to_string(),
+    archived: false,
+  }];
+
+  let resolved = with_clients(&projects, &clients)?;
+
+  assert_eq!(
+    resolved[0]["client"],
+    json!({ "id": 42, "name": "ACME", "archived": false })
+  );
+  assert_eq!(resolved[0]["name"], json!("fkbr.org"));
+  assert_eq!(resolved[1]["client"], json!(null));
+
+  Ok(())
+}
+
+#[test]
+fn test_project_update() {
+  let edit_project = EditProject {
+    id: "fkbr.org".to_string(),
+    name: Some("fkbr.com".to_string()),
+    client: Some("fkbr".to_string()),
+    billable: None,
+    color: None,
+    archive: true,
+  };
+
+  let update = project_update(&edit_project, Some(42));
+
+  assert_eq!(
+    serde_json::to_value(&update).unwrap(),
+    json!({
+      "name": "fkbr.com",
+      "client_id": 42,
+      "active": false
+    })
+  );
+
+  let edit_project = EditProject {
+    id: "fkbr.org".to_string(),
+    name: None,
+    client: None,
+    billable: None,
+    color: None,
+    archive: false,
+  };
+
+  assert_eq!(
+    project_update(&edit_project, None),
+    ProjectUpdate::default()
+  );
+}
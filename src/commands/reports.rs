@@ -1,13 +1,52 @@
-use chrono::{DateTime, Duration, Local};
-use chrono::{NaiveDate, Timelike};
+use chrono::{DateTime, Datelike, Duration, Local, Weekday};
+use chrono::{NaiveDate, TimeZone, Timelike};
+use chrono_tz::Tz;
 use colored::Colorize;
 use humantime::format_duration;
 use itertools::Itertools;
 
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::Write;
+
+use anyhow::anyhow;
+use clap::ValueEnum;
+use hhmmss::Hhmmss;
+use serde_json::{json, Value};
+use term_table::{row::Row, table_cell::TableCell, Table, TableStyle};
+
 use crate::{
-  client::TogglClient, model::Range, report_client::TogglReportClient,
+  cli::{
+    output_values_json, DateFormat, Detailed, Format, Output, ReportExport,
+    ReportField, Summary, SummaryField, SummaryGrouping,
+  },
+  client::TogglApi,
+  commands::{time_entries::logged_by_date, workspaces::selected_workspace_id},
+  config::Compliance,
+  model::{
+    Client, Project, Range, ReportDetails, ReportTimeEntry, TimeEntry,
+    WorkspaceUser,
+  },
+  report_client::{
+    requires_paid_plan, DetailedReport, TogglReportClient, PAID_PLAN_FALLBACK,
+  },
+  resolver::Resolver,
+  rounding::DurationRounding,
 };
 
+const NO_PROJECT: &str = "(no project)";
+const NO_CLIENT: &str = "(no client)";
+const NO_TAG: &str = "(no tag)";
+
+/// The given fields in their order, every field without any
+pub(super) fn selected_fields<F: ValueEnum + Copy>(fields: &[F]) -> Vec<F> {
+  if fields.is_empty() {
+    F::value_variants().to_vec()
+  } else {
+    fields.to_vec()
+  }
+}
+
 fn formatted_duration(duration: Duration) -> String {
   duration
     .to_std()
@@ -16,164 +55,1539 @@ fn formatted_duration(duration: Duration) -> String {
 
 pub fn detailed(
   debug: bool,
-  client: &TogglClient,
-  range: &Range,
+  compliance: &Compliance,
+  break_project: &str,
+  date_format: &DateFormat,
+  client: &dyn TogglApi,
+  detailed: &Detailed,
   report_client: &TogglReportClient,
 ) -> anyhow::Result<()> {
-  let me = client.get_me(debug)?;
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
 
-  let mut report_details = vec![];
+  if let Some(export @ (ReportExport::TogglCsv | ReportExport::Pdf)) =
+    &detailed.export
+  {
+    return toggl_export(debug, workspace_id, detailed, export, report_client);
+  }
+
+  // explicit breaks ('fbtoggl break') are no work, so they end up in the gaps
+  // between work entries and count as break like every other gap
+  let break_project_id = resolver.project_id(workspace_id, break_project).ok();
+
+  let report = match report_client.detailed(
+    debug,
+    workspace_id,
+    &detailed.range,
+    detailed.resume,
+  ) {
+    Err(err) if requires_paid_plan(&err) => {
+      eprintln!("{}", PAID_PLAN_FALLBACK.yellow());
+
+      let me = client.get_me(debug)?;
+      let time_entries =
+        own_time_entries(debug, workspace_id, &detailed.range, client)?;
+
+      DetailedReport {
+        details: report_details_from_time_entries(
+          me.fullname.as_deref().unwrap_or("me"),
+          &time_entries,
+        ),
+        failures: vec![],
+      }
+    }
+    report => report?,
+  };
 
-  let (next_row_number, details) =
-    report_client.details(debug, me.default_workspace_id, range, None)?;
+  let report_details = report
+    .details
+    .into_iter()
+    .filter(|detail| {
+      break_project_id.is_none() || detail.project_id != break_project_id
+    })
+    .collect::<Vec<_>>();
 
-  for detail in details {
-    report_details.push(detail);
+  match &detailed.display_tz {
+    Some(tz) => {
+      output_report(compliance, date_format, detailed, &report_details, tz)?
+    }
+    None => {
+      output_report(compliance, date_format, detailed, &report_details, &Local)?
+    }
   }
 
-  let mut outer_next_row_number = next_row_number;
+  if !report.failures.is_empty() {
+    eprintln!();
+    eprintln!(
+      "{}",
+      format!(
+        "Warning: {} report page(s) failed, the report is incomplete:",
+        report.failures.len()
+      )
+      .yellow()
+    );
 
-  while let Some(inner_next_row_number) = outer_next_row_number {
-    let (inner_next_row_number, details) = report_client.details(
-      debug,
-      me.default_workspace_id,
-      range,
-      Some(inner_next_row_number),
-    )?;
+    for failure in &report.failures {
+      eprintln!("  {failure}");
+    }
 
-    for detail in details {
-      report_details.push(detail);
+    eprintln!("Fetch the missing pages with --resume");
+  }
+
+  Ok(())
+}
+
+/// Exports of the Reports API, e.g. for the official PDF layout of Toggl
+fn toggl_export(
+  debug: bool,
+  workspace_id: u64,
+  detailed: &Detailed,
+  export: &ReportExport,
+  report_client: &TogglReportClient,
+) -> anyhow::Result<()> {
+  if !detailed.fields.is_empty() {
+    return Err(anyhow!("--fields is only supported by --export csv"));
+  }
+
+  let (name, output) = match export {
+    ReportExport::Pdf => (
+      "pdf",
+      Some(
+        detailed
+          .output
+          .as_ref()
+          .ok_or_else(|| anyhow!("--export pdf requires --output"))?,
+      ),
+    ),
+    _ => ("toggl-csv", detailed.output.as_ref()),
+  };
+
+  let file = match export {
+    ReportExport::Pdf => {
+      report_client.export_pdf(debug, workspace_id, &detailed.range)
+    }
+    _ => report_client.export_csv(debug, workspace_id, &detailed.range),
+  };
+
+  let file = match file {
+    Err(err) if requires_paid_plan(&err) => {
+      return Err(without_reports_api(&format!("--export {name}")))
     }
+    file => file?,
+  };
 
-    outer_next_row_number = inner_next_row_number;
+  match output {
+    Some(output) => {
+      std::fs::write(output, file)?;
+
+      println!("Wrote report to {output:?}");
+    }
+    None => std::io::stdout().write_all(&file)?,
   }
 
-  println!("Range: {range}");
+  Ok(())
+}
+
+pub fn summary(
+  debug: bool,
+  output: &Output,
+  client: &dyn TogglApi,
+  summary: &Summary,
+  rounding: Option<&DurationRounding>,
+  exchange_rates: &HashMap<String, f64>,
+  report_client: &TogglReportClient,
+) -> anyhow::Result<()> {
+  if summary.amounts && summary.grouping() != Some(SummaryGrouping::Project) {
+    return Err(anyhow!(
+      "--amounts is only available with --group-by project"
+    ));
+  }
+
+  if summary.round.is_some() && summary.grouping().is_some() {
+    return Err(anyhow!("--round is not available with --group-by"));
+  }
+
+  let workspace_id = selected_workspace_id(debug, client)?;
 
-  let time_entries_by_user = report_details
+  let user_id = if summary.team {
+    None
+  } else {
+    Some(
+      client
+        .get_me(debug)?
+        .id
+        .ok_or_else(|| anyhow!("Unknown user id"))?,
+    )
+  };
+
+  match summary.grouping() {
+    Some(SummaryGrouping::Weekday) => {
+      return weekday_summary(
+        debug,
+        output,
+        client,
+        workspace_id,
+        user_id,
+        &summary.range,
+        report_client,
+      );
+    }
+    Some(group_by) => {
+      return grouped_summary(
+        debug,
+        output,
+        client,
+        workspace_id,
+        user_id,
+        summary,
+        group_by,
+        exchange_rates,
+        report_client,
+      );
+    }
+    None => {}
+  }
+
+  let fields = selected_fields(&summary.fields);
+
+  let (by_project, billable) = match report_client.project_summary_by_user(
+    debug,
+    workspace_id,
+    user_id,
+    &summary.range,
+    false,
+  ) {
+    Ok(by_project) => {
+      // one request less if nobody is interested in billable hours
+      let billable = if fields.contains(&SummaryField::Billable) {
+        report_client.summary_by_user(
+          debug,
+          workspace_id,
+          user_id,
+          &summary.range,
+          true,
+        )?
+      } else {
+        HashMap::new()
+      };
+
+      (by_project, billable)
+    }
+    Err(err) if requires_paid_plan(&err) => {
+      let user_id = user_id.ok_or_else(|| without_reports_api("--team"))?;
+
+      eprintln!("{}", PAID_PLAN_FALLBACK.yellow());
+
+      let time_entries =
+        own_time_entries(debug, workspace_id, &summary.range, client)?;
+
+      (
+        HashMap::from([(user_id, logged_by_project(&time_entries))]),
+        HashMap::from([(user_id, logged_time(&time_entries, true))]),
+      )
+    }
+    Err(err) => return Err(err),
+  };
+
+  let total = by_project
     .iter()
-    .into_group_map_by(|a| a.username.to_owned());
+    .map(|(user_id, projects)| {
+      (
+        *user_id,
+        projects.values().fold(Duration::zero(), |a, b| a + *b),
+      )
+    })
+    .collect::<HashMap<u64, Duration>>();
 
-  if time_entries_by_user.is_empty() {
-    println!();
-    println!("No time entries found.");
+  let users = client.get_workspace_users(debug, workspace_id)?;
 
-    return Ok(());
+  let mut user_summaries = user_summaries(&total, &billable, &users);
+
+  // the project names are only needed to show them
+  if fields.contains(&SummaryField::Projects) {
+    let projects = client.get_workspace_projects(debug, true, workspace_id)?;
+
+    for user_summary in &mut user_summaries {
+      if let Some(durations) = by_project.get(&user_summary.user_id) {
+        user_summary.projects = project_durations(durations, &projects);
+      }
+    }
   }
 
-  for (user, details) in time_entries_by_user {
-    let mut total_seconds = Duration::zero();
+  if let Some(rounding) = rounding {
+    round_user_summaries(&mut user_summaries, rounding);
+  }
 
-    for detail in &details {
-      for time_entry in &detail.time_entries {
-        total_seconds += Duration::try_seconds(time_entry.seconds as i64)
-          .unwrap_or(Duration::zero());
+  if user_summaries.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &user_summaries
+        .iter()
+        .map(|user_summary| user_summary_json(user_summary, &fields))
+        .collect::<Vec<Value>>(),
+    ),
+    Format::Raw => {
+      for user_summary in &user_summaries {
+        println!(
+          "{}",
+          fields
+            .iter()
+            .map(|field| match field {
+              SummaryField::User => user_summary.name.to_owned(),
+              SummaryField::Total => user_summary.total.hhmmss(),
+              SummaryField::Billable => user_summary.billable.hhmmss(),
+              SummaryField::Projects => user_summary
+                .projects
+                .iter()
+                .map(|(project, duration)| {
+                  format!("{project}={}", duration.hhmmss())
+                })
+                .join(", "),
+            })
+            .join("\t")
+        );
       }
     }
+    Format::Table => output_user_summaries_table(&user_summaries, &fields),
+  }
 
-    println!();
-    println!(
-      "{} - {} hours ({})",
-      user,
-      total_seconds.num_hours(),
-      formatted_duration(total_seconds)
-    );
-    println!();
+  Ok(())
+}
+
+#[derive(Debug)]
+pub(super) struct UserSummary {
+  pub user_id: u64,
+  pub name: String,
+  pub total: Duration,
+  pub billable: Duration,
+  /// Logged time per project name, most time first
+  pub projects: Vec<(String, Duration)>,
+}
+
+/// Rounds every shown duration on its own, the total of a user is not the
+/// sum of its rounded projects
+pub(super) fn round_user_summaries(
+  user_summaries: &mut [UserSummary],
+  rounding: &DurationRounding,
+) {
+  for user_summary in user_summaries {
+    user_summary.total = rounding.round(user_summary.total);
+    user_summary.billable = rounding.round(user_summary.billable);
+
+    for (_, duration) in &mut user_summary.projects {
+      *duration = rounding.round(*duration);
+    }
+  }
+}
+
+/// Users with logged time, most hours first
+pub(super) fn user_summaries(
+  total: &HashMap<u64, Duration>,
+  billable: &HashMap<u64, Duration>,
+  users: &[WorkspaceUser],
+) -> Vec<UserSummary> {
+  total
+    .iter()
+    .map(|(user_id, total)| UserSummary {
+      user_id: *user_id,
+      name: users
+        .iter()
+        .find(|user| user.id == *user_id)
+        .map(|user| user.fullname.to_owned())
+        .unwrap_or_else(|| user_id.to_string()),
+      total: *total,
+      billable: billable
+        .get(user_id)
+        .copied()
+        .unwrap_or_else(Duration::zero),
+      projects: vec![],
+    })
+    .sorted_by(|a, b| b.total.cmp(&a.total).then(a.name.cmp(&b.name)))
+    .collect()
+}
 
-    let mut time_entries = vec![];
+/// Logged time per project name, most time first
+pub(super) fn project_durations(
+  durations: &HashMap<Option<u64>, Duration>,
+  projects: &[Project],
+) -> Vec<(String, Duration)> {
+  durations
+    .iter()
+    .map(|(project_id, duration)| {
+      let name = match project_id {
+        Some(project_id) => projects
+          .iter()
+          .find(|project| project.id == *project_id)
+          .map(|project| project.name.to_owned())
+          .unwrap_or_else(|| project_id.to_string()),
+        None => NO_PROJECT.to_string(),
+      };
 
-    for detail in &details {
-      for time_entry in &detail.time_entries {
-        time_entries.push(time_entry);
+      (name, *duration)
+    })
+    .sorted_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)))
+    .collect()
+}
+
+pub(super) fn user_summary_json(
+  user_summary: &UserSummary,
+  fields: &[SummaryField],
+) -> Value {
+  let mut value = json!({});
+
+  for field in fields {
+    match field {
+      SummaryField::User => {
+        value["user_id"] = json!(user_summary.user_id);
+        value["name"] = json!(user_summary.name);
+      }
+      SummaryField::Total => {
+        value["total"] = json!(user_summary.total.num_seconds());
+      }
+      SummaryField::Billable => {
+        value["billable"] = json!(user_summary.billable.num_seconds());
+      }
+      SummaryField::Projects => {
+        value["projects"] = json!(user_summary
+          .projects
+          .iter()
+          .map(|(project, duration)| {
+            json!({ "project": project, "total": duration.num_seconds() })
+          })
+          .collect::<Vec<Value>>());
       }
     }
+  }
+
+  value
+}
+
+fn output_user_summaries_table(
+  user_summaries: &[UserSummary],
+  fields: &[SummaryField],
+) {
+  let mut table = Table::new();
+  table.style = TableStyle::thin();
 
-    let time_entries_by_date = time_entries
+  // the share of billable hours needs both of them
+  let with_percentage = fields.contains(&SummaryField::Total)
+    && fields.contains(&SummaryField::Billable);
+
+  let mut header = fields
+    .iter()
+    .map(|field| match field {
+      SummaryField::User => "User",
+      SummaryField::Total => "Total",
+      SummaryField::Billable => "Billable",
+      SummaryField::Projects => "Projects",
+    })
+    .collect::<Vec<&str>>();
+
+  if with_percentage {
+    header.push("Billable %");
+  }
+
+  table.add_row(Row::new(
+    header
+      .into_iter()
+      .map(|title| TableCell::new(title.bold().white())),
+  ));
+
+  for user_summary in user_summaries {
+    let mut cells = fields
       .iter()
-      .into_group_map_by(|time_entry| time_entry.start.date_naive());
+      .map(|field| match field {
+        SummaryField::User => TableCell::new(&user_summary.name),
+        SummaryField::Total => TableCell::new(user_summary.total.hhmmss()),
+        SummaryField::Billable => {
+          TableCell::new(user_summary.billable.hhmmss())
+        }
+        SummaryField::Projects => TableCell::new(
+          user_summary
+            .projects
+            .iter()
+            .map(|(project, duration)| {
+              format!("{project}: {}", duration.hhmmss())
+            })
+            .join("\n"),
+        ),
+      })
+      .collect::<Vec<TableCell>>();
 
-    let mut dates = time_entries_by_date.keys().collect::<Vec<&NaiveDate>>();
-    dates.sort();
+    if with_percentage {
+      cells.push(TableCell::new(percentage(
+        user_summary.billable,
+        user_summary.total,
+      )));
+    }
 
-    for date in dates {
-      let time_entries = time_entries_by_date.get(date).unwrap();
+    table.add_row(Row::new(cells));
+  }
 
-      let hours = time_entries
-        .iter()
-        .flat_map(|time_entry| Duration::try_seconds(time_entry.seconds as i64))
-        .fold(Duration::zero(), |a, b| a + b);
+  let total = user_summaries
+    .iter()
+    .fold(Duration::zero(), |sum, user_summary| {
+      sum + user_summary.total
+    });
+  let billable = user_summaries
+    .iter()
+    .fold(Duration::zero(), |sum, user_summary| {
+      sum + user_summary.billable
+    });
 
-      let start = time_entries
+  let mut cells = fields
+    .iter()
+    .map(|field| match field {
+      SummaryField::User => TableCell::new("Total".bold()),
+      SummaryField::Total => TableCell::new(total.hhmmss().bold()),
+      SummaryField::Billable => TableCell::new(billable.hhmmss().bold()),
+      SummaryField::Projects => TableCell::new(""),
+    })
+    .collect::<Vec<TableCell>>();
+
+  if with_percentage {
+    cells.push(TableCell::new(percentage(billable, total).bold()));
+  }
+
+  table.add_row(Row::new(cells));
+
+  println!("{}", table.render());
+}
+
+/// Logged time per day, from the time entries without Reports API access
+fn daily_totals(
+  debug: bool,
+  client: &dyn TogglApi,
+  workspace_id: u64,
+  user_id: Option<u64>,
+  range: &Range,
+  report_client: &TogglReportClient,
+) -> anyhow::Result<HashMap<NaiveDate, Duration>> {
+  match report_client.daily_totals(debug, workspace_id, user_id, range) {
+    Err(err) if requires_paid_plan(&err) => {
+      if user_id.is_none() {
+        return Err(without_reports_api("--team"));
+      }
+
+      eprintln!("{}", PAID_PLAN_FALLBACK.yellow());
+
+      Ok(logged_by_date(&own_time_entries(
+        debug,
+        workspace_id,
+        range,
+        client,
+      )?))
+    }
+    daily_totals => daily_totals?
+      .ok_or_else(|| anyhow!("The API did not answer with daily totals")),
+  }
+}
+
+/// Logged time per project id
+type ProjectTotals = HashMap<Option<u64>, Duration>;
+
+/// Logged time per project of all users, from the time entries without
+/// Reports API access, with `billable` also the billable time per project
+fn project_totals(
+  debug: bool,
+  client: &dyn TogglApi,
+  workspace_id: u64,
+  user_id: Option<u64>,
+  range: &Range,
+  billable: bool,
+  report_client: &TogglReportClient,
+) -> anyhow::Result<(ProjectTotals, ProjectTotals)> {
+  let all_users = |by_project: HashMap<u64, HashMap<Option<u64>, Duration>>| {
+    by_project
+      .into_values()
+      .flatten()
+      .into_grouping_map()
+      .fold(Duration::zero(), |sum, _, duration| sum + duration)
+  };
+
+  match report_client.project_summary_by_user(
+    debug,
+    workspace_id,
+    user_id,
+    range,
+    false,
+  ) {
+    Ok(by_project) => {
+      let billable_by_project = if billable {
+        all_users(report_client.project_summary_by_user(
+          debug,
+          workspace_id,
+          user_id,
+          range,
+          true,
+        )?)
+      } else {
+        HashMap::new()
+      };
+
+      Ok((all_users(by_project), billable_by_project))
+    }
+    Err(err) if requires_paid_plan(&err) => {
+      if user_id.is_none() {
+        return Err(without_reports_api("--team"));
+      }
+
+      eprintln!("{}", PAID_PLAN_FALLBACK.yellow());
+
+      let time_entries = own_time_entries(debug, workspace_id, range, client)?;
+
+      let billable_time_entries = time_entries
         .iter()
-        .min_by_key(|time_entry| time_entry.start)
-        .map(|time_entry| DateTime::<Local>::from(time_entry.start));
+        .filter(|time_entry| time_entry.billable.unwrap_or_default())
+        .cloned()
+        .collect::<Vec<TimeEntry>>();
 
-      let end = time_entries
+      Ok((
+        logged_by_project(&time_entries),
+        logged_by_project(&billable_time_entries),
+      ))
+    }
+    Err(err) => Err(err),
+  }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn grouped_summary(
+  debug: bool,
+  output: &Output,
+  client: &dyn TogglApi,
+  workspace_id: u64,
+  user_id: Option<u64>,
+  summary: &Summary,
+  group_by: SummaryGrouping,
+  exchange_rates: &HashMap<String, f64>,
+  report_client: &TogglReportClient,
+) -> anyhow::Result<()> {
+  let range = &summary.range;
+
+  let groups = match group_by {
+    SummaryGrouping::Project => {
+      let (totals, billable) = project_totals(
+        debug,
+        client,
+        workspace_id,
+        user_id,
+        range,
+        true,
+        report_client,
+      )?;
+
+      let projects =
+        client.get_workspace_projects(debug, true, workspace_id)?;
+
+      let mut project_summaries =
+        project_summaries(&totals, &billable, &projects);
+
+      if summary.amounts {
+        match &summary.convert_to {
+          Some(currency) => {
+            project_summaries = converted_project_summaries(
+              project_summaries,
+              currency,
+              exchange_rates,
+            )?;
+          }
+          None => {
+            let currencies = amount_currencies(&project_summaries);
+
+            if currencies.len() > 1 {
+              eprintln!(
+                "{}",
+                format!(
+                  "Amounts in {} are totaled per currency, use --convert-to for a single total",
+                  currencies
+                    .iter()
+                    .map(|currency| currency.as_deref().unwrap_or("(none)"))
+                    .join(", ")
+                )
+                .yellow()
+              );
+            }
+          }
+        }
+      }
+
+      return output_project_summaries(
+        output,
+        &project_summaries,
+        summary.amounts,
+      );
+    }
+    SummaryGrouping::Client => {
+      let (totals, _) = project_totals(
+        debug,
+        client,
+        workspace_id,
+        user_id,
+        range,
+        false,
+        report_client,
+      )?;
+
+      let projects =
+        client.get_workspace_projects(debug, true, workspace_id)?;
+
+      let clients = client
+        .get_workspace_clients(debug, true, workspace_id)?
+        .unwrap_or_default();
+
+      client_durations(&totals, &projects, &clients)
+    }
+    // tags are not part of the summary of the Reports API
+    SummaryGrouping::Tag => {
+      if user_id.is_none() {
+        return Err(anyhow!("--team is not available with --group-by tag"));
+      }
+
+      tag_durations(&own_time_entries(debug, workspace_id, range, client)?)
+    }
+    SummaryGrouping::Day | SummaryGrouping::Weekday => {
+      daily_totals(debug, client, workspace_id, user_id, range, report_client)?
+        .into_iter()
+        .sorted_by_key(|(date, _)| *date)
+        .map(|(date, duration)| (output.format_date(&date), duration))
+        .collect()
+    }
+  };
+
+  if groups.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  let name = match group_by {
+    SummaryGrouping::Project => "project",
+    SummaryGrouping::Client => "client",
+    SummaryGrouping::Tag => "tag",
+    SummaryGrouping::Day | SummaryGrouping::Weekday => "day",
+  };
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &groups
         .iter()
-        .max_by_key(|time_entry| time_entry.stop)
-        .map(|time_entry| DateTime::<Local>::from(time_entry.stop));
+        .map(|(group, duration)| {
+          json!({ name: group, "total": duration.num_seconds() })
+        })
+        .collect::<Vec<Value>>(),
+    ),
+    Format::Raw => {
+      for (group, duration) in &groups {
+        println!("{group}\t{}", duration.hhmmss());
+      }
+    }
+    Format::Table => {
+      let mut table = Table::new();
+      table.style = TableStyle::thin();
 
-      let r#break = if let (Some(start), Some(end)) = (start, end) {
-        let total = end - start;
+      let mut title = name.to_string();
+      title[..1].make_ascii_uppercase();
 
-        Some(total - hours)
-      } else {
-        None
+      table.add_row(Row::new(
+        [title.as_str(), "Total"]
+          .into_iter()
+          .map(|title| TableCell::new(title.bold().white())),
+      ));
+
+      for (group, duration) in &groups {
+        table.add_row(Row::new(vec![
+          TableCell::new(group),
+          TableCell::new(duration.hhmmss()),
+        ]));
+      }
+
+      // time entries with several tags count for each of them
+      if group_by != SummaryGrouping::Tag {
+        let total = groups
+          .iter()
+          .fold(Duration::zero(), |sum, (_, duration)| sum + *duration);
+
+        table.add_row(Row::new(vec![
+          TableCell::new("Total".bold()),
+          TableCell::new(total.hhmmss().bold()),
+        ]));
+      }
+
+      println!("{}", table.render());
+    }
+  }
+
+  Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct ProjectSummary {
+  pub name: String,
+  pub total: Duration,
+  pub billable: Duration,
+  /// Billable hours times the rate of the project
+  pub amount: Option<f64>,
+  pub currency: Option<String>,
+}
+
+/// Logged and billable time per project, most time first
+pub(super) fn project_summaries(
+  totals: &HashMap<Option<u64>, Duration>,
+  billable: &HashMap<Option<u64>, Duration>,
+  projects: &[Project],
+) -> Vec<ProjectSummary> {
+  totals
+    .iter()
+    .map(|(project_id, total)| {
+      let project = project_id.and_then(|project_id| {
+        projects.iter().find(|project| project.id == project_id)
+      });
+
+      let name = match (project, project_id) {
+        (Some(project), _) => project.name.to_owned(),
+        (None, Some(project_id)) => project_id.to_string(),
+        (None, None) => NO_PROJECT.to_string(),
       };
 
-      let mut warnings = vec![];
+      let billable = billable.get(project_id).copied().unwrap_or_default();
 
-      if hours.num_hours() > 10 {
-        warnings.push("More than 10 hours".red().to_string());
+      ProjectSummary {
+        name,
+        total: *total,
+        billable,
+        amount: project
+          .and_then(|project| project.rate)
+          .map(|rate| rate * billable.num_seconds() as f64 / 3600.0),
+        currency: project.and_then(|project| project.currency.to_owned()),
       }
+    })
+    .sorted_by(|a, b| b.total.cmp(&a.total).then(a.name.cmp(&b.name)))
+    .collect()
+}
+
+/// Share of the part in percent, '-' without any time at all
+fn percentage(part: Duration, whole: Duration) -> String {
+  if whole.is_zero() {
+    "-".to_string()
+  } else {
+    format!(
+      "{:.0}",
+      part.num_seconds() as f64 * 100.0 / whole.num_seconds() as f64
+    )
+  }
+}
 
-      if let Some(start) = start {
-        if start.time().hour() < 6 {
-          warnings.push("Start time is before 6am".red().to_string());
+fn formatted_amount(amount: f64, currency: Option<&str>) -> String {
+  format!("{amount:.2} {}", currency.unwrap_or_default())
+    .trim_end()
+    .to_string()
+}
+
+/// Sum of the amounts per currency, e.g. '120.00 EUR, 40.00 USD'
+pub(super) fn total_amounts(project_summaries: &[ProjectSummary]) -> String {
+  let amounts = project_summaries
+    .iter()
+    .filter_map(|project_summary| {
+      project_summary
+        .amount
+        .map(|amount| (project_summary.currency.to_owned(), amount))
+    })
+    .into_grouping_map()
+    .sum()
+    .into_iter()
+    .sorted_by(|a, b| a.0.cmp(&b.0))
+    .map(|(currency, amount)| formatted_amount(amount, currency.as_deref()))
+    .join(", ");
+
+  if amounts.is_empty() {
+    "-".to_string()
+  } else {
+    amounts
+  }
+}
+
+/// Distinct currencies of the amounts, sorted
+pub(super) fn amount_currencies(
+  project_summaries: &[ProjectSummary],
+) -> Vec<Option<String>> {
+  project_summaries
+    .iter()
+    .filter(|project_summary| project_summary.amount.is_some())
+    .map(|project_summary| project_summary.currency.to_owned())
+    .unique()
+    .sorted()
+    .collect()
+}
+
+/// Amounts converted into `currency`, `exchange_rates` are the values of one
+/// unit of every currency in a common reference currency
+pub(super) fn converted_project_summaries(
+  project_summaries: Vec<ProjectSummary>,
+  currency: &str,
+  exchange_rates: &HashMap<String, f64>,
+) -> anyhow::Result<Vec<ProjectSummary>> {
+  let exchange_rate = |currency: &str| {
+    exchange_rates
+      .get(currency)
+      .copied()
+      .filter(|rate| *rate > 0.0)
+      .ok_or_else(|| {
+        anyhow!(
+          "No exchange rate of {currency} in 'exchange_rates' of settings.toml"
+        )
+      })
+  };
+
+  let target_rate = exchange_rate(currency)?;
+
+  project_summaries
+    .into_iter()
+    .map(|project_summary| {
+      let Some(amount) = project_summary.amount else {
+        return Ok(project_summary);
+      };
+
+      let from = project_summary.currency.as_deref().ok_or_else(|| {
+        anyhow!(
+          "Amount of '{}' has no currency to convert from",
+          project_summary.name
+        )
+      })?;
+
+      let amount = if from == currency {
+        amount
+      } else {
+        amount * exchange_rate(from)? / target_rate
+      };
+
+      Ok(ProjectSummary {
+        amount: Some(amount),
+        currency: Some(currency.to_string()),
+        ..project_summary
+      })
+    })
+    .collect()
+}
+
+fn output_project_summaries(
+  output: &Output,
+  project_summaries: &[ProjectSummary],
+  amounts: bool,
+) -> anyhow::Result<()> {
+  if project_summaries.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  let total = project_summaries
+    .iter()
+    .fold(Duration::zero(), |sum, project_summary| {
+      sum + project_summary.total
+    });
+  let billable = project_summaries
+    .iter()
+    .fold(Duration::zero(), |sum, project_summary| {
+      sum + project_summary.billable
+    });
+
+  let amount = |project_summary: &ProjectSummary| {
+    project_summary
+      .amount
+      .map(|amount| {
+        formatted_amount(amount, project_summary.currency.as_deref())
+      })
+      .unwrap_or_else(|| "-".to_string())
+  };
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &project_summaries
+        .iter()
+        .map(|project_summary| {
+          let mut value = json!({
+            "project": project_summary.name,
+            "total": project_summary.total.num_seconds(),
+            "billable": project_summary.billable.num_seconds(),
+          });
+
+          if amounts {
+            value["amount"] = json!(project_summary.amount);
+            value["currency"] = json!(project_summary.currency);
+          }
+
+          value
+        })
+        .collect::<Vec<Value>>(),
+    ),
+    Format::Raw => {
+      for project_summary in project_summaries {
+        let mut columns = vec![
+          project_summary.name.to_owned(),
+          project_summary.total.hhmmss(),
+          percentage(project_summary.total, total),
+          project_summary.billable.hhmmss(),
+        ];
+
+        if amounts {
+          columns.push(amount(project_summary));
         }
+
+        println!("{}", columns.join("\t"));
       }
+    }
+    Format::Table => {
+      let mut table = Table::new();
+      table.style = TableStyle::thin();
 
-      if let Some(end) = end {
-        if end.time().hour() > 22 {
-          warnings.push("End time is after 10pm".red().to_string());
-        }
+      let mut header = vec!["Project", "Total", "Share %", "Billable"];
+
+      if amounts {
+        header.push("Amount");
       }
 
-      let hours_formatted = formatted_duration(hours);
-
-      // https://www.gesetze-im-internet.de/arbzg/__4.html#:~:text=Arbeitszeitgesetz%20(ArbZG),neun%20Stunden%20insgesamt%20zu%20unterbrechen.
-      let formatted_break = if let Some(r#break) = r#break {
-        // between 6 and less than 10 hours, break has to be at least 30 minutes
-        if (hours > Duration::try_hours(6).unwrap()
-          && hours < Duration::try_hours(10).unwrap())
-          && r#break < Duration::try_minutes(30).unwrap()
-        {
-          warnings.push(
-              format!(
-                "Worked for {hours_formatted} => break should be at least 30 minutes!"
-              )
-              .red()
-              .to_string(),
-            );
-        }
-        // more than 9 hours, break has to be at least 45 minutes
-        else if hours > Duration::try_hours(9).unwrap()
-          && r#break < Duration::try_minutes(45).unwrap()
-        {
-          warnings.push(
-              format!(
-                "Worked for {hours_formatted} => break should be at least 45 minutes!"
-              )
-              .red()
-              .to_string(),
-            );
+      table.add_row(Row::new(
+        header
+          .into_iter()
+          .map(|title| TableCell::new(title.bold().white())),
+      ));
+
+      for project_summary in project_summaries {
+        let mut cells = vec![
+          TableCell::new(&project_summary.name),
+          TableCell::new(project_summary.total.hhmmss()),
+          TableCell::new(percentage(project_summary.total, total)),
+          TableCell::new(project_summary.billable.hhmmss()),
+        ];
+
+        if amounts {
+          cells.push(TableCell::new(amount(project_summary)));
         }
 
-        format!(", Break: {}", formatted_duration(r#break))
+        table.add_row(Row::new(cells));
+      }
+
+      let mut cells = vec![
+        TableCell::new("Total".bold()),
+        TableCell::new(total.hhmmss().bold()),
+        TableCell::new(percentage(total, total).bold()),
+        TableCell::new(billable.hhmmss().bold()),
+      ];
+
+      if amounts {
+        cells.push(TableCell::new(total_amounts(project_summaries).bold()));
+      }
+
+      table.add_row(Row::new(cells));
+
+      println!("{}", table.render());
+    }
+  }
+
+  Ok(())
+}
+
+/// Logged time per client name, most time first
+pub(super) fn client_durations(
+  durations: &HashMap<Option<u64>, Duration>,
+  projects: &[Project],
+  clients: &[Client],
+) -> Vec<(String, Duration)> {
+  durations
+    .iter()
+    .map(|(project_id, duration)| {
+      let client_id = project_id
+        .and_then(|project_id| projects.iter().find(|p| p.id == project_id))
+        .and_then(|project| project.cid);
+
+      let name = match client_id {
+        Some(client_id) => clients
+          .iter()
+          .find(|client| client.id == client_id)
+          .map(|client| client.name.to_owned())
+          .unwrap_or_else(|| client_id.to_string()),
+        None => NO_CLIENT.to_string(),
+      };
+
+      (name, *duration)
+    })
+    .into_grouping_map()
+    .fold(Duration::zero(), |sum, _, duration| sum + duration)
+    .into_iter()
+    .sorted_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)))
+    .collect()
+}
+
+/// Logged time of stopped time entries per tag, most time first, time
+/// entries count for each of their tags
+pub(super) fn tag_durations(
+  time_entries: &[TimeEntry],
+) -> Vec<(String, Duration)> {
+  time_entries
+    .iter()
+    .filter(|time_entry| time_entry.duration >= 0)
+    .flat_map(|time_entry| {
+      let duration =
+        Duration::try_seconds(time_entry.duration).unwrap_or_default();
+
+      let tags = time_entry
+        .tags
+        .to_owned()
+        .filter(|tags| !tags.is_empty())
+        .unwrap_or_else(|| vec![NO_TAG.to_string()]);
+
+      tags.into_iter().map(move |tag| (tag, duration))
+    })
+    .into_grouping_map()
+    .fold(Duration::zero(), |sum, _, duration| sum + duration)
+    .into_iter()
+    .sorted_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)))
+    .collect()
+}
+
+fn weekday_summary(
+  debug: bool,
+  output: &Output,
+  client: &dyn TogglApi,
+  workspace_id: u64,
+  user_id: Option<u64>,
+  range: &Range,
+  report_client: &TogglReportClient,
+) -> anyhow::Result<()> {
+  let daily_totals =
+    daily_totals(debug, client, workspace_id, user_id, range, report_client)?;
+
+  let (start, end) = range.as_range()?;
+
+  // the end of a range is exclusive and future days are not logged yet
+  let last_day = (end - Duration::try_seconds(1).unwrap())
+    .date_naive()
+    .min(Local::now().date_naive());
+
+  let weekday_summaries =
+    weekday_summaries(&daily_totals, start.date_naive(), last_day);
+
+  if weekday_summaries
+    .iter()
+    .all(|summary| summary.total.is_zero())
+  {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &weekday_summaries
+        .iter()
+        .map(|summary| {
+          json!({
+            "weekday": summary.weekday.to_string(),
+            "days": summary.days,
+            "total": summary.total.num_seconds(),
+            "average": summary.average.num_seconds(),
+          })
+        })
+        .collect::<Vec<Value>>(),
+    ),
+    Format::Raw => {
+      for summary in &weekday_summaries {
+        println!(
+          "{}\t{}\t{}\t{}",
+          summary.weekday,
+          summary.days,
+          summary.total.hhmmss(),
+          summary.average.hhmmss()
+        );
+      }
+    }
+    Format::Table => output_weekday_summaries_table(&weekday_summaries),
+  }
+
+  Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct WeekdaySummary {
+  pub weekday: Weekday,
+  /// Occurrences of the weekday, logged or not
+  pub days: i64,
+  pub total: Duration,
+  pub average: Duration,
+}
+
+/// Logged time per weekday from Monday to Sunday, the averages count every
+/// occurrence of the weekday since the first logged day up to `last_day`
+pub(super) fn weekday_summaries(
+  daily_totals: &HashMap<NaiveDate, Duration>,
+  start: NaiveDate,
+  last_day: NaiveDate,
+) -> Vec<WeekdaySummary> {
+  // 'all' starts long before anything was logged
+  let first_day = daily_totals
+    .keys()
+    .filter(|date| **date >= start)
+    .min()
+    .copied()
+    .unwrap_or(start);
+
+  let days = first_day
+    .iter_days()
+    .take_while(|date| *date <= last_day)
+    .collect::<Vec<NaiveDate>>();
+
+  [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+  ]
+  .into_iter()
+  .map(|weekday| {
+    let dates = days
+      .iter()
+      .filter(|date| date.weekday() == weekday)
+      .collect::<Vec<&NaiveDate>>();
+
+    let total = dates
+      .iter()
+      .filter_map(|date| daily_totals.get(date))
+      .fold(Duration::zero(), |sum, duration| sum + *duration);
+
+    let days = dates.len() as i64;
+
+    WeekdaySummary {
+      weekday,
+      days,
+      total,
+      average: if days == 0 {
+        Duration::zero()
       } else {
-        "".to_string()
+        total / days as i32
+      },
+    }
+  })
+  .collect()
+}
+
+fn output_weekday_summaries_table(weekday_summaries: &[WeekdaySummary]) {
+  let mut table = Table::new();
+  table.style = TableStyle::thin();
+
+  table.add_row(Row::new(
+    ["Weekday", "Days", "Total", "Average"]
+      .into_iter()
+      .map(|title| TableCell::new(title.bold().white())),
+  ));
+
+  // weekdays below the average of the working days stand out
+  let working_days = weekday_summaries
+    .iter()
+    .filter(|summary| !summary.total.is_zero())
+    .collect::<Vec<&WeekdaySummary>>();
+
+  let overall_average = working_days
+    .iter()
+    .fold(Duration::zero(), |sum, summary| sum + summary.average)
+    / working_days.len().max(1) as i32;
+
+  for summary in weekday_summaries {
+    let average =
+      if !summary.total.is_zero() && summary.average < overall_average {
+        summary.average.hhmmss().yellow()
+      } else {
+        summary.average.hhmmss().normal()
+      };
+
+    table.add_row(Row::new(vec![
+      TableCell::new(summary.weekday),
+      TableCell::new(summary.days),
+      TableCell::new(summary.total.hhmmss()),
+      TableCell::new(average),
+    ]));
+  }
+
+  let total = weekday_summaries
+    .iter()
+    .fold(Duration::zero(), |sum, summary| sum + summary.total);
+
+  table.add_row(Row::new(vec![
+    TableCell::new("Total".bold()),
+    TableCell::new(""),
+    TableCell::new(total.hhmmss().bold()),
+    TableCell::new(overall_average.hhmmss().bold()),
+  ]));
+
+  println!("{}", table.render());
+}
+
+fn without_reports_api(option: &str) -> anyhow::Error {
+  anyhow!(
+    "Reports API requires a paid plan, {option} is not available without it"
+  )
+}
+
+/// Time entries of the workspace, the fallback for workspaces without
+/// access to the Reports API
+fn own_time_entries(
+  debug: bool,
+  workspace_id: u64,
+  range: &Range,
+  client: &dyn TogglApi,
+) -> anyhow::Result<Vec<TimeEntry>> {
+  let mut time_entries = client.get_time_entries(debug, range)?;
+
+  time_entries.retain(|time_entry| time_entry.wid == workspace_id);
+
+  Ok(time_entries)
+}
+
+/// Logged time of stopped time entries per project, like the summary of
+/// the Reports API
+pub(super) fn logged_by_project(
+  time_entries: &[TimeEntry],
+) -> HashMap<Option<u64>, Duration> {
+  time_entries
+    .iter()
+    .filter(|time_entry| time_entry.duration >= 0)
+    .map(|time_entry| {
+      (
+        time_entry.pid,
+        Duration::try_seconds(time_entry.duration).unwrap_or_default(),
+      )
+    })
+    .into_grouping_map()
+    .fold(Duration::zero(), |sum, _, duration| sum + duration)
+}
+
+/// Logged time of stopped time entries (only billable ones with
+/// `billable_only`), running ones are not part of reports either
+pub(super) fn logged_time(
+  time_entries: &[TimeEntry],
+  billable_only: bool,
+) -> Duration {
+  time_entries
+    .iter()
+    .filter(|time_entry| time_entry.duration >= 0)
+    .filter(|time_entry| {
+      !billable_only || time_entry.billable.unwrap_or_default()
+    })
+    .fold(Duration::zero(), |sum, time_entry| {
+      sum + Duration::try_seconds(time_entry.duration).unwrap_or_default()
+    })
+}
+
+/// Report details like the Reports API answers them, running time entries
+/// are left out
+pub(super) fn report_details_from_time_entries(
+  username: &str,
+  time_entries: &[TimeEntry],
+) -> Vec<ReportDetails> {
+  time_entries
+    .iter()
+    .filter_map(|time_entry| {
+      let stop = time_entry.stop.filter(|_| time_entry.duration >= 0)?;
+
+      Some(ReportDetails {
+        username: username.to_string(),
+        project_id: time_entry.pid,
+        time_entries: vec![ReportTimeEntry {
+          id: time_entry.id,
+          start: time_entry.start,
+          stop,
+          seconds: time_entry.duration as u64,
+        }],
+      })
+    })
+    .collect()
+}
+
+/// Report days in the timezone `tz` as export or text
+fn output_report<Z: TimeZone>(
+  compliance: &Compliance,
+  date_format: &DateFormat,
+  detailed: &Detailed,
+  report_details: &[ReportDetails],
+  tz: &Z,
+) -> anyhow::Result<()>
+where
+  Z::Offset: Display,
+{
+  let report_days = report_days(compliance, report_details, tz);
+
+  let fields = selected_fields(&detailed.fields);
+
+  match detailed.export {
+    Some(ReportExport::Csv) => match &detailed.output {
+      Some(output) => {
+        write_csv(std::fs::File::create(output)?, &report_days, &fields)?;
+
+        println!("Wrote report to {output:?}");
+      }
+      None => write_csv(std::io::stdout(), &report_days, &fields)?,
+    },
+    // the exports of Toggl returned early
+    _ => output_report_days(
+      date_format,
+      &detailed.range,
+      detailed.display_tz.as_ref(),
+      detailed.explain,
+      &report_days,
+    ),
+  }
+
+  Ok(())
+}
+
+/// Work of one user on one day, the break is the time between the first
+/// start and the last end which is not covered by work
+#[derive(Debug)]
+pub(super) struct ReportDay<Z: TimeZone = Local> {
+  pub user: String,
+  pub date: NaiveDate,
+  pub start: Option<DateTime<Z>>,
+  pub end: Option<DateTime<Z>>,
+  pub work: Duration,
+  pub r#break: Option<Duration>,
+  pub violations: Vec<Violation>,
+}
+
+/// Violated compliance rule, the explanation cites the rule and its threshold
+#[derive(Debug, PartialEq)]
+pub(super) struct Violation {
+  pub message: String,
+  pub explanation: String,
+}
+
+/// Days (in the timezone `tz`) sorted by user and date
+pub(super) fn report_days<Z: TimeZone>(
+  compliance: &Compliance,
+  report_details: &[ReportDetails],
+  tz: &Z,
+) -> Vec<ReportDay<Z>> {
+  let time_entries_by_user_and_date = report_details
+    .iter()
+    .flat_map(|detail| {
+      detail
+        .time_entries
+        .iter()
+        .map(|time_entry| (detail.username.to_owned(), time_entry))
+    })
+    .into_group_map_by(|(user, time_entry)| {
+      (
+        user.to_owned(),
+        time_entry.start.with_timezone(tz).date_naive(),
+      )
+    });
+
+  time_entries_by_user_and_date
+    .into_iter()
+    .sorted_by(|(a, _), (b, _)| a.cmp(b))
+    .map(|((user, date), time_entries)| {
+      let work = time_entries
+        .iter()
+        .flat_map(|(_, time_entry)| {
+          Duration::try_seconds(time_entry.seconds as i64)
+        })
+        .fold(Duration::zero(), |a, b| a + b);
+
+      let start = time_entries
+        .iter()
+        .map(|(_, time_entry)| time_entry.start)
+        .min()
+        .map(|start| start.with_timezone(tz));
+
+      let end = time_entries
+        .iter()
+        .map(|(_, time_entry)| time_entry.stop)
+        .max()
+        .map(|end| end.with_timezone(tz));
+
+      let r#break = match (&start, &end) {
+        (Some(start), Some(end)) => Some(end.clone() - start.clone() - work),
+        _ => None,
       };
 
+      let violations = compliance_violations(
+        compliance,
+        work,
+        start.clone(),
+        end.clone(),
+        r#break,
+      );
+
+      ReportDay {
+        user,
+        date,
+        start,
+        end,
+        work,
+        r#break,
+        violations,
+      }
+    })
+    .collect()
+}
+
+fn output_report_days<Z: TimeZone>(
+  date_format: &DateFormat,
+  range: &Range,
+  display_tz: Option<&Tz>,
+  explain: bool,
+  report_days: &[ReportDay<Z>],
+) where
+  Z::Offset: Display,
+{
+  println!("Range: {range}");
+
+  if let Some(tz) = display_tz {
+    println!("Timezone: {tz}");
+  }
+
+  if report_days.is_empty() {
+    println!();
+    println!("No time entries found.");
+
+    return;
+  }
+
+  for (user, days) in &report_days.iter().chunk_by(|day| &day.user) {
+    let days = days.collect::<Vec<&ReportDay<Z>>>();
+
+    let total = days
+      .iter()
+      .fold(Duration::zero(), |total, day| total + day.work);
+
+    println!();
+    println!(
+      "{} - {} hours ({})",
+      user,
+      total.num_hours(),
+      formatted_duration(total)
+    );
+    println!();
+
+    for day in days {
+      let warnings = day
+        .violations
+        .iter()
+        .map(|violation| violation.message.red().to_string())
+        .collect::<Vec<String>>();
+
+      let formatted_break = day
+        .r#break
+        .map(|r#break| format!(", Break: {}", formatted_duration(r#break)))
+        .unwrap_or_default();
+
       let formatted_warnings = if !warnings.is_empty() {
         format!(" | {}", warnings.join(", ").bold())
       } else {
@@ -182,19 +1596,181 @@ pub fn detailed(
 
       println!(
         "{} - {} - {} | Work: {}{}{}",
-        date.format("%Y-%m-%d"),
-        start
-          .map(|s| s.format("%H:%M").to_string())
-          .unwrap_or_default(),
-        end
-          .map(|s| s.format("%H:%M").to_string())
-          .unwrap_or_default(),
-        hours_formatted,
+        date_format.date(&day.date),
+        formatted_time(&day.start),
+        formatted_time(&day.end),
+        formatted_duration(day.work),
         formatted_break,
         formatted_warnings
       );
+
+      if explain {
+        for violation in &day.violations {
+          println!("  {}: {}", violation.message, violation.explanation);
+        }
+      }
     }
   }
+}
+
+/// One row per user and day, durations as 'H:MM' which spreadsheets read as time
+pub(super) fn write_csv<W: Write, Z: TimeZone>(
+  writer: W,
+  report_days: &[ReportDay<Z>],
+  fields: &[ReportField],
+) -> anyhow::Result<()>
+where
+  Z::Offset: Display,
+{
+  let mut writer = csv::Writer::from_writer(writer);
+
+  writer.write_record(fields.iter().map(|field| match field {
+    ReportField::User => "user",
+    ReportField::Date => "date",
+    ReportField::Start => "start",
+    ReportField::End => "end",
+    ReportField::Work => "work",
+    ReportField::Break => "break",
+    ReportField::Violations => "violations",
+  }))?;
+
+  for day in report_days {
+    writer.write_record(fields.iter().map(|field| {
+      match field {
+        ReportField::User => day.user.to_owned(),
+        ReportField::Date => day.date.format("%Y-%m-%d").to_string(),
+        ReportField::Start => formatted_time(&day.start),
+        ReportField::End => formatted_time(&day.end),
+        ReportField::Work => hours_minutes(day.work),
+        ReportField::Break => {
+          day.r#break.map(hours_minutes).unwrap_or_default()
+        }
+        ReportField::Violations => day
+          .violations
+          .iter()
+          .map(|violation| violation.message.as_str())
+          .join("; "),
+      }
+    }))?;
+  }
+
+  writer.flush()?;
 
   Ok(())
 }
+
+fn formatted_time<Z: TimeZone>(time: &Option<DateTime<Z>>) -> String
+where
+  Z::Offset: Display,
+{
+  time
+    .as_ref()
+    .map(|time| time.format("%H:%M").to_string())
+    .unwrap_or_default()
+}
+
+fn hours_minutes(duration: Duration) -> String {
+  format!(
+    "{}:{:02}",
+    duration.num_hours(),
+    duration.num_minutes() % 60
+  )
+}
+
+pub(super) fn compliance_violations<Z: TimeZone>(
+  compliance: &Compliance,
+  hours: Duration,
+  start: Option<DateTime<Z>>,
+  end: Option<DateTime<Z>>,
+  r#break: Option<Duration>,
+) -> Vec<Violation> {
+  let mut warnings = vec![];
+
+  if compliance.max_work.enabled
+    && hours.num_hours() > compliance.max_work.hours
+  {
+    warnings.push(Violation {
+      message: format!("More than {} hours", compliance.max_work.hours),
+      explanation: format!(
+        "worked {}, [compliance.max_work] allows at most hours = {}",
+        formatted_duration(hours),
+        compliance.max_work.hours
+      ),
+    });
+  }
+
+  if let Some(start) = start {
+    if compliance.earliest_start.enabled
+      && start.time().hour() < compliance.earliest_start.hour
+    {
+      warnings.push(Violation {
+        message: format!(
+          "Start time is before {:02}:00",
+          compliance.earliest_start.hour
+        ),
+        explanation: format!(
+          "started at {}, [compliance.earliest_start] requires hour >= {}",
+          start.time().format("%H:%M"),
+          compliance.earliest_start.hour
+        ),
+      });
+    }
+  }
+
+  if let Some(end) = end {
+    if compliance.latest_end.enabled
+      && end.time().hour() > compliance.latest_end.hour
+    {
+      warnings.push(Violation {
+        message: format!(
+          "End time is after {:02}:00",
+          compliance.latest_end.hour
+        ),
+        explanation: format!(
+          "ended at {}, [compliance.latest_end] requires hour <= {}",
+          end.time().format("%H:%M"),
+          compliance.latest_end.hour
+        ),
+      });
+    }
+  }
+
+  if let Some(r#break) = r#break {
+    // the rule with the highest threshold below the worked hours applies,
+    // thresholds out of range of a duration never apply
+    let break_rule = compliance
+      .breaks
+      .rules
+      .iter()
+      .filter(|rule| {
+        hours
+          > Duration::try_hours(rule.after_hours)
+            .unwrap_or(Duration::max_value())
+      })
+      .max_by_key(|rule| rule.after_hours);
+
+    if let Some(break_rule) = break_rule {
+      if compliance.breaks.enabled
+        && r#break
+          < Duration::try_minutes(break_rule.minutes)
+            .unwrap_or(Duration::max_value())
+      {
+        warnings.push(Violation {
+          message: format!(
+            "Worked for {} => break should be at least {} minutes!",
+            formatted_duration(hours),
+            break_rule.minutes
+          ),
+          explanation: format!(
+            "break of {}, [compliance.breaks] rule after_hours = {} requires minutes = {}",
+            formatted_duration(r#break),
+            break_rule.after_hours,
+            break_rule.minutes
+          ),
+        });
+      }
+    }
+  }
+
+  warnings
+}
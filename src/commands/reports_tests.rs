@@ -0,0 +1,657 @@
+use crate::{
+  cli::{ReportField, SummaryField},
+  commands::reports::amount_currencies,
+  commands::reports::client_durations,
+  commands::reports::compliance_violations,
+  commands::reports::converted_project_summaries,
+  commands::reports::logged_by_project,
+  commands::reports::logged_time,
+  commands::reports::project_durations,
+  commands::reports::project_summaries,
+  commands::reports::report_days,
+  commands::reports::report_details_from_time_entries,
+  commands::reports::selected_fields,
+  commands::reports::tag_durations,
+  commands::reports::total_amounts,
+  commands::reports::user_summaries,
+  commands::reports::user_summary_json,
+  commands::reports::weekday_summaries,
+  commands::reports::write_csv,
+  commands::reports::ProjectSummary,
+  commands::reports::Violation,
+  config::{BreakRule, Compliance},
+  model::{Client, Project, ReportDetails, TimeEntry, WorkspaceUser},
+};
+use chrono::{DateTime, Duration, Local, NaiveDate, Weekday};
+use chrono_tz::Tz;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+fn messages(violations: Vec<Violation>) -> Vec<String> {
+  violations
+    .into_iter()
+    .map(|violation| violation.message)
+    .collect()
+}
+
+#[test]
+fn test_compliance_warnings_with_defaults() -> anyhow::Result<()> {
+  let compliance = Compliance::default();
+
+  let no_warnings = messages(compliance_violations(
+    &compliance,
+    Duration::try_hours(8).unwrap(),
+    Some(DateTime::<Local>::from_str("2021-11-22T08:00:00+01:00")?),
+    Some(DateTime::<Local>::from_str("2021-11-22T17:00:00+01:00")?),
+    Some(Duration::try_hours(1).unwrap()),
+  ));
+
+  assert_eq!(no_warnings, Vec::<String>::new());
+
+  let warnings = messages(compliance_violations(
+    &compliance,
+    Duration::try_hours(12).unwrap(),
+    Some(DateTime::<Local>::from_str("2021-11-22T05:00:00+01:00")?),
+    Some(DateTime::<Local>::from_str("2021-11-22T23:30:00+01:00")?),
+    Some(Duration::try_minutes(40).unwrap()),
+  ));
+
+  assert_eq!(
+    warnings,
+    vec![
+      "More than 10 hours".to_string(),
+      "Start time is before 06:00".to_string(),
+      "End time is after 22:00".to_string(),
+      "Worked for 12h => break should be at least 45 minutes!".to_string(),
+    ]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_compliance_warnings_with_custom_rules() -> anyhow::Result<()> {
+  let mut compliance = Compliance::default();
+  compliance.max_work.enabled = false;
+  compliance.earliest_start.hour = 7;
+  compliance.latest_end.enabled = false;
+  compliance.breaks.rules = vec![BreakRule {
+    after_hours: 5,
+    minutes: 60,
+  }];
+
+  let warnings = messages(compliance_violations(
+    &compliance,
+    Duration::try_hours(12).unwrap(),
+    Some(DateTime::<Local>::from_str("2021-11-22T06:30:00+01:00")?),
+    Some(DateTime::<Local>::from_str("2021-11-22T23:30:00+01:00")?),
+    Some(Duration::try_minutes(40).unwrap()),
+  ));
+
+  assert_eq!(
+    warnings,
+    vec![
+      "Start time is before 07:00".to_string(),
+      "Worked for 12h => break should be at least 60 minutes!".to_string(),
+    ]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_compliance_warnings_with_out_of_range_rules() -> anyhow::Result<()> {
+  let mut compliance = Compliance::default();
+  compliance.breaks.rules = vec![
+    BreakRule {
+      after_hours: i64::MAX,
+      minutes: 30,
+    },
+    BreakRule {
+      after_hours: 6,
+      minutes: i64::MAX,
+    },
+  ];
+
+  let warnings = messages(compliance_violations(
+    &compliance,
+    Duration::try_hours(8).unwrap(),
+    Some(DateTime::<Local>::from_str("2021-11-22T08:00:00+01:00")?),
+    Some(DateTime::<Local>::from_str("2021-11-22T17:00:00+01:00")?),
+    Some(Duration::try_minutes(60).unwrap()),
+  ));
+
+  assert_eq!(
+    warnings,
+    vec![format!(
+      "Worked for 8h => break should be at least {} minutes!",
+      i64::MAX
+    )]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_compliance_violations_explained() -> anyhow::Result<()> {
+  let violations = compliance_violations(
+    &Compliance::default(),
+    Duration::try_hours(11).unwrap(),
+    Some(DateTime::<Local>::from_str("2021-11-22T07:00:00+01:00")?),
+    Some(DateTime::<Local>::from_str("2021-11-22T18:20:00+01:00")?),
+    Some(Duration::try_minutes(20).unwrap()),
+  );
+
+  assert_eq!(
+    violations
+      .iter()
+      .map(|violation| violation.explanation.as_str())
+      .collect::<Vec<&str>>(),
+    vec![
+      "worked 11h, [compliance.max_work] allows at most hours = 10",
+      "break of 20m, [compliance.breaks] rule after_hours = 9 requires minutes = 45",
+    ]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_report_days_as_csv() -> anyhow::Result<()> {
+  let report_details: Vec<ReportDetails> = serde_json::from_value(json!([
+    {
+      "username": "zoe",
+      "time_entries": [
+        {
+          "id": 3,
+          "start": "2023-03-01T11:00:00+00:00",
+          "stop": "2023-03-01T12:00:00+00:00",
+          "seconds": 3600
+        }
+      ]
+    },
+    {
+      "username": "fkbr",
+      "time_entries": [
+        {
+          "id": 1,
+          "start": "2023-03-01T08:00:00+00:00",
+          "stop": "2023-03-01T12:00:00+00:00",
+          "seconds": 14400
+        },
+        {
+          "id": 2,
+          "start": "2023-03-01T12:15:00+00:00",
+          "stop": "2023-03-01T15:15:00+00:00",
+          "seconds": 10800
+        }
+      ]
+    }
+  ]))?;
+
+  let days = report_days(&Compliance::default(), &report_details, &Local);
+
+  assert_eq!(
+    days
+      .iter()
+      .map(|day| day.user.as_str())
+      .collect::<Vec<&str>>(),
+    vec!["fkbr", "zoe"]
+  );
+  assert_eq!(days[0].work, Duration::try_hours(7).unwrap());
+  assert_eq!(days[0].r#break, Duration::try_minutes(15));
+  assert_eq!(days[0].violations.len(), 1);
+
+  let mut csv = vec![];
+  write_csv(&mut csv, &days, &selected_fields::<ReportField>(&[]))?;
+
+  let csv = String::from_utf8(csv)?;
+  let lines = csv.lines().collect::<Vec<&str>>();
+
+  assert_eq!(lines[0], "user,date,start,end,work,break,violations");
+  assert!(lines[1].starts_with("fkbr,2023-03-01,"));
+  assert!(lines[1].contains(
+    ",7:00,0:15,Worked for 7h => break should be at least 30 minutes!"
+  ));
+  assert!(lines[2].starts_with("zoe,2023-03-01,"));
+  assert!(lines[2].ends_with(",1:00,0:00,"));
+
+  let mut csv = vec![];
+  write_csv(&mut csv, &days, &[ReportField::Date, ReportField::Work])?;
+
+  assert_eq!(
+    String::from_utf8(csv)?.lines().collect::<Vec<&str>>(),
+    vec!["date,work", "2023-03-01,7:00", "2023-03-01,1:00"]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_user_summaries() {
+  let hours = |hours| Duration::try_hours(hours).unwrap();
+
+  let total = HashMap::from([(1, hours(10)), (2, hours(30)), (3, hours(5))]);
+  let billable = HashMap::from([(2, hours(20))]);
+
+  let users = vec![
+    WorkspaceUser {
+      id: 1,
+      fullname: "Ralph Bower".to_string(),
+    },
+    WorkspaceUser {
+      id: 2,
+      fullname: "Zoe".to_string(),
+    },
+  ];
+
+  let summaries = user_summaries(&total, &billable, &users);
+
+  assert_eq!(
+    summaries
+      .iter()
+      .map(|summary| (summary.name.as_str(), summary.billable))
+      .collect::<Vec<(&str, Duration)>>(),
+    vec![
+      ("Zoe", hours(20)),
+      ("Ralph Bower", Duration::zero()),
+      ("3", Duration::zero()),
+    ]
+  );
+}
+
+#[test]
+fn test_user_summary_json() {
+  let summaries = user_summaries(
+    &HashMap::from([(1, Duration::try_hours(10).unwrap())]),
+    &HashMap::new(),
+    &[],
+  );
+
+  assert_eq!(
+    user_summary_json(&summaries[0], &selected_fields(&[])),
+    json!({
+      "user_id": 1,
+      "name": "1",
+      "total": 36000,
+      "billable": 0,
+      "projects": []
+    })
+  );
+  assert_eq!(
+    user_summary_json(&summaries[0], &[SummaryField::Total]),
+    json!({ "total": 36000 })
+  );
+}
+
+#[test]
+fn test_weekday_summaries() {
+  let date = |day| NaiveDate::from_ymd_opt(2021, 11, day).unwrap();
+  let hours = |hours| Duration::try_hours(hours).unwrap();
+
+  // Monday 2021-11-15 to Sunday 2021-11-28, nothing logged on the 2nd Monday
+  let daily_totals = HashMap::from([
+    (date(15), hours(8)),
+    (date(16), hours(8)),
+    (date(23), hours(6)),
+    (date(27), hours(2)),
+  ]);
+
+  let summaries = weekday_summaries(&daily_totals, date(1), date(28));
+
+  assert_eq!(summaries.len(), 7);
+
+  assert_eq!(summaries[0].weekday, Weekday::Mon);
+  assert_eq!(summaries[0].days, 2);
+  assert_eq!(summaries[0].total, hours(8));
+  assert_eq!(summaries[0].average, hours(4));
+
+  assert_eq!(summaries[1].weekday, Weekday::Tue);
+  assert_eq!(summaries[1].total, hours(14));
+  assert_eq!(summaries[1].average, hours(7));
+
+  assert_eq!(summaries[5].weekday, Weekday::Sat);
+  assert_eq!(summaries[5].average, hours(1));
+
+  assert_eq!(summaries[6].weekday, Weekday::Sun);
+  assert_eq!(summaries[6].days, 2);
+  assert_eq!(summaries[6].total, Duration::zero());
+}
+
+#[test]
+fn test_report_details_from_time_entries() -> anyhow::Result<()> {
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "pid": 1,
+      "billable": true,
+      "start": "2021-11-22T08:00:00Z",
+      "stop": "2021-11-22T10:00:00Z",
+      "duration": 7200
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-22T10:30:00Z",
+      "stop": "2021-11-22T11:00:00Z",
+      "duration": 1800
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "start": "2021-11-22T11:00:00Z",
+      "duration": -1637578800
+    }
+  ]))?;
+
+  let report_details = report_details_from_time_entries("fkbr", &time_entries);
+
+  assert_eq!(report_details.len(), 2);
+  assert_eq!(report_details[0].username, "fkbr");
+  assert_eq!(report_details[0].project_id, Some(1));
+  assert_eq!(report_details[0].time_entries[0].seconds, 7200);
+  assert_eq!(report_details[1].project_id, None);
+  assert_eq!(report_details[1].time_entries[0].id, 2);
+
+  assert_eq!(
+    logged_time(&time_entries, false),
+    Duration::try_minutes(150).unwrap()
+  );
+  assert_eq!(
+    logged_time(&time_entries, true),
+    Duration::try_hours(2).unwrap()
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_project_durations() -> anyhow::Result<()> {
+  let projects: Vec<Project> = serde_json::from_value(json!([
+    { "id": 1, "name": "fkbr.org", "wid": 1234567, "status": "active" },
+    { "id": 2, "name": "sxoe.org", "wid": 1234567, "status": "archived" }
+  ]))?;
+
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T08:00:00Z",
+      "duration": 3600
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "pid": 2,
+      "start": "2021-11-22T09:00:00Z",
+      "duration": 7200
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T11:00:00Z",
+      "duration": 1800
+    },
+    {
+      "id": 4,
+      "wid": 1234567,
+      "start": "2021-11-22T12:00:00Z",
+      "duration": 900
+    },
+    {
+      "id": 5,
+      "wid": 1234567,
+      "pid": 3,
+      "start": "2021-11-22T13:00:00Z",
+      "duration": -1637586000
+    }
+  ]))?;
+
+  assert_eq!(
+    project_durations(&logged_by_project(&time_entries), &projects),
+    vec![
+      ("sxoe.org".to_string(), Duration::try_hours(2).unwrap()),
+      ("fkbr.org".to_string(), Duration::try_minutes(90).unwrap()),
+      (
+        "(no project)".to_string(),
+        Duration::try_minutes(15).unwrap()
+      ),
+    ]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_project_summaries() -> anyhow::Result<()> {
+  let projects: Vec<Project> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "name": "fkbr.org",
+      "wid": 1234567,
+      "status": "active",
+      "rate": 100.0,
+      "currency": "EUR"
+    },
+    { "id": 2, "name": "sxoe.org", "wid": 1234567, "status": "active" }
+  ]))?;
+
+  let totals = HashMap::from([
+    (Some(1), Duration::try_hours(3).unwrap()),
+    (Some(2), Duration::try_hours(4).unwrap()),
+    (None, Duration::try_minutes(30).unwrap()),
+  ]);
+
+  let billable = HashMap::from([(Some(1), Duration::try_minutes(90).unwrap())]);
+
+  let project_summaries = project_summaries(&totals, &billable, &projects);
+
+  assert_eq!(
+    project_summaries,
+    vec![
+      ProjectSummary {
+        name: "sxoe.org".to_string(),
+        total: Duration::try_hours(4).unwrap(),
+        billable: Duration::zero(),
+        amount: None,
+        currency: None,
+      },
+      ProjectSummary {
+        name: "fkbr.org".to_string(),
+        total: Duration::try_hours(3).unwrap(),
+        billable: Duration::try_minutes(90).unwrap(),
+        amount: Some(150.0),
+        currency: Some("EUR".to_string()),
+      },
+      ProjectSummary {
+        name: "(no project)".to_string(),
+        total: Duration::try_minutes(30).unwrap(),
+        billable: Duration::zero(),
+        amount: None,
+        currency: None,
+      },
+    ]
+  );
+
+  assert_eq!(total_amounts(&project_summaries), "150.00 EUR");
+  assert_eq!(total_amounts(&project_summaries[..1]), "-");
+
+  Ok(())
+}
+
+#[test]
+fn test_mixed_currency_amounts() -> anyhow::Result<()> {
+  let project_summary =
+    |name: &str, amount: Option<f64>, currency: &str| ProjectSummary {
+      name: name.to_string(),
+      total: Duration::try_hours(2).unwrap(),
+      billable: Duration::try_hours(2).unwrap(),
+      amount,
+      currency: Some(currency.to_string()),
+    };
+
+  let project_summaries = vec![
+    project_summary("fkbr.org", Some(200.0), "EUR"),
+    project_summary("sxoe.org", Some(100.0), "USD"),
+    project_summary("kuci.org", None, "CHF"),
+  ];
+
+  assert_eq!(
+    amount_currencies(&project_summaries),
+    vec![Some("EUR".to_string()), Some("USD".to_string())]
+  );
+  assert_eq!(total_amounts(&project_summaries), "200.00 EUR, 100.00 USD");
+
+  let exchange_rates =
+    HashMap::from([("EUR".to_string(), 1.0), ("USD".to_string(), 0.9)]);
+
+  let converted =
+    converted_project_summaries(project_summaries, "EUR", &exchange_rates)?;
+
+  assert_eq!(amount_currencies(&converted), vec![Some("EUR".to_string())]);
+  assert_eq!(total_amounts(&converted), "290.00 EUR");
+  assert_eq!(converted[2].amount, None);
+
+  assert_eq!(
+    converted_project_summaries(converted, "GBP", &exchange_rates)
+      .unwrap_err()
+      .to_string(),
+    "No exchange rate of GBP in 'exchange_rates' of settings.toml"
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_client_durations() -> anyhow::Result<()> {
+  let projects: Vec<Project> = serde_json::from_value(json!([
+    { "id": 1, "name": "fkbr.org", "wid": 1234567, "cid": 1, "status": "active" },
+    { "id": 2, "name": "sxoe.org", "wid": 1234567, "cid": 1, "status": "active" },
+    { "id": 3, "name": "Internal", "wid": 1234567, "status": "active" }
+  ]))?;
+
+  let clients: Vec<Client> = serde_json::from_value(json!([
+    { "id": 1, "name": "fkbr", "archived": false }
+  ]))?;
+
+  let hours = |hours| Duration::try_hours(hours).unwrap();
+
+  let durations = HashMap::from([
+    (Some(1), hours(1)),
+    (Some(2), hours(2)),
+    (Some(3), hours(4)),
+    (None, hours(1)),
+  ]);
+
+  assert_eq!(
+    client_durations(&durations, &projects, &clients),
+    vec![
+      ("(no client)".to_string(), hours(5)),
+      ("fkbr".to_string(), hours(3)),
+    ]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_tag_durations() -> anyhow::Result<()> {
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "start": "2021-11-22T08:00:00Z",
+      "duration": 3600,
+      "tags": ["meeting", "remote"]
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-22T09:00:00Z",
+      "duration": 1800,
+      "tags": ["remote"]
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "start": "2021-11-22T10:00:00Z",
+      "duration": 900,
+      "tags": []
+    },
+    {
+      "id": 4,
+      "wid": 1234567,
+      "start": "2021-11-22T11:00:00Z",
+      "duration": -1637578800,
+      "tags": ["meeting"]
+    }
+  ]))?;
+
+  assert_eq!(
+    tag_durations(&time_entries),
+    vec![
+      ("remote".to_string(), Duration::try_minutes(90).unwrap()),
+      ("meeting".to_string(), Duration::try_hours(1).unwrap()),
+      ("(no tag)".to_string(), Duration::try_minutes(15).unwrap()),
+    ]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_report_days_in_display_timezone() -> anyhow::Result<()> {
+  let report_details: Vec<ReportDetails> = serde_json::from_value(json!([
+    {
+      "username": "zoe",
+      "time_entries": [
+        {
+          "id": 1,
+          "start": "2023-03-01T22:00:00+00:00",
+          "stop": "2023-03-02T02:00:00+00:00",
+          "seconds": 14400
+        }
+      ]
+    }
+  ]))?;
+
+  let tz: Tz = "America/New_York".parse()?;
+  let days = report_days(&Compliance::default(), &report_details, &tz);
+
+  assert_eq!(days.len(), 1);
+  assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2023, 3, 1).unwrap());
+  assert_eq!(
+    days[0].start.map(|start| start.format("%H:%M").to_string()),
+    Some("17:00".to_string())
+  );
+  assert_eq!(days[0].violations, vec![]);
+
+  let mut csv = vec![];
+  write_csv(&mut csv, &days, &[ReportField::Start, ReportField::End])?;
+
+  assert_eq!(
+    String::from_utf8(csv)?.lines().collect::<Vec<&str>>(),
+    vec!["start,end", "17:00,21:00"]
+  );
+
+  let tz: Tz = "Asia/Kolkata".parse()?;
+  let days = report_days(&Compliance::default(), &report_details, &tz);
+
+  assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2023, 3, 2).unwrap());
+  assert_eq!(
+    days[0].violations,
+    vec![Violation {
+      message: "Start time is before 06:00".to_string(),
+      explanation:
+        "started at 03:30, [compliance.earliest_start] requires hour >= 6"
+          .to_string(),
+    }]
+  );
+
+  Ok(())
+}
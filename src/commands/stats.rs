@@ -0,0 +1,283 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, Duration, Local};
+use colored::Colorize;
+use hhmmss::Hhmmss;
+use serde_json::{json, Value};
+use term_table::{
+  row::Row, table_cell::Alignment, table_cell::TableCell, Table, TableStyle,
+};
+
+use crate::{
+  cli::{output_values_json, Format, Output, Stats, StatsGrouping},
+  client::TogglApi,
+  model::{Client, Project, TimeEntry},
+};
+
+const NO_PROJECT: &str = "(no project)";
+const NO_CLIENT: &str = "(no client)";
+
+pub fn stats(
+  debug: bool,
+  output: &Output,
+  stats: &Stats,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let time_entries = client.get_time_entries(debug, &stats.range)?;
+
+  let workspace_ids = time_entries
+    .iter()
+    .map(|time_entry| time_entry.wid)
+    .collect::<BTreeSet<u64>>();
+
+  let mut projects = vec![];
+  let mut clients = vec![];
+
+  for workspace_id in workspace_ids {
+    projects.extend(client.get_workspace_projects(
+      debug,
+      true,
+      workspace_id,
+    )?);
+    clients.extend(
+      client
+        .get_workspace_clients(debug, true, workspace_id)?
+        .unwrap_or_default(),
+    );
+  }
+
+  let statistics =
+    statistics(&time_entries, &projects, &clients, &stats.by, Local::now());
+
+  if statistics.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &statistics
+        .iter()
+        .map(|statistic| {
+          let mut value = json!({
+            "client": statistic.client,
+            "entries": statistic.entries,
+            "total": statistic.total.num_seconds(),
+            "billable": statistic.billable.num_seconds(),
+            "billable_share": statistic.billable_share(),
+          });
+
+          if let Some(project) = &statistic.project {
+            value["project"] = json!(project);
+          }
+
+          value
+        })
+        .collect::<Vec<Value>>(),
+    ),
+    Format::Raw => {
+      for statistic in &statistics {
+        let mut columns = vec![];
+
+        columns.extend(statistic.project.to_owned());
+        columns.extend([
+          statistic.client.to_owned(),
+          statistic.entries.to_string(),
+          statistic.total.hhmmss(),
+          statistic.billable.hhmmss(),
+          format_share(statistic.billable_share()),
+        ]);
+
+        println!("{}", columns.join("\t"));
+      }
+    }
+    Format::Table => output_statistics_table(&stats.by, &statistics),
+  }
+
+  Ok(())
+}
+
+#[derive(Debug)]
+pub(super) struct Statistic {
+  /// None when grouped by client
+  pub project: Option<String>,
+  pub client: String,
+  pub entries: usize,
+  pub total: Duration,
+  pub billable: Duration,
+}
+
+impl Statistic {
+  /// Percentage of the billable time, None without any time
+  pub fn billable_share(&self) -> Option<f64> {
+    if self.total.is_zero() {
+      None
+    } else {
+      Some(
+        self.billable.num_seconds() as f64 * 100.0
+          / self.total.num_seconds() as f64,
+      )
+    }
+  }
+}
+
+/// Logged time, billable time and number of time entries per project or
+/// client, most time first, running time entries count until `now`
+pub(super) fn statistics(
+  time_entries: &[TimeEntry],
+  projects: &[Project],
+  clients: &[Client],
+  by: &StatsGrouping,
+  now: DateTime<Local>,
+) -> Vec<Statistic> {
+  let project_lookup = projects
+    .iter()
+    .map(|project| (project.id, project))
+    .collect::<HashMap<u64, &Project>>();
+
+  let client_name = |project: Option<&&Project>| {
+    project
+      .and_then(|project| project.cid)
+      .map(|cid| {
+        clients
+          .iter()
+          .find(|client| client.id == cid)
+          .map(|client| client.name.to_owned())
+          .unwrap_or_else(|| cid.to_string())
+      })
+      .unwrap_or_else(|| NO_CLIENT.to_string())
+  };
+
+  let mut statistics = HashMap::<(Option<String>, String), Statistic>::new();
+
+  for time_entry in time_entries {
+    let project = time_entry.pid.map(|pid| project_lookup.get(&pid));
+
+    let project_name = time_entry
+      .pid
+      .map(|pid| {
+        project
+          .flatten()
+          .map(|project| project.name.to_owned())
+          .unwrap_or_else(|| pid.to_string())
+      })
+      .unwrap_or_else(|| NO_PROJECT.to_string());
+
+    let client = client_name(project.flatten());
+
+    let project = match by {
+      StatsGrouping::Project => Some(project_name),
+      StatsGrouping::Client => None,
+    };
+
+    let duration = if time_entry.duration < 0 {
+      now.to_utc() - time_entry.start
+    } else {
+      Duration::try_seconds(time_entry.duration).unwrap_or_default()
+    };
+
+    let statistic = statistics
+      .entry((project.to_owned(), client.to_owned()))
+      .or_insert_with(|| Statistic {
+        project,
+        client,
+        entries: 0,
+        total: Duration::zero(),
+        billable: Duration::zero(),
+      });
+
+    statistic.entries += 1;
+    statistic.total += duration;
+
+    if time_entry.billable.unwrap_or_default() {
+      statistic.billable += duration;
+    }
+  }
+
+  let mut statistics = statistics.into_values().collect::<Vec<_>>();
+
+  statistics.sort_by(|a, b| {
+    b.total
+      .cmp(&a.total)
+      .then(a.project.cmp(&b.project))
+      .then(a.client.cmp(&b.client))
+  });
+
+  statistics
+}
+
+fn format_share(share: Option<f64>) -> String {
+  share
+    .map(|share| format!("{share:.0}%"))
+    .unwrap_or_else(|| "-".to_string())
+}
+
+fn output_statistics_table(by: &StatsGrouping, statistics: &[Statistic]) {
+  let mut table = Table::new();
+  table.style = TableStyle::thin();
+
+  let mut header = vec![];
+
+  if *by == StatsGrouping::Project {
+    header.push("Project");
+  }
+
+  header.extend(["Client", "Entries", "Total", "Billable", "Billable %"]);
+
+  table.add_row(Row::new(
+    header
+      .into_iter()
+      .map(|title| TableCell::new(title.bold().white())),
+  ));
+
+  let right =
+    |text: String| TableCell::builder(text).alignment(Alignment::Right).build();
+
+  for statistic in statistics {
+    let mut cells = vec![];
+
+    if let Some(project) = &statistic.project {
+      cells.push(TableCell::new(project));
+    }
+
+    cells.extend([
+      TableCell::new(&statistic.client),
+      right(statistic.entries.to_string()),
+      right(statistic.total.hhmmss()),
+      right(statistic.billable.hhmmss()),
+      right(format_share(statistic.billable_share())),
+    ]);
+
+    table.add_row(Row::new(cells));
+  }
+
+  let total = Statistic {
+    project: None,
+    client: String::new(),
+    entries: statistics.iter().map(|statistic| statistic.entries).sum(),
+    total: statistics
+      .iter()
+      .fold(Duration::zero(), |sum, statistic| sum + statistic.total),
+    billable: statistics
+      .iter()
+      .fold(Duration::zero(), |sum, statistic| sum + statistic.billable),
+  };
+
+  let mut cells = vec![TableCell::new("Total".bold())];
+
+  if *by == StatsGrouping::Project {
+    cells.push(TableCell::new(""));
+  }
+
+  cells.extend([
+    right(total.entries.to_string().bold().to_string()),
+    right(total.total.hhmmss().bold().to_string()),
+    right(total.billable.hhmmss().bold().to_string()),
+    right(format_share(total.billable_share()).bold().to_string()),
+  ]);
+
+  table.add_row(Row::new(cells));
+
+  println!("{}", table.render());
+}
@@ -0,0 +1,155 @@
+use crate::{
+  cli::StatsGrouping,
+  commands::stats::statistics,
+  model::{Client, Project, TimeEntry},
+};
+use chrono::{DateTime, Duration, Local};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::str::FromStr;
+
+fn fixtures() -> anyhow::Result<(Vec<TimeEntry>, Vec<Project>, Vec<Client>)> {
+  let projects: Vec<Project> = serde_json::from_value(json!([
+    { "id": 1, "name": "fkbr.org", "wid": 1234567, "cid": 1, "status": "active" },
+    { "id": 2, "name": "sxoe.org", "wid": 1234567, "cid": 1, "status": "active" },
+    { "id": 3, "name": "Internal", "wid": 1234567, "status": "archived" }
+  ]))?;
+
+  let clients: Vec<Client> = serde_json::from_value(json!([
+    { "id": 1, "name": "fkbr", "archived": false }
+  ]))?;
+
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "pid": 1,
+      "billable": true,
+      "start": "2021-11-22T08:00:00+01:00",
+      "duration": 7200
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T10:00:00+01:00",
+      "duration": 3600
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "pid": 2,
+      "billable": true,
+      "start": "2021-11-22T11:00:00+01:00",
+      "duration": 1800
+    },
+    {
+      "id": 4,
+      "wid": 1234567,
+      "pid": 3,
+      "start": "2021-11-22T12:00:00+01:00",
+      "duration": 900
+    },
+    {
+      "id": 5,
+      "wid": 1234567,
+      "start": "2021-11-23T08:00:00+01:00",
+      "duration": -1637650800
+    }
+  ]))?;
+
+  Ok((time_entries, projects, clients))
+}
+
+#[test]
+fn test_statistics_by_project() -> anyhow::Result<()> {
+  let (time_entries, projects, clients) = fixtures()?;
+  let now = DateTime::<Local>::from_str("2021-11-23T09:00:00+01:00")?;
+
+  let statistics = statistics(
+    &time_entries,
+    &projects,
+    &clients,
+    &StatsGrouping::Project,
+    now,
+  );
+
+  assert_eq!(
+    statistics
+      .iter()
+      .map(|statistic| (
+        statistic.project.as_deref().unwrap(),
+        statistic.client.as_str(),
+        statistic.entries,
+        statistic.total,
+        statistic.billable
+      ))
+      .collect::<Vec<_>>(),
+    vec![
+      (
+        "fkbr.org",
+        "fkbr",
+        2,
+        Duration::try_hours(3).unwrap(),
+        Duration::try_hours(2).unwrap()
+      ),
+      (
+        "(no project)",
+        "(no client)",
+        1,
+        Duration::try_hours(1).unwrap(),
+        Duration::zero()
+      ),
+      (
+        "sxoe.org",
+        "fkbr",
+        1,
+        Duration::try_minutes(30).unwrap(),
+        Duration::try_minutes(30).unwrap()
+      ),
+      (
+        "Internal",
+        "(no client)",
+        1,
+        Duration::try_minutes(15).unwrap(),
+        Duration::zero()
+      ),
+    ]
+  );
+
+  assert_eq!(statistics[0].billable_share().map(f64::round), Some(67.0));
+
+  Ok(())
+}
+
+#[test]
+fn test_statistics_by_client() -> anyhow::Result<()> {
+  let (time_entries, projects, clients) = fixtures()?;
+  let now = DateTime::<Local>::from_str("2021-11-23T09:00:00+01:00")?;
+
+  let statistics = statistics(
+    &time_entries,
+    &projects,
+    &clients,
+    &StatsGrouping::Client,
+    now,
+  );
+
+  assert_eq!(
+    statistics
+      .iter()
+      .map(|statistic| (
+        statistic.project.is_none(),
+        statistic.client.as_str(),
+        statistic.entries,
+        statistic.total
+      ))
+      .collect::<Vec<_>>(),
+    vec![
+      (true, "fkbr", 3, Duration::try_minutes(210).unwrap()),
+      (true, "(no client)", 2, Duration::try_minutes(75).unwrap()),
+    ]
+  );
+
+  Ok(())
+}
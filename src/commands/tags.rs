@@ -0,0 +1,240 @@
+use anyhow::anyhow;
+use colored::Colorize;
+use itertools::Itertools;
+use term_table::{row::Row, table_cell::TableCell, Table, TableStyle};
+
+use crate::{
+  cli::{output_values_json, CreateTag, DeleteTag, Format, Output, RenameTag},
+  client::TogglApi,
+  commands::workspaces::selected_workspace_id,
+  model::{Range, Tag, TimeEntry},
+  resolver::Resolver,
+};
+
+pub fn list(
+  debug: bool,
+  output: &Output,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let workspace_id = selected_workspace_id(debug, client)?;
+  let mut tags = client.get_workspace_tags(debug, workspace_id)?;
+
+  if tags.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  tags.sort_by_key(|tag| tag.name.to_lowercase());
+
+  output_tags(output, &tags);
+
+  Ok(())
+}
+
+pub fn create(
+  debug: bool,
+  output: &Output,
+  create_tag: &CreateTag,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let workspace_id = selected_workspace_id(debug, client)?;
+  let tags = client.get_workspace_tags(debug, workspace_id)?;
+
+  if let Some(existing) = existing_tag(&tags, &create_tag.name, None) {
+    return Err(anyhow!(
+      "Tag '{}' exists already (id={})",
+      existing.name,
+      existing.id
+    ));
+  }
+
+  let tag = client.create_tag(debug, &create_tag.name, workspace_id)?;
+
+  output_tags(output, &[tag]);
+
+  Ok(())
+}
+
+pub fn rename(
+  debug: bool,
+  output: &Output,
+  rename_tag: &RenameTag,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let tag_id = resolver.tag_id(workspace_id, &rename_tag.id)?;
+
+  let tags = client.get_workspace_tags(debug, workspace_id)?;
+
+  if rename_tag.migrate {
+    return migrate(
+      debug,
+      output,
+      workspace_id,
+      tag_id,
+      &tags,
+      rename_tag,
+      client,
+    );
+  }
+
+  // renaming to a different spelling of the same tag is fine
+  if let Some(existing) = existing_tag(&tags, &rename_tag.to, Some(tag_id)) {
+    return Err(anyhow!(
+      "Tag '{}' exists already (id={}), delete one of the tags instead",
+      existing.name,
+      existing.id
+    ));
+  }
+
+  let tag = client.update_tag(debug, workspace_id, tag_id, &rename_tag.to)?;
+
+  output_tags(output, &[tag]);
+
+  Ok(())
+}
+
+/// Moves the time entries of the range from the tag to the new tag,
+/// which is created if necessary
+fn migrate(
+  debug: bool,
+  output: &Output,
+  workspace_id: u64,
+  tag_id: u64,
+  tags: &[Tag],
+  rename_tag: &RenameTag,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let all = matches!(rename_tag.range, Range::All);
+  let existing = existing_tag(tags, &rename_tag.to, Some(tag_id));
+
+  // time entries reference tags by id, a plain rename moves all of them
+  if existing.is_none() && all {
+    let tag = client.update_tag(debug, workspace_id, tag_id, &rename_tag.to)?;
+
+    output_tags(output, &[tag]);
+
+    return Ok(());
+  }
+
+  let from = tags
+    .iter()
+    .find(|tag| tag.id == tag_id)
+    .map(|tag| tag.name.to_owned())
+    .ok_or_else(|| anyhow!("Cannot find tag with id={tag_id}"))?;
+
+  let to = match existing {
+    Some(tag) => tag.name.to_owned(),
+    None => client.create_tag(debug, &rename_tag.to, workspace_id)?.name,
+  };
+
+  let time_entries = client
+    .get_time_entries(debug, &rename_tag.range)?
+    .into_iter()
+    .filter(|time_entry| time_entry.wid == workspace_id)
+    .filter(|time_entry| {
+      time_entry
+        .tags
+        .as_ref()
+        .is_some_and(|tags| tags.contains(&from))
+    })
+    .collect::<Vec<TimeEntry>>();
+
+  for mut time_entry in time_entries.iter().cloned() {
+    time_entry.tags =
+      time_entry.tags.map(|tags| migrated_tags(&tags, &from, &to));
+
+    client.update_time_entry(debug, &time_entry)?;
+  }
+
+  println!(
+    "Migrated {} time entries from '{from}' to '{to}'",
+    time_entries.len()
+  );
+
+  if all {
+    client.delete_tag(debug, workspace_id, tag_id)?;
+
+    println!("Deleted tag '{from}'");
+  }
+
+  Ok(())
+}
+
+/// Tags with `from` replaced by `to`, without duplicates
+pub(super) fn migrated_tags(
+  tags: &[String],
+  from: &str,
+  to: &str,
+) -> Vec<String> {
+  tags
+    .iter()
+    .map(|tag| if tag == from { to } else { tag.as_str() })
+    .unique()
+    .map(|tag| tag.to_string())
+    .collect()
+}
+
+pub fn delete(
+  debug: bool,
+  output: &Output,
+  delete_tag: &DeleteTag,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let tag_id = resolver.tag_id(workspace_id, &delete_tag.id)?;
+
+  client.delete_tag(debug, workspace_id, tag_id)?;
+
+  list(debug, output, client)?;
+
+  Ok(())
+}
+
+/// Tag with the same name (case-insensitive), apart from the tag `except`
+pub(super) fn existing_tag<'a>(
+  tags: &'a [Tag],
+  name: &str,
+  except: Option<u64>,
+) -> Option<&'a Tag> {
+  tags
+    .iter()
+    .filter(|tag| Some(tag.id) != except)
+    .find(|tag| tag.name.eq_ignore_ascii_case(name))
+}
+
+fn output_tags(output: &Output, tags: &[Tag]) {
+  match output.format {
+    Format::Json => output_values_json(output, tags),
+    Format::Raw => output_values_raw(tags),
+    Format::Table => output_values_table(tags),
+  }
+}
+
+fn output_values_raw(values: &[Tag]) {
+  for tag in values {
+    println!("\"{}\"", tag.name);
+  }
+}
+
+fn output_values_table(values: &[Tag]) {
+  let mut table = Table::new();
+  table.style = TableStyle::thin();
+
+  let header = Row::new(vec![
+    TableCell::new("ID".bold().white()),
+    TableCell::new("Name".bold().white()),
+  ]);
+
+  table.add_row(header);
+
+  for tag in values {
+    let row = Row::new(vec![TableCell::new(tag.id), TableCell::new(&tag.name)]);
+
+    table.add_row(row);
+  }
+
+  println!("{}", table.render());
+}
@@ -0,0 +1,56 @@
+use crate::{
+  commands::tags::{existing_tag, migrated_tags},
+  model::Tag,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_existing_tag() {
+  let tags = vec![
+    Tag {
+      id: 1,
+      name: "Meeting".to_string(),
+    },
+    Tag {
+      id: 2,
+      name: "review".to_string(),
+    },
+  ];
+
+  assert_eq!(
+    existing_tag(&tags, "meeting", None).map(|tag| tag.id),
+    Some(1)
+  );
+  assert_eq!(
+    existing_tag(&tags, "Review", None).map(|tag| tag.id),
+    Some(2)
+  );
+  assert_eq!(
+    existing_tag(&tags, "meeting", Some(1)).map(|tag| tag.id),
+    None
+  );
+  assert_eq!(existing_tag(&tags, "standup", None).map(|tag| tag.id), None);
+}
+
+#[test]
+fn test_migrated_tags() {
+  let tags = |tags: &[&str]| {
+    tags
+      .iter()
+      .map(|tag| tag.to_string())
+      .collect::<Vec<String>>()
+  };
+
+  assert_eq!(
+    migrated_tags(&tags(&["meeting", "internal"]), "meeting", "sync"),
+    tags(&["sync", "internal"])
+  );
+  assert_eq!(
+    migrated_tags(&tags(&["meeting", "sync"]), "meeting", "sync"),
+    tags(&["sync"])
+  );
+  assert_eq!(
+    migrated_tags(&tags(&["internal"]), "meeting", "sync"),
+    tags(&["internal"])
+  );
+}
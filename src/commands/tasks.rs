@@ -0,0 +1,87 @@
+use colored::Colorize;
+use term_table::{row::Row, table_cell::TableCell, Table, TableStyle};
+
+use crate::{
+  cli::{output_values_json, CreateTask, Format, ListTasks, Output},
+  client::TogglApi,
+  model::Task,
+  resolver::Resolver,
+};
+
+pub fn list(
+  debug: bool,
+  output: &Output,
+  list_tasks: &ListTasks,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let project_id = resolver.project_id(workspace_id, &list_tasks.project)?;
+
+  let mut tasks = client.get_project_tasks(debug, workspace_id, project_id)?;
+  tasks.retain(|task| task.active);
+
+  if tasks.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  tasks.sort_by_key(|task| task.name.to_lowercase());
+
+  output_tasks(output, &tasks);
+
+  Ok(())
+}
+
+pub fn create(
+  debug: bool,
+  output: &Output,
+  create_task: &CreateTask,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let project_id = resolver.project_id(workspace_id, &create_task.project)?;
+
+  let task =
+    client.create_task(debug, workspace_id, project_id, &create_task.name)?;
+
+  output_tasks(output, &[task]);
+
+  Ok(())
+}
+
+fn output_tasks(output: &Output, tasks: &[Task]) {
+  match output.format {
+    Format::Json => output_values_json(output, tasks),
+    Format::Raw => output_values_raw(tasks),
+    Format::Table => output_values_table(tasks),
+  }
+}
+
+fn output_values_raw(values: &[Task]) {
+  for task in values {
+    println!("\"{}\"", task.name);
+  }
+}
+
+fn output_values_table(values: &[Task]) {
+  let mut table = Table::new();
+  table.style = TableStyle::thin();
+
+  let header = Row::new(vec![
+    TableCell::new("ID".bold().white()),
+    TableCell::new("Name".bold().white()),
+  ]);
+
+  table.add_row(header);
+
+  for task in values {
+    let row =
+      Row::new(vec![TableCell::new(task.id), TableCell::new(&task.name)]);
+
+    table.add_row(row);
+  }
+
+  println!("{}", table.render());
+}
@@ -1,17 +1,45 @@
 use crate::{
+  calendar,
   cli::{
-    output_values_json, CreateTimeEntry, DeleteTimeEntry, Format,
-    StartTimeEntry, StopTimeEntry,
+    format_billable, output_values_json, truncate, BillableMarker,
+    BulkDeleteTimeEntries, BulkEditTimeEntries, ContinueTimeEntry,
+    CreateTimeEntry, CurrentTimeEntry, DateFormat, DeleteTimeEntry,
+    EditTimeEntry, Format, ListTimeEntries, MergeTimeEntries, Month, Output,
+    SplitTimeEntry, StartTimeEntry, StopTimeEntry, TimeEntryDetails, Week,
+    APP_NAME,
   },
-  client::TogglClient,
-  model::{Client, Project, Range, TimeEntry, Workspace},
+  client::TogglApi,
+  commands::workspaces::selected_workspace_id,
+  config::{is_non_billable_project, Calendar},
+  history::{self, DescriptionVersion},
+  http_client::is_network_error,
+  journal::{self, PendingOperation},
+  model::{
+    BulkEditResult, Client, ImportTimeEntry, PatchOperation, Project, Range,
+    TimeEntry, TimeEntryId, Workspace,
+  },
+  progress::{self, ProgressEvent},
+  report_client::{requires_paid_plan, TogglReportClient, PAID_PLAN_FALLBACK},
+  resolver::Resolver,
+  rounding::DurationRounding,
+  routing::{self, ProjectRule},
+  template::expand_description,
 };
 use anyhow::anyhow;
-use chrono::{DateTime, Duration, Local, NaiveDate};
-use colored::Colorize;
+use chrono::{
+  DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc,
+  Weekday,
+};
+use colored::{ColoredString, Colorize};
+use dialoguer::{Confirm, MultiSelect, Select};
 use hhmmss::Hhmmss;
-use itertools::Itertools;
-use std::{collections::HashMap, ops::Div};
+use indicatif::{ProgressBar, ProgressStyle};
+use itertools::{Either, Itertools};
+use regex::{Regex, RegexBuilder};
+use serde_json::{json, Value};
+use std::{
+  collections::HashMap, io::Write, ops::Div, path::Path, process::Command,
+};
 use term_table::{
   row::Row, table_cell::Alignment, table_cell::TableCell, Table, TableStyle,
 };
@@ -19,86 +47,558 @@ use term_table::{
 struct OutputEntry {
   id: u64,
   date: NaiveDate,
+  start: DateTime<Local>,
   duration: Duration,
   workspace: String,
   project: String,
   client: String,
   description: String,
   billable: bool,
+  /// Running for longer than 'stale_timer_hours'
+  stale: bool,
 }
 
 pub fn list(
   debug: bool,
-  format: &Format,
+  output: &Output,
   range: &Range,
-  missing: bool,
-  client: &TogglClient,
+  filter: &TimeEntryFilter,
+  rounding: Option<&DurationRounding>,
+  client: &dyn TogglApi,
 ) -> anyhow::Result<()> {
   let mut time_entries = client.get_time_entries(debug, range)?;
 
-  if missing {
-    let missing_datetimes = if time_entries.is_empty() {
-      range.get_datetimes()?
-    } else {
-      let mut missing_datetimes = vec![];
-
-      for date in range.get_datetimes()? {
-        if !time_entries
-          .iter()
-          .map(|entry| DateTime::<Local>::from(entry.start).date_naive())
-          .any(|x| x == date.date_naive())
-        {
-          missing_datetimes.push(date);
-        }
+  time_entries.retain(|time_entry| filter.matches(time_entry));
+
+  let overlaps = if filter.overlaps {
+    let overlaps = overlaps(&time_entries, Utc::now());
+
+    time_entries.retain(|time_entry| {
+      overlaps
+        .iter()
+        .any(|overlap| overlap.contains(time_entry.id))
+    });
+
+    overlaps
+  } else {
+    vec![]
+  };
+
+  if let Some(rounding) = rounding {
+    for time_entry in &mut time_entries {
+      if let Some(rounded) = rounding.round_time_entry(time_entry) {
+        *time_entry = rounded;
       }
+    }
+  }
 
-      missing_datetimes
-    };
+  if time_entries.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
 
-    if missing_datetimes.is_empty() {
-      println!("No entries found!");
-      return Ok(());
+  let workspaces = client.get_workspaces(debug)?;
+  let workspace_id = selected_workspace_id(debug, client)?;
+
+  let projects = client.get_workspace_projects(debug, false, workspace_id)?;
+  let clients = client
+    .get_workspace_clients(debug, false, workspace_id)?
+    .unwrap_or_default();
+
+  let output_entries = collect_output_entries(
+    output,
+    &mut time_entries,
+    &workspaces,
+    &projects,
+    &clients,
+  );
+
+  match output.format {
+    Format::Json => output_values_json(output, &time_entries),
+    Format::Raw => output_values_raw(output, &output_entries),
+    Format::Table => output_values_table(output, &output_entries),
+  }
+
+  if !overlaps.is_empty() && output.format != Format::Json {
+    let total = overlaps
+      .iter()
+      .fold(Duration::zero(), |total, overlap| total + overlap.duration);
+
+    for overlap in &overlaps {
+      eprintln!(
+        "{}",
+        format!(
+          "Time entry {} overlaps {} by {}",
+          overlap.first,
+          overlap.second,
+          overlap.duration.hhmmss()
+        )
+        .red()
+      );
     }
 
-    match format {
-      Format::Json => output_values_json(&missing_datetimes),
-      Format::Raw => output_missing_days_raw(&missing_datetimes),
-      Format::Table => output_missing_days_table(&missing_datetimes),
+    eprintln!(
+      "{}",
+      format!("{} counted twice", total.hhmmss()).red().bold()
+    );
+  }
+
+  Ok(())
+}
+
+/// Two time entries whose intervals intersect
+#[derive(Debug, PartialEq)]
+pub(super) struct Overlap {
+  /// Id of the time entry which started first
+  pub first: u64,
+  pub second: u64,
+  pub duration: Duration,
+}
+
+impl Overlap {
+  fn contains(&self, time_entry_id: u64) -> bool {
+    self.first == time_entry_id || self.second == time_entry_id
+  }
+}
+
+/// Every pair of overlapping time entries by start, running ones end `now`
+pub(super) fn overlaps(
+  time_entries: &[TimeEntry],
+  now: DateTime<Utc>,
+) -> Vec<Overlap> {
+  let end = |time_entry: &TimeEntry| {
+    if time_entry.duration.is_negative() {
+      now
+    } else {
+      time_entry.start + Duration::seconds(time_entry.duration)
     }
-  } else {
-    if time_entries.is_empty() {
-      println!("No entries found!");
-      return Ok(());
+  };
+
+  let time_entries = time_entries
+    .iter()
+    .sorted_by_key(|time_entry| (time_entry.start, time_entry.id))
+    .collect::<Vec<&TimeEntry>>();
+
+  let mut overlaps = vec![];
+
+  for (index, first) in time_entries.iter().enumerate() {
+    for second in &time_entries[index + 1..] {
+      if second.start >= end(first) {
+        break;
+      }
+
+      overlaps.push(Overlap {
+        first: first.id,
+        second: second.id,
+        duration: end(first).min(end(second)) - second.start,
+      });
     }
+  }
 
-    let workspaces = client.get_workspaces(debug)?;
-    let me = client.get_me(debug)?;
+  overlaps
+}
 
-    let workspace_id = me.default_workspace_id;
+/// Changes the durations of the matching stopped time entries to the rounded
+/// ones, their start stays
+pub fn apply_rounding(
+  debug: bool,
+  range: &Range,
+  filter: &TimeEntryFilter,
+  rounding: &DurationRounding,
+  yes: bool,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let time_entries = client
+    .get_time_entries(debug, range)?
+    .into_iter()
+    .filter(|time_entry| filter.matches(time_entry))
+    .filter_map(|time_entry| {
+      rounding
+        .round_time_entry(&time_entry)
+        .filter(|rounded| rounded.duration != time_entry.duration)
+        .map(|rounded| (time_entry, rounded))
+    })
+    .collect::<Vec<(TimeEntry, TimeEntry)>>();
 
-    let projects = client.get_workspace_projects(debug, false, workspace_id)?;
-    let clients = client
-      .get_workspace_clients(debug, false, workspace_id)?
-      .unwrap_or_default();
+  if time_entries.is_empty() {
+    println!("All time entries are rounded already");
+    return Ok(());
+  }
 
-    let output_entries = collect_output_entries(
-      &mut time_entries,
-      &workspaces,
-      &projects,
-      &clients,
+  for (time_entry, rounded) in &time_entries {
+    println!(
+      "{} -> {}",
+      time_entry_summary(time_entry),
+      Duration::seconds(rounded.duration).hhmmss()
     );
+  }
+
+  if !yes
+    && !Confirm::new()
+      .with_prompt(format!("Round {} time entries?", time_entries.len()))
+      .default(false)
+      .interact()?
+  {
+    println!("Do nothing!");
+    return Ok(());
+  }
+
+  for (_, rounded) in &time_entries {
+    client.update_time_entry(debug, rounded)?;
+  }
+
+  println!("Rounded {} time entries", time_entries.len());
+
+  Ok(())
+}
+
+/// Filters of `time-entries list`, the default keeps every time entry
+#[derive(Debug, Default)]
+pub struct TimeEntryFilter {
+  tags: Vec<String>,
+  search: Option<Regex>,
+  overlaps: bool,
+}
+
+impl TimeEntryFilter {
+  pub fn new(
+    list_time_entries: &ListTimeEntries,
+  ) -> anyhow::Result<TimeEntryFilter> {
+    let search = list_time_entries
+      .search
+      .as_ref()
+      .map(|search| {
+        let pattern = if list_time_entries.regex {
+          search.to_owned()
+        } else {
+          regex::escape(search)
+        };
+
+        RegexBuilder::new(&pattern).case_insensitive(true).build()
+      })
+      .transpose()?;
+
+    Ok(TimeEntryFilter {
+      tags: list_time_entries.tags.to_owned(),
+      search,
+      overlaps: list_time_entries.overlaps,
+    })
+  }
+
+  pub fn matches(&self, time_entry: &TimeEntry) -> bool {
+    let tagged = self.tags.is_empty() || has_any_tag(time_entry, &self.tags);
+
+    let found = self.search.as_ref().is_none_or(|search| {
+      time_entry
+        .description
+        .as_deref()
+        .is_some_and(|description| search.is_match(description))
+    });
+
+    tagged && found
+  }
+}
+
+pub(super) fn has_any_tag(time_entry: &TimeEntry, tags: &[String]) -> bool {
+  time_entry
+    .tags
+    .iter()
+    .flatten()
+    .any(|entry_tag| tags.iter().any(|tag| tag.eq_ignore_ascii_case(entry_tag)))
+}
+
+pub fn week(
+  debug: bool,
+  output: &Output,
+  week: &Week,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let (start, _) = week.range.as_range()?;
+  let monday = week_start(start.date_naive());
+
+  let mut time_entries = client.get_time_entries(
+    debug,
+    &Range::FromTo(monday, monday + Duration::days(6)),
+  )?;
+
+  if time_entries.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  let workspaces = client.get_workspaces(debug)?;
+  let workspace_id = selected_workspace_id(debug, client)?;
+
+  let projects = client.get_workspace_projects(debug, false, workspace_id)?;
+  let clients = client
+    .get_workspace_clients(debug, false, workspace_id)?
+    .unwrap_or_default();
+
+  let output_entries = collect_output_entries(
+    output,
+    &mut time_entries,
+    &workspaces,
+    &projects,
+    &clients,
+  );
+
+  match output.format {
+    Format::Json => output_values_json(output, &time_entries),
+    Format::Raw => output_values_raw(output, &output_entries),
+    Format::Table => output_week_table(output, monday, &output_entries),
+  }
+
+  Ok(())
+}
+
+/// Monday of the week which contains the date
+pub(super) fn week_start(date: NaiveDate) -> NaiveDate {
+  date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+pub fn month(
+  debug: bool,
+  output: &Output,
+  month: &Month,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let (start, _) = month.range.as_range()?;
+
+  let first_day = start.date_naive().with_day(1).unwrap();
+  let last_day = first_day
+    .checked_add_months(chrono::Months::new(1))
+    .and_then(|date| date.pred_opt())
+    .ok_or_else(|| anyhow!("Could not calculate the end of the month"))?;
+
+  let time_entries =
+    client.get_time_entries(debug, &Range::FromTo(first_day, last_day))?;
+
+  let logged_by_date = logged_by_date(&time_entries);
+
+  let days = first_day
+    .iter_days()
+    .take_while(|date| *date <= last_day)
+    .map(|date| {
+      let logged = logged_by_date.get(&date).copied().unwrap_or_default();
+
+      (date, logged)
+    })
+    .collect::<Vec<(NaiveDate, Duration)>>();
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &days
+        .iter()
+        .map(|(date, logged)| {
+          json!({ "date": date, "logged": logged.num_seconds() })
+        })
+        .collect::<Vec<Value>>(),
+    ),
+    Format::Raw => {
+      for (date, logged) in &days {
+        println!("{}\t{}", output.format_date(date), logged.hhmmss());
+      }
+    }
+    Format::Table => output_month_table(output, &days),
+  }
+
+  Ok(())
+}
+
+/// How much was logged on a day of `fbtoggl month`
+#[derive(Debug, PartialEq)]
+pub(super) enum Heat {
+  Nothing,
+  Below4Hours,
+  From4To8Hours,
+  Above8Hours,
+}
+
+pub(super) fn heat(logged: Duration) -> Heat {
+  if logged.is_zero() {
+    Heat::Nothing
+  } else if logged < Duration::try_hours(4).unwrap() {
+    Heat::Below4Hours
+  } else if logged <= Duration::try_hours(8).unwrap() {
+    Heat::From4To8Hours
+  } else {
+    Heat::Above8Hours
+  }
+}
+
+/// Weeks (Monday to Sunday) of the consecutive days, days before the first
+/// and after the last one are None
+pub(super) fn calendar_weeks<T: Copy>(
+  days: &[(NaiveDate, T)],
+) -> Vec<[Option<(NaiveDate, T)>; 7]> {
+  if days.is_empty() {
+    return vec![];
+  }
+
+  let mut weeks = vec![];
+  let mut week = [None; 7];
+
+  for (index, day) in days.iter().enumerate() {
+    let weekday = day.0.weekday().num_days_from_monday() as usize;
+
+    if weekday == 0 && index > 0 {
+      weeks.push(week);
+      week = [None; 7];
+    }
+
+    week[weekday] = Some(*day);
+  }
+
+  weeks.push(week);
 
-    match format {
-      Format::Json => output_values_json(&time_entries),
-      Format::Raw => output_values_raw(&output_entries),
-      Format::Table => output_values_table(&output_entries),
+  weeks
+}
+
+pub fn missing(
+  debug: bool,
+  output: &Output,
+  list_time_entries: &ListTimeEntries,
+  client: &dyn TogglApi,
+  report_client: &TogglReportClient,
+) -> anyhow::Result<()> {
+  let range = &list_time_entries.range;
+
+  let expected = list_time_entries.expected_hours.map(|hours| {
+    Duration::try_seconds((hours * 3600.0) as i64).unwrap_or_default()
+  });
+
+  let logged_by_date = match daily_totals(debug, range, client, report_client)?
+  {
+    Some(daily_totals) => daily_totals,
+    None => logged_by_date(&client.get_time_entries(debug, range)?),
+  };
+
+  let missing_days = missing_days(
+    &range.get_datetimes(list_time_entries.include_weekends)?,
+    &logged_by_date,
+    expected,
+  );
+
+  if missing_days.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  match (&output.format, expected) {
+    (Format::Json, None) => output_values_json(
+      output,
+      &missing_days
+        .iter()
+        .map(|missing_day| missing_day.date)
+        .collect::<Vec<DateTime<Local>>>(),
+    ),
+    (Format::Json, Some(_)) => output_values_json(
+      output,
+      &missing_days
+        .iter()
+        .map(|missing_day| {
+          json!({
+            "date": missing_day.date.date_naive(),
+            "logged": missing_day.logged.num_seconds(),
+          })
+        })
+        .collect::<Vec<Value>>(),
+    ),
+    (Format::Raw, _) => {
+      output_missing_days_raw(output, &missing_days, expected)
+    }
+    (Format::Table, _) => {
+      output_missing_days_table(output, &missing_days, expected)
     }
   }
 
   Ok(())
 }
 
+#[derive(Debug, PartialEq)]
+pub struct MissingDay {
+  pub date: DateTime<Local>,
+  pub logged: Duration,
+}
+
+// Ranges longer than a month are summed up by the Reports API (one request
+// per month) instead of downloading every single time entry
+fn daily_totals(
+  debug: bool,
+  range: &Range,
+  client: &dyn TogglApi,
+  report_client: &TogglReportClient,
+) -> anyhow::Result<Option<HashMap<NaiveDate, Duration>>> {
+  let (start, end) = range.as_range()?;
+
+  if end - start <= Duration::try_days(31).unwrap() {
+    return Ok(None);
+  }
+
+  let workspace_id = selected_workspace_id(debug, client)?;
+  let user_id = client.get_me(debug)?.id;
+
+  match report_client.daily_totals(debug, workspace_id, user_id, range) {
+    Err(err) if requires_paid_plan(&err) => {
+      eprintln!("{}", PAID_PLAN_FALLBACK.yellow());
+
+      Ok(None)
+    }
+    daily_totals => daily_totals,
+  }
+}
+
+/// Logged time per day, running entries count up to now
+pub(super) fn logged_by_date(
+  time_entries: &[TimeEntry],
+) -> HashMap<NaiveDate, Duration> {
+  let now = Local::now();
+
+  time_entries
+    .iter()
+    .map(|entry| {
+      let start = DateTime::<Local>::from(entry.start);
+
+      let duration = if entry.duration.is_negative() {
+        now - start
+      } else {
+        Duration::try_seconds(entry.duration).unwrap_or_default()
+      };
+
+      (start.date_naive(), duration)
+    })
+    .into_grouping_map()
+    .fold(Duration::zero(), |sum, _, duration| sum + duration)
+}
+
+/// Days without any entry, with expected hours also days which are
+/// logged below the expectation
+pub(super) fn missing_days(
+  dates: &[DateTime<Local>],
+  logged_by_date: &HashMap<NaiveDate, Duration>,
+  expected: Option<Duration>,
+) -> Vec<MissingDay> {
+  dates
+    .iter()
+    .filter_map(|date| {
+      let logged = logged_by_date.get(&date.date_naive()).copied();
+
+      match (logged, expected) {
+        (None, _) => Some(Duration::zero()),
+        (Some(logged), Some(expected)) if logged < expected => Some(logged),
+        _ => None,
+      }
+      .map(|logged| MissingDay {
+        date: *date,
+        logged,
+      })
+    })
+    .collect()
+}
+
 fn collect_output_entries(
+  output: &Output,
   values: &mut [TimeEntry],
   workspaces: &[Workspace],
   projects: &[Project],
@@ -121,7 +621,7 @@ fn collect_output_entries(
 
   let mut output_entries = vec![];
 
-  values.sort_by(|e1, e2| e1.start.cmp(&e2.start));
+  values.sort_by_key(|entry| entry.start);
 
   for entry in values {
     let maybe_workspace = workspace_lookup.get(&entry.wid);
@@ -139,6 +639,7 @@ fn collect_output_entries(
     output_entries.push(OutputEntry {
       id: entry.id,
       date: entry.start.date_naive(),
+      start: DateTime::<Local>::from(entry.start),
       duration,
       workspace: maybe_workspace
         .map(|w| w.name.to_owned())
@@ -151,190 +652,2240 @@ fn collect_output_entries(
         .unwrap_or_else(|| "-".to_string()),
       description: entry.description.to_owned().unwrap_or_default(),
       billable: entry.billable.unwrap_or_default(),
+      stale: stale_warning(output, entry, Utc::now()).is_some(),
     })
   }
 
-  output_entries
+  output_entries
+}
+
+pub fn create(
+  debug: bool,
+  output: &Output,
+  time_entry: &CreateTimeEntry,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  if let Some(path) = &time_entry.from_json {
+    return import(
+      debug,
+      output,
+      parse_import(&read_input(path)?)?,
+      time_entry.non_billable,
+      client,
+    );
+  }
+
+  if let Some(path) = &time_entry.from_toggl_csv {
+    return import(
+      debug,
+      output,
+      parse_toggl_csv(&read_input(path)?)?,
+      time_entry.non_billable,
+      client,
+    );
+  }
+
+  match create_time_entries(
+    debug,
+    time_entry,
+    &output.date_format,
+    false,
+    client,
+  ) {
+    Err(err) if is_network_error(&err) => {
+      journal::push(PendingOperation::Create {
+        workspace: client.workspace().map(|workspace| workspace.to_string()),
+        project: time_entry.project.to_owned().unwrap_or_default(),
+        task: time_entry.task.to_owned(),
+        description: time_entry.description.to_owned(),
+        tags: time_entry.tags.to_owned(),
+        start: time_entry.start,
+        end: time_entry.end,
+        duration_seconds: time_entry
+          .duration
+          .map(|duration| duration.num_seconds()),
+        lunch_break: time_entry.lunch_break,
+        non_billable: time_entry.non_billable,
+        split_by_days: time_entry.split_by_days,
+        create_missing_project: time_entry.create_missing_project,
+        client: time_entry.client.to_owned(),
+      })?;
+
+      println!(
+        "No network connection, the time entry will be created with the next command or 'fbtoggl sync'"
+      );
+
+      return Ok(());
+    }
+    result => result?,
+  }
+
+  list(
+    debug,
+    output,
+    &Range::Today,
+    &TimeEntryFilter::default(),
+    None,
+    client,
+  )
+}
+
+/// Creates the time entries of --start/--end or --duration, split by days if
+/// requested. `skip_existing` skips time entries with the same start and
+/// description, e.g. when replaying a create which failed partway through.
+pub(crate) fn create_time_entries(
+  debug: bool,
+  time_entry: &CreateTimeEntry,
+  date_format: &DateFormat,
+  skip_existing: bool,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let project = time_entry.project.as_deref().ok_or_else(|| {
+    anyhow!("--project is missing, or set 'default_project' in settings.toml")
+  })?;
+
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let project_id = resolver.project_id_or_create(
+    workspace_id,
+    project,
+    time_entry.create_missing_project,
+    time_entry.client.as_deref(),
+  )?;
+  let task_id = time_entry
+    .task
+    .as_ref()
+    .map(|task| resolver.task_id(workspace_id, project_id, task))
+    .transpose()?;
+  let tags = resolver.tags(workspace_id, &time_entry.tags)?;
+  let non_billable = time_entry.non_billable
+    || resolver.is_non_billable_project(workspace_id, project_id);
+
+  let spans = match time_entry.end {
+    Some(end) if end.date_naive() != time_entry.start.date_naive() => {
+      if !time_entry.split_by_days {
+        return Err(anyhow!(
+          "start='{}' and end='{}' are on different days, use --split-by-days to create one entry per day",
+          time_entry.start,
+          end
+        ));
+      }
+
+      split_by_days(time_entry.start, end, time_entry.lunch_break)?
+    }
+    _ => vec![(time_entry.start, calculate_duration(time_entry)?)],
+  };
+
+  let mut entries = vec![];
+
+  for (start, duration) in spans {
+    let description =
+      expand_description(&time_entry.description, start, date_format)?;
+
+    if time_entry.lunch_break {
+      let duration = duration.div(2);
+
+      entries.push((start, duration, description.to_owned()));
+      entries.push((start + launch_break() + duration, duration, description));
+    } else {
+      entries.push((start, duration, description));
+    }
+  }
+
+  let existing = match (skip_existing, entries.first(), entries.last()) {
+    (true, Some((first, ..)), Some((last, ..))) => client.get_time_entries(
+      debug,
+      &Range::FromTo(first.date_naive(), last.date_naive()),
+    )?,
+    _ => vec![],
+  };
+
+  for (start, duration, description) in entries {
+    if existing.iter().any(|existing| {
+      existing.start == start
+        && existing.description.as_deref().unwrap_or_default()
+          == description.as_deref().unwrap_or_default()
+    }) {
+      continue;
+    }
+
+    client.create_time_entry(
+      debug,
+      &description,
+      workspace_id,
+      &tags,
+      duration,
+      start,
+      project_id,
+      task_id,
+      non_billable,
+    )?;
+  }
+
+  Ok(())
+}
+
+/// Content of the file, '-' reads stdin
+fn read_input(path: &Path) -> anyhow::Result<String> {
+  if path == Path::new("-") {
+    Ok(std::io::read_to_string(std::io::stdin())?)
+  } else {
+    Ok(std::fs::read_to_string(path)?)
+  }
+}
+
+/// Creates every time entry after all of them are validated
+fn import(
+  debug: bool,
+  output: &Output,
+  time_entries: Vec<ImportTimeEntry>,
+  non_billable_by_default: bool,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+  let selected_workspace_id = resolver.workspace_id()?;
+
+  let mut errors = vec![];
+  let mut validated = vec![];
+
+  for (index, time_entry) in time_entries.iter().enumerate() {
+    let workspace_id = time_entry.wid.unwrap_or(selected_workspace_id);
+
+    let project_id = time_entry
+      .project_selector()
+      .and_then(|selector| resolver.project_id(workspace_id, &selector));
+    let tags = resolver.tags(workspace_id, &time_entry.tags);
+
+    match (project_id, tags, time_entry.stopped_duration()) {
+      (Ok(project_id), Ok(tags), Ok(duration)) => {
+        validated.push((time_entry, workspace_id, project_id, tags, duration))
+      }
+      (project_id, tags, duration) => errors.extend(
+        [project_id.err(), tags.err(), duration.err()]
+          .into_iter()
+          .flatten()
+          .map(|err| format!("entry {}: {err}", index + 1)),
+      ),
+    }
+  }
+
+  if !errors.is_empty() {
+    return Err(anyhow!(
+      "Nothing created, invalid time entries:\n{}",
+      errors.join("\n")
+    ));
+  }
+
+  let mut created = vec![];
+
+  for (time_entry, workspace_id, project_id, tags, duration) in validated {
+    let created_time_entry = client
+      .create_time_entry(
+        debug,
+        &time_entry.description,
+        workspace_id,
+        &tags,
+        duration,
+        DateTime::<Local>::from(time_entry.start),
+        project_id,
+        None,
+        time_entry
+          .billable
+          .map_or(non_billable_by_default, |billable| !billable)
+          || resolver.is_non_billable_project(workspace_id, project_id),
+      )
+      .map_err(|err| {
+        anyhow!(
+          "Created {} of {} time entries, then failed: {err}",
+          created.len(),
+          time_entries.len()
+        )
+      })?;
+
+    created.push(created_time_entry);
+  }
+
+  match output.format {
+    Format::Json => output_values_json(output, &created),
+    Format::Raw | Format::Table => {
+      println!("Created {} time entries", created.len())
+    }
+  }
+
+  Ok(())
+}
+
+pub(super) fn parse_import(json: &str) -> anyhow::Result<Vec<ImportTimeEntry>> {
+  serde_json::from_str(json)
+    .map_err(|err| anyhow!("Expected a JSON array of time entries: {err}"))
+}
+
+/// Time entries of a detailed report CSV of the Toggl web app, columns are
+/// looked up by name ('End date' and 'End time' are 'Stop date' and
+/// 'Stop time' in newer exports), dates and times are local
+pub(super) fn parse_toggl_csv(
+  csv: &str,
+) -> anyhow::Result<Vec<ImportTimeEntry>> {
+  let mut reader = csv::Reader::from_reader(csv.as_bytes());
+
+  let headers = reader.headers()?.clone();
+
+  let column = |names: &[&str]| {
+    headers.iter().position(|header| {
+      names
+        .iter()
+        .any(|name| header.trim().eq_ignore_ascii_case(name))
+    })
+  };
+
+  let start_date = column(&["Start date"])
+    .ok_or_else(|| anyhow!("Expected a Toggl CSV export with 'Start date'"))?;
+  let start_time = column(&["Start time"])
+    .ok_or_else(|| anyhow!("Expected a Toggl CSV export with 'Start time'"))?;
+  let end_date = column(&["End date", "Stop date"]);
+  let end_time = column(&["End time", "Stop time"]);
+  let duration = column(&["Duration"]);
+  let project = column(&["Project"]);
+  let description = column(&["Description"]);
+  let billable = column(&["Billable"]);
+  let tags = column(&["Tags"]);
+
+  let mut time_entries = vec![];
+
+  for (index, record) in reader.records().enumerate() {
+    let record = record?;
+
+    // first line are the headers
+    let line = index + 2;
+
+    let value = |column: Option<usize>| {
+      column
+        .and_then(|column| record.get(column))
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+    };
+
+    let local_time = |date: Option<usize>, time: Option<usize>| {
+      let (Some(date), Some(time)) = (value(date), value(time)) else {
+        return Ok(None);
+      };
+
+      let date_time = chrono::NaiveDateTime::parse_from_str(
+        &format!("{date} {time}"),
+        "%Y-%m-%d %H:%M:%S",
+      )
+      .map_err(|err| anyhow!("line {line}: '{date} {time}': {err}"))?;
+
+      Local
+        .from_local_datetime(&date_time)
+        .earliest()
+        .map(|date_time| Some(date_time.with_timezone(&Utc)))
+        .ok_or_else(|| anyhow!("line {line}: '{date} {time}' does not exist"))
+    };
+
+    let start = local_time(Some(start_date), Some(start_time))?
+      .ok_or_else(|| anyhow!("line {line}: start is missing"))?;
+
+    let duration = value(duration)
+      .map(|duration| {
+        parse_hhmmss(duration)
+          .ok_or_else(|| anyhow!("line {line}: invalid duration '{duration}'"))
+      })
+      .transpose()?;
+
+    time_entries.push(ImportTimeEntry {
+      wid: None,
+      pid: None,
+      project: value(project).map(|project| project.to_string()),
+      billable: value(billable)
+        .map(|billable| billable.eq_ignore_ascii_case("yes")),
+      start,
+      stop: local_time(end_date, end_time)?,
+      duration,
+      description: value(description)
+        .map(|description| description.to_string()),
+      tags: value(tags).map(|tags| {
+        tags.split(',').map(|tag| tag.trim().to_string()).collect()
+      }),
+    });
+  }
+
+  Ok(time_entries)
+}
+
+/// Seconds of 'HH:MM:SS', hours may exceed a day
+fn parse_hhmmss(duration: &str) -> Option<i64> {
+  let parts = duration
+    .split(':')
+    .map(|part| part.parse::<i64>().ok())
+    .collect::<Option<Vec<i64>>>()?;
+
+  match parts[..] {
+    [hours, minutes, seconds] => Some(hours * 3600 + minutes * 60 + seconds),
+    _ => None,
+  }
+}
+
+fn launch_break() -> Duration {
+  Duration::try_hours(1).unwrap()
+}
+
+pub(super) fn calculate_duration(
+  time_entry: &CreateTimeEntry,
+) -> anyhow::Result<Duration> {
+  if let Some(end) = time_entry.end {
+    let start = time_entry.start;
+
+    if start >= end {
+      return Err(anyhow!(
+        "start='{}' is greater or equal than end='{}'",
+        start,
+        end
+      ));
+    }
+
+    let duration = end - start;
+
+    if time_entry.lunch_break {
+      calculate_duration_with_lunch_break(duration)
+    } else {
+      Ok(duration)
+    }
+  } else {
+    time_entry
+      .duration
+      .ok_or_else(|| anyhow!("Please use either --duration or --end"))
+  }
+}
+
+// Creates one span per weekday between start and end,
+// each covering the time of day of start until the time of day of end
+pub(super) fn split_by_days(
+  start: DateTime<Local>,
+  end: DateTime<Local>,
+  lunch_break: bool,
+) -> anyhow::Result<Vec<(DateTime<Local>, Duration)>> {
+  if start.time() >= end.time() {
+    return Err(anyhow!(
+      "start time='{}' is greater or equal than end time='{}'",
+      start.time(),
+      end.time()
+    ));
+  }
+
+  let mut spans = vec![];
+
+  for date in start.date_naive().iter_days() {
+    if date > end.date_naive() {
+      break;
+    }
+
+    if date.weekday() == Weekday::Sat || date.weekday() == Weekday::Sun {
+      continue;
+    }
+
+    let day_start = Local
+      .from_local_datetime(&date.and_time(start.time()))
+      .single()
+      .ok_or_else(|| anyhow!("Could not create start datetime for {date}"))?;
+
+    let day_end = Local
+      .from_local_datetime(&date.and_time(end.time()))
+      .single()
+      .ok_or_else(|| anyhow!("Could not create end datetime for {date}"))?;
+
+    let duration = if lunch_break {
+      calculate_duration_with_lunch_break(day_end - day_start)?
+    } else {
+      day_end - day_start
+    };
+
+    spans.push((day_start, duration));
+  }
+
+  if spans.is_empty() {
+    return Err(anyhow!(
+      "No working days between {} and {}",
+      start.date_naive(),
+      end.date_naive()
+    ));
+  }
+
+  Ok(spans)
+}
+
+fn calculate_duration_with_lunch_break(
+  duration: Duration,
+) -> anyhow::Result<Duration> {
+  let duration_with_lunch_break = duration - launch_break();
+
+  if duration_with_lunch_break <= Duration::zero() {
+    Err(anyhow!("Duration minus lunch break is <= 0"))
+  } else {
+    Ok(duration_with_lunch_break)
+  }
+}
+
+pub fn start(
+  debug: bool,
+  output: &Output,
+  time_entry: &StartTimeEntry,
+  calendar: &Calendar,
+  project_rules: &[ProjectRule],
+  default_project: Option<&str>,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let start = match &time_entry.after {
+    Some(after) => chained_start(debug, after, client)?,
+    None => chrono::Local::now(),
+  };
+
+  let recent = if time_entry.recent {
+    match select_time_entry(
+      debug,
+      RECENT_CHOICES,
+      "Select the time entry to start again",
+      client,
+    )? {
+      Some(recent) => Some(recent),
+      None => {
+        println!("No entries found!");
+        return Ok(());
+      }
+    }
+  } else {
+    None
+  };
+
+  let task = time_entry.task.to_owned().or_else(|| {
+    recent
+      .as_ref()
+      .and_then(|recent| recent.task_id)
+      .map(|task_id| task_id.to_string())
+  });
+  let tags = time_entry
+    .tags
+    .to_owned()
+    .or_else(|| recent.as_ref().and_then(|recent| recent.tags.to_owned()));
+
+  let (project, description) = match (recent, &time_entry.from_ical) {
+    // candidates always have a project
+    (Some(recent), _) => (
+      recent.pid.map(|pid| pid.to_string()).unwrap_or_default(),
+      recent.description,
+    ),
+    (None, Some(source)) => {
+      let events = calendar::parse_events(&calendar::read_calendar(source)?)?;
+
+      let event = calendar::ongoing_event(&events, start)
+        .ok_or_else(|| anyhow!("No ongoing event in calendar '{source}'"))?;
+
+      let project =
+        calendar::mapped_project(&calendar.projects, &event.summary)
+          .map(|project| project.to_string())
+          .or_else(|| time_entry.project.to_owned())
+          .or_else(|| {
+            routing::routed_project(project_rules, start)
+              .or(default_project)
+              .map(|project| project.to_string())
+          })
+          .ok_or_else(|| {
+            anyhow!(
+              "No project mapped for event '{}', use --project",
+              event.summary
+            )
+          })?;
+
+      (project, Some(event.summary.to_owned()))
+    }
+    (None, None) => {
+      let project = time_entry
+        .project
+        .to_owned()
+        .or_else(|| {
+          routing::routed_project(project_rules, start)
+            .or(default_project)
+            .map(|project| project.to_string())
+        })
+        .ok_or_else(|| {
+          anyhow!(
+            "No [[project_rules]] entry matches right now, use --project or 'default_project' in settings.toml"
+          )
+        })?;
+
+      (
+        project,
+        expand_description(
+          &time_entry.description,
+          start,
+          &output.date_format,
+        )?,
+      )
+    }
+  };
+
+  let started_time_entry = match start_time_entry(
+    debug,
+    start,
+    &project,
+    &task,
+    &description,
+    &tags,
+    time_entry.non_billable,
+    time_entry.create_missing_project,
+    &time_entry.client,
+    client,
+  ) {
+    Err(err) if is_network_error(&err) => {
+      journal::push(PendingOperation::Start {
+        start,
+        workspace: client.workspace().map(|workspace| workspace.to_string()),
+        project,
+        task,
+        description,
+        tags,
+        non_billable: time_entry.non_billable,
+        create_missing_project: time_entry.create_missing_project,
+        client: time_entry.client.to_owned(),
+      })?;
+
+      println!(
+        "No network connection, the time entry will be started at {} with the next command or 'fbtoggl sync'",
+        start.format("%H:%M:%S")
+      );
+
+      return Ok(());
+    }
+    result => result?,
+  };
+
+  output_time_entry(output, &started_time_entry);
+
+  if let Some(alarm) = time_entry.alarm {
+    watch_alarm(
+      debug,
+      alarm,
+      time_entry.auto_stop,
+      &started_time_entry,
+      client,
+    )?;
+  }
+
+  if let Some(interval) = time_entry.pomodoro {
+    pomodoro(
+      debug,
+      interval,
+      time_entry.notify,
+      &started_time_entry,
+      client,
+    )?;
+  }
+
+  Ok(())
+}
+
+/// Stop time of the time entry, a running one is stopped right now
+pub(super) fn chained_start(
+  debug: bool,
+  after: &TimeEntryId,
+  client: &dyn TogglApi,
+) -> anyhow::Result<DateTime<Local>> {
+  let time_entry_id = Resolver::new(debug, client).time_entry_id(after)?;
+  let mut time_entry = client.get_time_entry(debug, time_entry_id)?;
+
+  if time_entry.duration.is_negative() {
+    time_entry =
+      client.stop_time_entry(debug, time_entry.wid, time_entry.id)?;
+  }
+
+  let stop = time_entry.stop.unwrap_or_else(|| {
+    time_entry.start + Duration::seconds(time_entry.duration)
+  });
+
+  Ok(stop.with_timezone(&Local))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn start_time_entry(
+  debug: bool,
+  start: DateTime<Local>,
+  project_name: &str,
+  task: &Option<String>,
+  description: &Option<String>,
+  tags: &Option<Vec<String>>,
+  non_billable: bool,
+  create_missing_project: bool,
+  project_client: &Option<String>,
+  client: &dyn TogglApi,
+) -> anyhow::Result<TimeEntry> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let project_id = resolver.project_id_or_create(
+    workspace_id,
+    project_name,
+    create_missing_project,
+    project_client.as_deref(),
+  )?;
+  let task_id = task
+    .as_ref()
+    .map(|task| resolver.task_id(workspace_id, project_id, task))
+    .transpose()?;
+
+  client.start_time_entry(
+    debug,
+    start,
+    workspace_id,
+    description,
+    &resolver.tags(workspace_id, tags)?,
+    project_id,
+    task_id,
+    non_billable || resolver.is_non_billable_project(workspace_id, project_id),
+  )
+}
+
+// Blocks until the time entry exceeds the alarm duration,
+// time entries stopped in the meantime (e.g. in the web UI) end the watch silently
+fn watch_alarm(
+  debug: bool,
+  alarm: Duration,
+  auto_stop: bool,
+  time_entry: &TimeEntry,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let alarm_at = DateTime::<Local>::from(time_entry.start) + alarm;
+  let remaining = alarm_at - Local::now();
+
+  if remaining > Duration::zero() {
+    std::thread::sleep(remaining.to_std()?);
+  }
+
+  let current = client.get_time_entry(debug, time_entry.id)?;
+
+  if current.stop.is_some() || !current.duration.is_negative() {
+    return Ok(());
+  }
+
+  // \x07 rings the terminal bell
+  println!(
+    "\x07{}",
+    format!("Time entry {} exceeded {}!", time_entry.id, alarm.hhmmss())
+      .bold()
+      .red()
+  );
+
+  if auto_stop {
+    client.stop_time_entry(debug, current.wid, current.id)?;
+
+    println!("Stopped time entry {}", current.id);
+  }
+
+  Ok(())
+}
+
+/// Counts down the interval and stops the time entry afterwards,
+/// unless it was stopped in the meantime
+fn pomodoro(
+  debug: bool,
+  interval: Duration,
+  notify: bool,
+  time_entry: &TimeEntry,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let end = DateTime::<Local>::from(time_entry.start) + interval;
+
+  let progress_bar = ProgressBar::new(interval.num_seconds().max(0) as u64)
+    .with_style(ProgressStyle::with_template("{bar:40} {msg} left")?);
+
+  loop {
+    let remaining = end - Local::now();
+
+    if remaining <= Duration::zero() {
+      break;
+    }
+
+    progress_bar
+      .set_position((interval - remaining).num_seconds().max(0) as u64);
+    progress_bar.set_message(remaining.hhmmss());
+
+    std::thread::sleep(remaining.min(Duration::seconds(1)).to_std()?);
+  }
+
+  progress_bar.finish_and_clear();
+
+  let current = client.get_time_entry(debug, time_entry.id)?;
+
+  if current.stop.is_some() || !current.duration.is_negative() {
+    println!("Time entry {} was stopped in the meantime", current.id);
+
+    return Ok(());
+  }
+
+  client.stop_time_entry(debug, current.wid, current.id)?;
+
+  let message = format!(
+    "Pomodoro of {} is over, stopped time entry {}",
+    interval.hhmmss(),
+    current.id
+  );
+
+  // \x07 rings the terminal bell
+  println!("\x07{}", message.bold().green());
+
+  if notify {
+    if let Err(err) = desktop_notification(&message) {
+      eprintln!("Failed to show desktop notification: {err}");
+    }
+  }
+
+  Ok(())
+}
+
+/// Command showing a desktop notification on this platform, if there is one
+pub(super) fn notification_command(message: &str) -> Option<Command> {
+  if cfg!(target_os = "macos") {
+    let mut command = Command::new("osascript");
+    command.arg("-e").arg(format!(
+      "display notification {message:?} with title \"{APP_NAME}\""
+    ));
+
+    Some(command)
+  } else if cfg!(unix) {
+    let mut command = Command::new("notify-send");
+    command.arg(APP_NAME).arg(message);
+
+    Some(command)
+  } else {
+    None
+  }
+}
+
+fn desktop_notification(message: &str) -> anyhow::Result<()> {
+  let mut command = notification_command(message)
+    .ok_or_else(|| anyhow!("Not supported on this platform"))?;
+
+  let status = command.status()?;
+
+  if !status.success() {
+    return Err(anyhow!("{command:?} exited with {status}"));
+  }
+
+  Ok(())
+}
+
+/// Shows the running time entry (nothing with --check),
+/// returns whether a time entry is running
+pub fn current(
+  debug: bool,
+  output: &Output,
+  current_time_entry: &CurrentTimeEntry,
+  client: &dyn TogglApi,
+) -> anyhow::Result<bool> {
+  if current_time_entry.watch {
+    watch_current(output, debug, client)?;
+  }
+
+  let running = client.get_current_time_entry(debug)?;
+
+  if !current_time_entry.check {
+    match &running {
+      Some(time_entry) => {
+        output_time_entry(output, time_entry);
+
+        if let Some(warning) = stale_warning(output, time_entry, Utc::now()) {
+          eprintln!("{}", warning.red());
+        }
+      }
+      None => println!("No time entry is running"),
+    }
+  }
+
+  Ok(running.is_some())
+}
+
+/// Stops the time entry, false if no time entry is running (without --id)
+pub fn stop(
+  debug: bool,
+  output: &Output,
+  time_entry: &StopTimeEntry,
+  client: &dyn TogglApi,
+) -> anyhow::Result<bool> {
+  let stop = chrono::Local::now();
+
+  let stopped = match &time_entry.id {
+    Some(id) => stop_time_entry_at(debug, id, stop, client).map(Some),
+    None => stop_current(debug, client),
+  };
+
+  match stopped {
+    Err(err) if is_network_error(&err) => {
+      journal::push(PendingOperation::Stop {
+        stop,
+        workspace: client.workspace().map(|workspace| workspace.to_string()),
+        id: time_entry.id.as_ref().map(|id| id.to_string()),
+      })?;
+
+      println!(
+        "No network connection, the time entry will be stopped at {} with the next command or 'fbtoggl sync'",
+        stop.format("%H:%M:%S")
+      );
+
+      return Ok(true);
+    }
+    Ok(None) => {
+      println!("No timer is running");
+
+      return Ok(false);
+    }
+    result => result?,
+  };
+
+  list(
+    debug,
+    output,
+    &Range::Today,
+    &TimeEntryFilter::default(),
+    None,
+    client,
+  )?;
+
+  Ok(true)
+}
+
+/// Stops the running time entry (if any) with two requests, the workspace
+/// is the one of the time entry
+pub(super) fn stop_current(
+  debug: bool,
+  client: &dyn TogglApi,
+) -> anyhow::Result<Option<TimeEntry>> {
+  let Some(running) = client.get_current_time_entry(debug)? else {
+    return Ok(None);
+  };
+
+  client
+    .stop_time_entry(debug, running.wid, running.id)
+    .map(Some)
+}
+
+/// Seconds between two requests for the running time entry of --watch,
+/// the elapsed time is updated every second in between
+const WATCH_POLL_SECONDS: i64 = 30;
+
+fn watch_current(
+  output: &Output,
+  debug: bool,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let mut running = None;
+  let mut project = None;
+  let mut polled_at = None;
+
+  loop {
+    let now = Local::now();
+
+    if polled_at.is_none_or(|polled_at| {
+      now - polled_at >= Duration::seconds(WATCH_POLL_SECONDS)
+    }) {
+      running = client.get_current_time_entry(debug)?;
+      project = match &running {
+        Some(TimeEntry {
+          wid,
+          pid: Some(pid),
+          ..
+        }) => client
+          .get_workspace_projects(debug, true, *wid)?
+          .into_iter()
+          .find(|project| project.id == *pid)
+          .map(|project| project.name),
+        _ => None,
+      };
+      polled_at = Some(now);
+    }
+
+    // \r and \x1b[2K overwrite the previous line
+    print!(
+      "\r\x1b[2K{}",
+      timer_line(output, running.as_ref(), project.as_deref(), now.into())
+    );
+    std::io::stdout().flush()?;
+
+    std::thread::sleep(std::time::Duration::from_secs(1));
+  }
+}
+
+/// Elapsed time, project and description of the running time entry
+pub(super) fn timer_line(
+  output: &Output,
+  running: Option<&TimeEntry>,
+  project: Option<&str>,
+  now: DateTime<Utc>,
+) -> String {
+  let Some(time_entry) = running else {
+    return "No time entry is running".to_string();
+  };
+
+  let elapsed = (now - time_entry.start).max(Duration::zero()).hhmmss();
+  let elapsed = if output.is_stale(time_entry.start, now) {
+    elapsed.bold().red()
+  } else {
+    elapsed.bold()
+  };
+
+  let description = time_entry
+    .description
+    .as_deref()
+    .filter(|description| !description.is_empty())
+    .map(|description| format!(" - {description}"))
+    .unwrap_or_default();
+
+  format!("{elapsed} {}{description}", project.unwrap_or("-"))
+}
+
+/// Warning about a time entry which is running for longer than
+/// 'stale_timer_hours', it was probably forgotten
+pub(crate) fn stale_warning(
+  output: &Output,
+  time_entry: &TimeEntry,
+  now: DateTime<Utc>,
+) -> Option<String> {
+  if !time_entry.duration.is_negative()
+    || !output.is_stale(time_entry.start, now)
+  {
+    return None;
+  }
+
+  Some(format!(
+    "Time entry {} is running since {} ({}), forgot to stop it?",
+    time_entry.id,
+    DateTime::<Local>::from(time_entry.start).format("%Y-%m-%d %H:%M"),
+    (now - time_entry.start).hhmmss()
+  ))
+}
+
+/// Stops a running time entry, backdated to `stop` if that lies in the past
+pub(crate) fn stop_time_entry_at(
+  debug: bool,
+  id: &TimeEntryId,
+  stop: DateTime<Local>,
+  client: &dyn TogglApi,
+) -> anyhow::Result<TimeEntry> {
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+  let time_entry_id = resolver.time_entry_id(id)?;
+
+  if stop >= Local::now() - Duration::minutes(1) {
+    return client.stop_time_entry(debug, workspace_id, time_entry_id);
+  }
+
+  let mut time_entry = client.get_time_entry(debug, time_entry_id)?;
+
+  if time_entry.stop.is_some() || time_entry.duration >= 0 {
+    return Err(anyhow!("Time entry {time_entry_id} is not running"));
+  }
+
+  time_entry.stop = Some(stop.with_timezone(&chrono::Utc));
+  time_entry.duration = (stop.with_timezone(&chrono::Utc) - time_entry.start)
+    .num_seconds()
+    .max(0);
+
+  client.update_time_entry(debug, &time_entry)
+}
+
+pub fn delete(
+  debug: bool,
+  output: &Output,
+  time_entry: &DeleteTimeEntry,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  if time_entry.interactive {
+    delete_interactive(debug, time_entry, client)?;
+  } else if let Some(id) = &time_entry.id {
+    let time_entry_id = Resolver::new(debug, client).time_entry_id(id)?;
+
+    let running = client
+      .get_current_time_entry(debug)?
+      .filter(|current| current.id == time_entry_id);
+
+    delete_checked(debug, time_entry_id, running.as_ref(), time_entry, client)?;
+  }
+
+  list(
+    debug,
+    output,
+    &Range::Today,
+    &TimeEntryFilter::default(),
+    None,
+    client,
+  )?;
+
+  Ok(())
+}
+
+fn delete_checked(
+  debug: bool,
+  time_entry_id: u64,
+  running: Option<&TimeEntry>,
+  delete_time_entry: &DeleteTimeEntry,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  if let Some(running) = running {
+    let stop_first = stop_before_delete(
+      running,
+      delete_time_entry.force,
+      delete_time_entry.stop_first,
+      Local::now(),
+    )?;
+
+    if stop_first {
+      let stopped = client.stop_time_entry(debug, running.wid, running.id)?;
+
+      eprintln!("Stopped {}", time_entry_summary(&stopped));
+    }
+  }
+
+  client.delete_time_entry(debug, time_entry_id)
+}
+
+/// Deleting a running time entry loses its elapsed time, therefore it
+/// requires --force or --stop-first (returns whether to stop it first)
+pub(super) fn stop_before_delete(
+  running: &TimeEntry,
+  force: bool,
+  stop_first: bool,
+  now: DateTime<Local>,
+) -> anyhow::Result<bool> {
+  match (force, stop_first) {
+    (_, true) => Ok(true),
+    (true, false) => Ok(false),
+    (false, false) => Err(anyhow!(
+      "Time entry {} is running since {} ({}), deleting it loses the elapsed time. Use --force or --stop-first",
+      running.id,
+      DateTime::<Local>::from(running.start).format("%Y-%m-%d %H:%M"),
+      (now - DateTime::<Local>::from(running.start)).hhmmss()
+    )),
+  }
+}
+
+fn delete_interactive(
+  debug: bool,
+  delete_time_entry: &DeleteTimeEntry,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let mut time_entries =
+    client.get_time_entries(debug, &delete_time_entry.range)?;
+  time_entries.sort_by_key(|time_entry| time_entry.start);
+
+  if time_entries.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
+
+  let items = time_entries
+    .iter()
+    .map(time_entry_summary)
+    .collect::<Vec<String>>();
+
+  let selection = MultiSelect::new()
+    .with_prompt(
+      "Select the time entries to delete (space to select, enter to continue)",
+    )
+    .items(&items)
+    .interact()?;
+
+  if selection.is_empty() {
+    println!("Nothing selected!");
+    return Ok(());
+  }
+
+  for index in &selection {
+    println!("{}", items[*index]);
+  }
+
+  if !Confirm::new()
+    .with_prompt(format!("Delete {} time entries?", selection.len()))
+    .default(false)
+    .interact()?
+  {
+    println!("Do nothing!");
+    return Ok(());
+  }
+
+  for index in selection {
+    let time_entry = &time_entries[index];
+    let running = Some(time_entry).filter(|time_entry| time_entry.duration < 0);
+
+    delete_checked(debug, time_entry.id, running, delete_time_entry, client)?;
+  }
+
+  Ok(())
+}
+
+fn time_entry_summary(time_entry: &TimeEntry) -> String {
+  let duration = if time_entry.duration.is_negative() {
+    "running ".to_string()
+  } else {
+    Duration::try_seconds(time_entry.duration)
+      .unwrap_or_default()
+      .hhmmss()
+  };
+
+  format!(
+    "{} {} {} {}",
+    time_entry.id,
+    DateTime::<Local>::from(time_entry.start).format("%Y-%m-%d %H:%M"),
+    duration,
+    time_entry.description.to_owned().unwrap_or_default()
+  )
+}
+
+/// How far back `continue` looks for time entries to select from
+const CONTINUE_DAYS: i64 = 30;
+
+/// Number of project and description pairs `start --recent` selects from
+const RECENT_CHOICES: usize = 10;
+
+pub fn continue_time_entry(
+  debug: bool,
+  output: &Output,
+  continue_time_entry: &ContinueTimeEntry,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let previous = match &continue_time_entry.id {
+    Some(id) => {
+      let time_entry_id = Resolver::new(debug, client).time_entry_id(id)?;
+
+      client.get_time_entry(debug, time_entry_id)?
+    }
+    None => match select_time_entry(
+      debug,
+      continue_time_entry.last,
+      "Select the time entry to continue",
+      client,
+    )? {
+      Some(time_entry) => time_entry,
+      None => {
+        println!("No entries found!");
+        return Ok(());
+      }
+    },
+  };
+
+  let project_id = previous
+    .pid
+    .ok_or_else(|| anyhow!("Time entry {} has no project", previous.id))?;
+
+  let continued = client.start_time_entry(
+    debug,
+    Local::now(),
+    previous.wid,
+    &previous.description,
+    &previous.tags,
+    project_id,
+    previous.task_id,
+    !previous.billable.unwrap_or_default(),
+  )?;
+
+  output_time_entry(output, &continued);
+
+  Ok(())
+}
+
+fn select_time_entry(
+  debug: bool,
+  last: usize,
+  prompt: &str,
+  client: &dyn TogglApi,
+) -> anyhow::Result<Option<TimeEntry>> {
+  let today = Local::now().date_naive();
+
+  let time_entries = client.get_time_entries(
+    debug,
+    &Range::FromTo(today - Duration::days(CONTINUE_DAYS), today),
+  )?;
+
+  let mut candidates = continue_candidates(time_entries, last);
+
+  if candidates.is_empty() {
+    return Ok(None);
+  }
+
+  let mut projects = vec![];
+
+  for workspace_id in candidates.iter().map(|entry| entry.wid).unique() {
+    projects.extend(client.get_workspace_projects(
+      debug,
+      true,
+      workspace_id,
+    )?);
+  }
+
+  let items = candidates
+    .iter()
+    .map(|time_entry| {
+      let project = projects
+        .iter()
+        .find(|project| Some(project.id) == time_entry.pid)
+        .map(|project| project.name.as_str())
+        .unwrap_or("-");
+
+      format!("{project} - {}", time_entry_summary(time_entry))
+    })
+    .collect::<Vec<String>>();
+
+  let selection = Select::new()
+    .with_prompt(prompt)
+    .items(&items)
+    .default(0)
+    .interact()?;
+
+  Ok(Some(candidates.swap_remove(selection)))
+}
+
+/// Last time entries with a project, newest first, entries with the same
+/// project, task and description only once
+pub(super) fn continue_candidates(
+  time_entries: Vec<TimeEntry>,
+  last: usize,
+) -> Vec<TimeEntry> {
+  time_entries
+    .into_iter()
+    .filter(|time_entry| time_entry.pid.is_some())
+    .sorted_by(|a, b| b.start.cmp(&a.start))
+    .unique_by(|time_entry| {
+      (
+        time_entry.pid,
+        time_entry.task_id,
+        time_entry.description.to_owned(),
+      )
+    })
+    .take(last)
+    .collect()
+}
+
+pub fn details(
+  debug: bool,
+  output: &Output,
+  time_entry_details: &TimeEntryDetails,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let time_entry_id =
+    Resolver::new(debug, client).time_entry_id(&time_entry_details.id)?;
+
+  let time_entry = client.get_time_entry(debug, time_entry_id)?;
+  let projects = client.get_workspace_projects(debug, true, time_entry.wid)?;
+
+  let project = time_entry
+    .pid
+    .and_then(|pid| projects.iter().find(|project| project.id == pid));
+
+  let related = if time_entry_details.related {
+    let day =
+      Range::Date(DateTime::<Local>::from(time_entry.start).date_naive());
+    let day_entries = client.get_time_entries(debug, &day)?;
+
+    Some(related_time_entries(&time_entry, &day_entries, Utc::now()))
+  } else {
+    None
+  };
+
+  match output.format {
+    Format::Json => match related {
+      Some(related) => output_values_json(
+        output,
+        &[json!({
+          "time_entry": time_entry,
+          "previous": related.previous,
+          "next": related.next,
+          "overlapping": related.overlapping,
+        })],
+      ),
+      None => output_values_json(output, &[time_entry]),
+    },
+    Format::Raw => {
+      output_time_entry_details_raw(
+        output,
+        &time_entry,
+        project,
+        time_entry_details.verbose,
+      );
+
+      if let Some(related) = related {
+        for (title, time_entries) in related.sections() {
+          println!();
+          println!("{title}:");
+
+          for time_entry in &time_entries {
+            println!("{}", time_entry_summary(time_entry));
+          }
+        }
+      }
+    }
+    Format::Table => {
+      output_time_entry_details_table(
+        output,
+        &time_entry,
+        project,
+        time_entry_details.verbose,
+      );
+
+      if let Some(related) = related {
+        for (title, time_entries) in related.sections() {
+          println!("{}", title.bold());
+          output_time_entries_table(&time_entries);
+        }
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Neighbours of a time entry on its day, see 'details --related'
+#[derive(Debug)]
+pub(super) struct RelatedTimeEntries {
+  pub previous: Option<TimeEntry>,
+  pub next: Option<TimeEntry>,
+  pub overlapping: Vec<TimeEntry>,
+}
+
+impl RelatedTimeEntries {
+  /// Non-empty groups with their title
+  fn sections(&self) -> Vec<(&str, Vec<TimeEntry>)> {
+    [
+      (
+        "Previous",
+        self.previous.iter().cloned().collect::<Vec<_>>(),
+      ),
+      ("Next", self.next.iter().cloned().collect()),
+      ("Overlapping", self.overlapping.clone()),
+    ]
+    .into_iter()
+    .filter(|(_, time_entries)| !time_entries.is_empty())
+    .collect()
+  }
+}
+
+/// Previous and next time entry (by start) of the day and the ones
+/// overlapping `time_entry`, running ones end `now`
+pub(super) fn related_time_entries(
+  time_entry: &TimeEntry,
+  day_entries: &[TimeEntry],
+  now: DateTime<Utc>,
+) -> RelatedTimeEntries {
+  let end = |time_entry: &TimeEntry| {
+    if time_entry.duration.is_negative() {
+      now
+    } else {
+      time_entry.start + Duration::seconds(time_entry.duration)
+    }
+  };
+
+  let others = day_entries
+    .iter()
+    .filter(|other| other.id != time_entry.id)
+    .sorted_by_key(|other| (other.start, other.id))
+    .collect::<Vec<&TimeEntry>>();
+
+  let key = (time_entry.start, time_entry.id);
+
+  RelatedTimeEntries {
+    previous: others
+      .iter()
+      .rev()
+      .find(|other| (other.start, other.id) < key)
+      .map(|other| (*other).clone()),
+    next: others
+      .iter()
+      .find(|other| (other.start, other.id) > key)
+      .map(|other| (*other).clone()),
+    overlapping: others
+      .iter()
+      .filter(|other| {
+        other.start < end(time_entry) && end(other) > time_entry.start
+      })
+      .map(|other| (*other).clone())
+      .collect(),
+  }
+}
+
+pub fn edit(
+  debug: bool,
+  output: &Output,
+  edit_time_entry: &EditTimeEntry,
+  keep_history: bool,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+
+  let time_entry_id = resolver.time_entry_id(&edit_time_entry.id)?;
+  let before = client.get_time_entry(debug, time_entry_id)?;
+  let mut time_entry = before.clone();
+
+  if let Some(project_name) = &edit_time_entry.project {
+    time_entry.pid = Some(resolver.project_id(time_entry.wid, project_name)?);
+  }
+
+  if edit_time_entry.description.is_some() {
+    time_entry.description = expand_description(
+      &edit_time_entry.description,
+      DateTime::<Local>::from(time_entry.start),
+      &output.date_format,
+    )?;
+  }
+
+  if edit_time_entry.tags.is_some() {
+    time_entry.tags = resolver.tags(time_entry.wid, &edit_time_entry.tags)?;
+  }
+
+  time_entry.billable = edited_billable(time_entry.billable, edit_time_entry);
+
+  if let Some(rate) = edit_time_entry.rate {
+    time_entry.rate = Some(rate);
+  }
+
+  let updated_time_entry = client.update_time_entry(debug, &time_entry)?;
+
+  if keep_history && updated_time_entry.description != before.description {
+    history::append(&DescriptionVersion {
+      time_entry_id,
+      changed_at: Local::now(),
+      description: before.description.to_owned(),
+    })?;
+  }
+
+  match output.format {
+    Format::Json => output_values_json(
+      output,
+      &[json!({
+        "before": before,
+        "after": updated_time_entry,
+      })],
+    ),
+    Format::Raw => output_time_entry_raw(output, &updated_time_entry),
+    Format::Table => {
+      output_time_entries_table(std::slice::from_ref(&updated_time_entry))
+    }
+  }
+
+  Ok(())
+}
+
+pub fn split(
+  debug: bool,
+  output: &Output,
+  split_time_entry: &SplitTimeEntry,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let time_entry_id =
+    Resolver::new(debug, client).time_entry_id(&split_time_entry.id)?;
+  let time_entry = client.get_time_entry(debug, time_entry_id)?;
+
+  let Some(project_id) = time_entry.pid else {
+    return Err(anyhow!(
+      "Time entry {} has no project, set one with 'time-entries edit' first",
+      time_entry.id
+    ));
+  };
+
+  let at = split_time(&time_entry, split_time_entry.at, Local::now())?;
+  let (first, remainder) = split_durations(&time_entry, at);
+
+  let shortened = client.update_time_entry(
+    debug,
+    &TimeEntry {
+      duration: first.num_seconds(),
+      stop: Some(at.with_timezone(&Utc)),
+      ..time_entry.clone()
+    },
+  )?;
+
+  let non_billable = !time_entry.billable.unwrap_or_default();
+
+  let created = match remainder {
+    Some(remainder) => client.create_time_entry(
+      debug,
+      &time_entry.description,
+      time_entry.wid,
+      &time_entry.tags,
+      remainder,
+      at,
+      project_id,
+      time_entry.task_id,
+      non_billable,
+    )?,
+    None => client.start_time_entry(
+      debug,
+      at,
+      time_entry.wid,
+      &time_entry.description,
+      &time_entry.tags,
+      project_id,
+      time_entry.task_id,
+      non_billable,
+    )?,
+  };
+
+  let time_entries = [shortened, created];
+
+  match output.format {
+    Format::Json => output_values_json(output, &time_entries),
+    Format::Raw => time_entries
+      .iter()
+      .for_each(|time_entry| output_time_entry_raw(output, time_entry)),
+    Format::Table => output_time_entries_table(&time_entries),
+  }
+
+  Ok(())
+}
+
+/// Point in time of --at on the day the time entry started (or the day
+/// after for time entries running over midnight), it has to lie between
+/// start and end of the time entry
+pub(super) fn split_time(
+  time_entry: &TimeEntry,
+  at: NaiveTime,
+  now: DateTime<Local>,
+) -> anyhow::Result<DateTime<Local>> {
+  let start = DateTime::<Local>::from(time_entry.start);
+  let end = if time_entry.duration.is_negative() {
+    now
+  } else {
+    start + Duration::seconds(time_entry.duration)
+  };
+
+  let split = start
+    .date_naive()
+    .and_time(at)
+    .and_local_timezone(Local)
+    .single()
+    .filter(|split| *split > start)
+    .or_else(|| {
+      (start.date_naive() + Duration::days(1))
+        .and_time(at)
+        .and_local_timezone(Local)
+        .single()
+    })
+    .filter(|split| *split > start && *split < end);
+
+  split.ok_or_else(|| {
+    anyhow!(
+      "{} is not between start '{}' and end '{}' of time entry {}",
+      at.format("%H:%M"),
+      start.format("%Y-%m-%d %H:%M"),
+      end.format("%Y-%m-%d %H:%M"),
+      time_entry.id
+    )
+  })
+}
+
+/// Duration of the shortened time entry and of the remainder, which is
+/// `None` for running time entries (the remainder keeps running)
+pub(super) fn split_durations(
+  time_entry: &TimeEntry,
+  at: DateTime<Local>,
+) -> (Duration, Option<Duration>) {
+  let first = at.with_timezone(&Utc) - time_entry.start;
+
+  let remainder = (!time_entry.duration.is_negative())
+    .then(|| Duration::seconds(time_entry.duration) - first);
+
+  (first, remainder)
+}
+
+pub fn merge(
+  debug: bool,
+  output: &Output,
+  merge: &MergeTimeEntries,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
+
+  let mut time_entries = vec![];
+
+  for id in &merge.ids {
+    let time_entry_id = resolver.time_entry_id(id)?;
+    time_entries.push(client.get_time_entry(debug, time_entry_id)?);
+  }
+
+  let merged = merged_time_entry(&time_entries, merge.tolerance)?;
+  let updated_time_entry = client.update_time_entry(debug, &merged)?;
+
+  for time_entry in &time_entries {
+    if time_entry.id != merged.id {
+      client.delete_time_entry(debug, time_entry.id)?;
+
+      eprintln!("Deleted time entry {}", time_entry.id);
+    }
+  }
+
+  output_time_entry(output, &updated_time_entry);
+
+  Ok(())
+}
+
+/// Earliest of the time entries with the durations of all of them, their
+/// distinct descriptions and all tags. The time entries have to be stopped,
+/// of the same project and follow each other with gaps of at most `tolerance`
+pub(super) fn merged_time_entry(
+  time_entries: &[TimeEntry],
+  tolerance: Duration,
+) -> anyhow::Result<TimeEntry> {
+  let time_entries = time_entries
+    .iter()
+    .unique_by(|time_entry| time_entry.id)
+    .sorted_by_key(|time_entry| time_entry.start)
+    .collect::<Vec<&TimeEntry>>();
+
+  let [first, ..] = time_entries.as_slice() else {
+    return Err(anyhow!("No time entries to merge"));
+  };
+
+  if let Some(running) = time_entries
+    .iter()
+    .find(|time_entry| time_entry.duration.is_negative())
+  {
+    return Err(anyhow!(
+      "Time entry {} is running, stop it first",
+      running.id
+    ));
+  }
+
+  if let Some(other) = time_entries.iter().find(|time_entry| {
+    time_entry.wid != first.wid || time_entry.pid != first.pid
+  }) {
+    return Err(anyhow!(
+      "Time entries {} and {} belong to different projects",
+      first.id,
+      other.id
+    ));
+  }
+
+  for (previous, next) in time_entries.iter().tuple_windows() {
+    let gap =
+      next.start - (previous.start + Duration::seconds(previous.duration));
+
+    if gap > tolerance || gap < -tolerance {
+      return Err(anyhow!(
+        "Time entries {} and {} are not adjacent, {} apart (tolerance {})",
+        previous.id,
+        next.id,
+        gap.abs().hhmmss(),
+        tolerance.hhmmss()
+      ));
+    }
+  }
+
+  // Overlapping parts are only counted once, gaps are not counted at all
+  let (duration, _) = time_entries.iter().fold(
+    (0, first.start),
+    |(duration, covered_until), time_entry| {
+      let stop = time_entry.start + Duration::seconds(time_entry.duration);
+      let start = time_entry.start.max(covered_until);
+
+      if stop > start {
+        (duration + (stop - start).num_seconds(), stop)
+      } else {
+        (duration, covered_until)
+      }
+    },
+  );
+
+  let description = time_entries
+    .iter()
+    .filter_map(|time_entry| time_entry.description.as_deref())
+    .filter(|description| !description.is_empty())
+    .unique()
+    .join("; ");
+
+  let tags = time_entries
+    .iter()
+    .flat_map(|time_entry| time_entry.tags.iter().flatten())
+    .unique()
+    .cloned()
+    .collect::<Vec<String>>();
+
+  Ok(TimeEntry {
+    duration,
+    stop: Some(first.start + Duration::seconds(duration)),
+    description: Some(description)
+      .filter(|description| !description.is_empty()),
+    tags: Some(tags).filter(|tags| !tags.is_empty()),
+    ..(*first).clone()
+  })
 }
 
-pub fn create(
+/// Billable flag after --toggle-billable, --billable or --non-billable
+pub(super) fn edited_billable(
+  billable: Option<bool>,
+  edit_time_entry: &EditTimeEntry,
+) -> Option<bool> {
+  if edit_time_entry.billable {
+    Some(true)
+  } else if edit_time_entry.non_billable {
+    Some(false)
+  } else if edit_time_entry.toggle_billable {
+    Some(!billable.unwrap_or_default())
+  } else {
+    billable
+  }
+}
+
+pub fn bulk_edit(
   debug: bool,
-  format: &Format,
-  time_entry: &CreateTimeEntry,
-  client: &TogglClient,
+  output: &Output,
+  bulk_edit: &BulkEditTimeEntries,
+  non_billable_projects: &[String],
+  client: &dyn TogglApi,
 ) -> anyhow::Result<()> {
-  let me = client.get_me(debug)?;
-  let workspace_id = me.default_workspace_id;
-  let projects = client.get_workspace_projects(debug, false, workspace_id)?;
+  let resolver = Resolver::new(debug, client);
+  let workspace_id = resolver.workspace_id()?;
+
+  let set_project_id = bulk_edit
+    .set_project
+    .as_ref()
+    .map(|project| resolver.project_id(workspace_id, project))
+    .transpose()?;
+  let add_tags = resolver
+    .tags(workspace_id, &Some(bulk_edit.add_tag.to_owned()))?
+    .unwrap_or_default();
+
+  let time_entries = matching_time_entries(
+    debug,
+    &bulk_edit.range,
+    &bulk_edit.project,
+    &bulk_edit.filter_tag,
+    &resolver,
+    client,
+  )?;
 
-  let project = projects
-    .iter()
-    .find(|project| project.name == time_entry.project)
-    .ok_or_else(|| {
-      anyhow!(format!("Cannot find project='{}'", time_entry.project))
-    })?;
+  if time_entries.is_empty() {
+    println!("No entries found!");
+    return Ok(());
+  }
 
-  let duration = calculate_duration(time_entry)?;
+  if !bulk_edit.yes
+    && !Confirm::new()
+      .with_prompt(format!("Change {} time entries?", time_entries.len()))
+      .default(false)
+      .interact()?
+  {
+    println!("Do nothing!");
+    return Ok(());
+  }
 
-  if time_entry.lunch_break {
-    let start = time_entry.start;
-    let duration = duration.div(2);
+  let billable = if bulk_edit.set_billable {
+    Some(true)
+  } else if bulk_edit.set_non_billable {
+    Some(false)
+  } else {
+    None
+  };
+
+  // time entries ending up in one of 'non_billable_projects' are never billable
+  let projects = if !non_billable_projects.is_empty()
+    && (billable.is_some() || set_project_id.is_some())
+  {
+    client.get_workspace_projects(debug, true, workspace_id)?
+  } else {
+    vec![]
+  };
+
+  let (non_billable_ids, ids): (Vec<u64>, Vec<u64>) =
+    time_entries.iter().partition_map(|time_entry| {
+      let non_billable = projects
+        .iter()
+        .find(|project| Some(project.id) == set_project_id.or(time_entry.pid))
+        .is_some_and(|project| {
+          is_non_billable_project(non_billable_projects, project)
+        });
+
+      if non_billable {
+        Either::Left(time_entry.id)
+      } else {
+        Either::Right(time_entry.id)
+      }
+    });
 
-    client.create_time_entry(
-      debug,
-      &time_entry.description,
-      workspace_id,
-      &time_entry.tags,
-      duration,
-      start,
-      project.id,
-      time_entry.non_billable,
-    )?;
+  // moved into a non-billable project they become non-billable even without
+  // --set-billable/--set-non-billable
+  let non_billable =
+    (billable.is_some() || set_project_id.is_some()).then_some(false);
 
-    let new_start = start + launch_break() + duration;
+  let mut result = BulkEditResult::default();
 
-    client.create_time_entry(
-      debug,
-      &time_entry.description,
-      workspace_id,
-      &time_entry.tags,
-      duration,
-      new_start,
-      project.id,
-      time_entry.non_billable,
-    )?;
-  } else {
-    client.create_time_entry(
-      debug,
-      &time_entry.description,
-      workspace_id,
-      &time_entry.tags,
-      duration,
-      time_entry.start,
-      project.id,
-      time_entry.non_billable,
-    )?;
+  for (ids, billable) in [(ids, billable), (non_billable_ids, non_billable)] {
+    let operations = bulk_edit_operations(
+      set_project_id,
+      &add_tags,
+      &bulk_edit.remove_tag,
+      billable,
+    );
+
+    if ids.is_empty() || operations.is_empty() {
+      continue;
+    }
+
+    let changed =
+      client.bulk_edit_time_entries(debug, workspace_id, &ids, &operations)?;
+
+    result.success.extend(changed.success);
+    result.failure.extend(changed.failure);
+  }
+
+  match output.format {
+    Format::Json => output_values_json(output, &[&result]),
+    Format::Raw | Format::Table => {
+      println!("Changed {} time entries", result.success.len());
+
+      for failure in &result.failure {
+        let line = format!("Time entry {}: {}", failure.id, failure.message);
+
+        if output.format == Format::Table {
+          println!("{}", line.red());
+        } else {
+          println!("{line}");
+        }
+      }
+    }
   }
 
-  list(debug, format, &Range::Today, false, client)?;
+  if !result.failure.is_empty() {
+    return Err(anyhow!(
+      "{} time entries could not be changed",
+      result.failure.len()
+    ));
+  }
 
   Ok(())
 }
 
-fn launch_break() -> Duration {
-  Duration::try_hours(1).unwrap()
+/// Time entries of the selected workspace in the range, only those of the
+/// project and with any of the tags if given
+fn matching_time_entries(
+  debug: bool,
+  range: &Range,
+  project: &Option<String>,
+  tags: &[String],
+  resolver: &Resolver,
+  client: &dyn TogglApi,
+) -> anyhow::Result<Vec<TimeEntry>> {
+  let workspace_id = resolver.workspace_id()?;
+
+  let project_id = project
+    .as_ref()
+    .map(|project| resolver.any_project_id(workspace_id, project))
+    .transpose()?;
+
+  Ok(
+    client
+      .get_time_entries(debug, range)?
+      .into_iter()
+      .filter(|time_entry| time_entry.wid == workspace_id)
+      .filter(|time_entry| project_id.is_none() || time_entry.pid == project_id)
+      .filter(|time_entry| tags.is_empty() || has_any_tag(time_entry, tags))
+      .collect(),
+  )
 }
 
-pub(super) fn calculate_duration(
-  time_entry: &CreateTimeEntry,
-) -> anyhow::Result<Duration> {
-  if let Some(end) = time_entry.end {
-    let start = time_entry.start;
+/// Pause between two deletions, Toggl asks for about one request per second
+const BULK_DELETE_PAUSE: std::time::Duration =
+  std::time::Duration::from_secs(1);
 
-    if start >= end {
-      return Err(anyhow!(
-        "start='{}' is greater or equal than end='{}'",
-        start,
-        end
-      ));
-    }
+pub fn bulk_delete(
+  debug: bool,
+  output: &Output,
+  bulk_delete: &BulkDeleteTimeEntries,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  let resolver = Resolver::new(debug, client);
 
-    let duration = end - start;
+  let mut time_entries = matching_time_entries(
+    debug,
+    &bulk_delete.range,
+    &bulk_delete.project,
+    &bulk_delete.filter_tag,
+    &resolver,
+    client,
+  )?
+  .into_iter()
+  // deleting a running time entry loses its elapsed time
+  .filter(|time_entry| time_entry.duration >= 0)
+  .sorted_by_key(|time_entry| time_entry.start)
+  .collect::<Vec<TimeEntry>>();
+
+  if bulk_delete.duplicates {
+    time_entries = duplicates(&time_entries);
+  }
 
-    if time_entry.lunch_break {
-      calculate_duration_with_lunch_break(duration)
-    } else {
-      Ok(duration)
-    }
-  } else {
-    time_entry
-      .duration
-      .ok_or_else(|| anyhow!("Please use either --duration or --end"))
+  if time_entries.is_empty() {
+    println!("No entries found!");
+    return Ok(());
   }
-}
 
-fn calculate_duration_with_lunch_break(
-  duration: Duration,
-) -> anyhow::Result<Duration> {
-  let duration_with_lunch_break = duration - launch_break();
+  for time_entry in &time_entries {
+    println!("{}", time_entry_summary(time_entry));
+  }
 
-  if duration_with_lunch_break <= Duration::zero() {
-    Err(anyhow!("Duration minus lunch break is <= 0"))
+  if !bulk_delete.yes
+    && !Confirm::new()
+      .with_prompt(format!("Delete {} time entries?", time_entries.len()))
+      .default(false)
+      .interact()?
+  {
+    println!("Do nothing!");
+    return Ok(());
+  }
+
+  // events replace the bar with --progress json
+  let progress_bar = if progress::is_json() {
+    ProgressBar::hidden()
   } else {
-    Ok(duration_with_lunch_break)
+    ProgressBar::new(time_entries.len() as u64).with_style(
+      ProgressStyle::with_template("{bar:40} {pos}/{len} deleted")?,
+    )
+  };
+
+  for (index, time_entry) in time_entries.iter().enumerate() {
+    if index > 0 {
+      std::thread::sleep(BULK_DELETE_PAUSE);
+    }
+
+    client
+      .delete_time_entry(debug, time_entry.id)
+      .map_err(|err| {
+        anyhow!(
+          "Deleted {index} of {} time entries, then failed: {err}",
+          time_entries.len()
+        )
+      })?;
+
+    progress_bar.inc(1);
+    progress::emit(&ProgressEvent::Progress {
+      operation: "bulk-delete".to_string(),
+      position: index as u64 + 1,
+      length: time_entries.len() as u64,
+    });
   }
-}
 
-pub fn start(
-  debug: bool,
-  format: &Format,
-  time_entry: &StartTimeEntry,
-  client: &TogglClient,
-) -> anyhow::Result<()> {
-  let me = client.get_me(debug)?;
-  let workspace_id = me.default_workspace_id;
-  let projects = client.get_workspace_projects(debug, false, workspace_id)?;
+  progress_bar.finish_and_clear();
+  progress::emit(&ProgressEvent::Done {
+    operation: "bulk-delete".to_string(),
+  });
+
+  match output.format {
+    Format::Json => output_values_json(output, &time_entries),
+    Format::Raw | Format::Table => {
+      println!("Deleted {} time entries", time_entries.len())
+    }
+  }
 
-  let project = projects
+  Ok(())
+}
+
+/// Time entries with the same start, duration, project and description as
+/// an earlier created one (lower id), which is kept
+pub(super) fn duplicates(time_entries: &[TimeEntry]) -> Vec<TimeEntry> {
+  time_entries
     .iter()
-    .find(|project| project.name == time_entry.project)
-    .ok_or_else(|| {
-      anyhow!(format!("Cannot find project='{}'", time_entry.project))
-    })?;
+    .sorted_by_key(|time_entry| time_entry.id)
+    .into_group_map_by(|time_entry| {
+      (
+        time_entry.start,
+        time_entry.duration,
+        time_entry.pid,
+        time_entry.description.to_owned(),
+      )
+    })
+    .into_values()
+    .flat_map(|group| group.into_iter().skip(1))
+    .sorted_by_key(|time_entry| (time_entry.start, time_entry.id))
+    .cloned()
+    .collect()
+}
 
-  let started_time_entry = client.start_time_entry(
-    debug,
-    chrono::Local::now(),
-    workspace_id,
-    &time_entry.description,
-    &time_entry.tags,
-    project.id,
-    time_entry.non_billable,
-  )?;
+/// JSON Patch operations of the bulk edit, without any change there is
+/// nothing to send
+pub(super) fn bulk_edit_operations(
+  project_id: Option<u64>,
+  add_tags: &[String],
+  remove_tags: &[String],
+  billable: Option<bool>,
+) -> Vec<PatchOperation> {
+  let mut operations = vec![];
+
+  if let Some(project_id) = project_id {
+    operations.push(PatchOperation {
+      op: "replace".to_string(),
+      path: "/project_id".to_string(),
+      value: json!(project_id),
+    });
+  }
 
-  match format {
-    Format::Json => output_values_json(&[started_time_entry]),
-    Format::Raw => output_time_entry_raw(&started_time_entry),
-    Format::Table => output_time_entry_table(&started_time_entry),
+  if !add_tags.is_empty() {
+    operations.push(PatchOperation {
+      op: "add".to_string(),
+      path: "/tags".to_string(),
+      value: json!(add_tags),
+    });
   }
 
-  Ok(())
+  if !remove_tags.is_empty() {
+    operations.push(PatchOperation {
+      op: "remove".to_string(),
+      path: "/tags".to_string(),
+      value: json!(remove_tags),
+    });
+  }
+
+  if let Some(billable) = billable {
+    operations.push(PatchOperation {
+      op: "replace".to_string(),
+      path: "/billable".to_string(),
+      value: json!(billable),
+    });
+  }
+
+  operations
 }
 
-pub fn stop(
-  debug: bool,
-  format: &Format,
-  time_entry: &StopTimeEntry,
-  client: &TogglClient,
-) -> anyhow::Result<()> {
-  let me = client.get_me(debug)?;
-  let workspace_id = me.default_workspace_id;
+fn formatted_rate(rate: Option<f64>, project: Option<&Project>) -> String {
+  let currency = project
+    .and_then(|project| project.currency.to_owned())
+    .unwrap_or_default();
 
-  client.stop_time_entry(debug, workspace_id, time_entry.id)?;
+  rate
+    .map(|rate| format!("{rate:.2} {currency}").trim_end().to_string())
+    .unwrap_or_else(|| "-".to_string())
+}
 
-  list(debug, format, &Range::Today, false, client)?;
+fn time_entry_details(
+  output: &Output,
+  time_entry: &TimeEntry,
+  project: Option<&Project>,
+  verbose: bool,
+) -> Vec<(&'static str, String)> {
+  let duration = if time_entry.duration.is_negative() {
+    "running".to_string()
+  } else {
+    Duration::try_seconds(time_entry.duration)
+      .unwrap_or_default()
+      .hhmmss()
+  };
+
+  let rate = match time_entry.rate {
+    Some(_) => format!(
+      "{} (entry)",
+      formatted_rate(time_entry.effective_rate(project), project)
+    ),
+    None => formatted_rate(time_entry.effective_rate(project), project),
+  };
+
+  let mut details = vec![
+    ("Id", time_entry.id.to_string()),
+    (
+      "Project",
+      project
+        .map(|project| project.name.to_owned())
+        .unwrap_or_else(|| "-".to_string()),
+    ),
+    (
+      "Description",
+      time_entry.description.to_owned().unwrap_or_default(),
+    ),
+    ("Start", output.format_timestamp(&time_entry.start)),
+    (
+      "Stop",
+      time_entry
+        .stop
+        .map(|stop| output.format_timestamp(&stop))
+        .unwrap_or_else(|| "-".to_string()),
+    ),
+    ("Duration", duration),
+    (
+      "Tags",
+      time_entry
+        .tags
+        .as_ref()
+        .map(|tags| tags.join(", "))
+        .unwrap_or_default(),
+    ),
+    (
+      "Billable",
+      format_billable(
+        time_entry.billable.unwrap_or_default(),
+        output.billable_marker(BillableMarker::Words),
+      ),
+    ),
+    ("Rate", rate),
+    (
+      "Amount",
+      formatted_rate(time_entry.amount(project), project),
+    ),
+  ];
+
+  if verbose {
+    details.insert(
+      1,
+      (
+        "GUID",
+        time_entry
+          .guid
+          .to_owned()
+          .unwrap_or_else(|| "-".to_string()),
+      ),
+    );
+  }
 
-  Ok(())
+  details
 }
 
-pub fn delete(
-  debug: bool,
-  format: &Format,
-  time_entry: &DeleteTimeEntry,
-  client: &TogglClient,
-) -> anyhow::Result<()> {
-  client.delete_time_entry(debug, time_entry.id)?;
+fn output_time_entry_details_raw(
+  output: &Output,
+  time_entry: &TimeEntry,
+  project: Option<&Project>,
+  verbose: bool,
+) {
+  for (key, value) in time_entry_details(output, time_entry, project, verbose) {
+    println!("{key}\t{value}");
+  }
+}
 
-  list(debug, format, &Range::Today, false, client)?;
+fn output_time_entry_details_table(
+  output: &Output,
+  time_entry: &TimeEntry,
+  project: Option<&Project>,
+  verbose: bool,
+) {
+  let mut table = Table::new();
+  table.style = TableStyle::thin();
+  table.separate_rows = false;
 
-  Ok(())
+  for (key, value) in time_entry_details(output, time_entry, project, verbose) {
+    table.add_row(Row::new(vec![
+      TableCell::new(key.bold()),
+      TableCell::new(value),
+    ]));
+  }
+
+  println!("{}", table.render());
+}
+
+pub(super) fn output_time_entry(output: &Output, time_entry: &TimeEntry) {
+  match output.format {
+    Format::Json => {
+      output_values_json(output, std::slice::from_ref(time_entry))
+    }
+    Format::Raw => output_time_entry_raw(output, time_entry),
+    Format::Table => {
+      output_time_entries_table(std::slice::from_ref(time_entry))
+    }
+  }
 }
 
-fn output_time_entry_raw(time_entry: &TimeEntry) {
+fn output_time_entry_raw(output: &Output, time_entry: &TimeEntry) {
   println!(
     "{}\t{}\t{}\t{}",
     &time_entry.id,
-    &time_entry.start,
+    output.format_timestamp(&time_entry.start),
     &time_entry.description.to_owned().unwrap_or_default(),
     &time_entry
       .tags
@@ -344,7 +2895,7 @@ fn output_time_entry_raw(time_entry: &TimeEntry) {
   );
 }
 
-fn output_time_entry_table(time_entry: &TimeEntry) {
+fn output_time_entries_table(time_entries: &[TimeEntry]) {
   let mut table = Table::new();
   table.style = TableStyle::thin();
   table.separate_rows = false;
@@ -358,47 +2909,79 @@ fn output_time_entry_table(time_entry: &TimeEntry) {
 
   table.add_row(header);
 
-  table.add_row(Row::new(vec![
-    TableCell::new(time_entry.id),
-    TableCell::new(time_entry.start),
-    TableCell::new(time_entry.description.to_owned().unwrap_or_default()),
-    TableCell::new(
-      time_entry
-        .tags
-        .as_ref()
-        .map(|tags| tags.join(", "))
-        .unwrap_or_default(),
-    ),
-  ]));
+  for time_entry in time_entries {
+    table.add_row(Row::new(vec![
+      TableCell::new(time_entry.id),
+      TableCell::new(time_entry.start),
+      TableCell::new(time_entry.description.to_owned().unwrap_or_default()),
+      TableCell::new(
+        time_entry
+          .tags
+          .as_ref()
+          .map(|tags| tags.join(", "))
+          .unwrap_or_default(),
+      ),
+    ]));
+  }
 
   println!("{}", table.render());
 }
 
-fn output_missing_days_table(missing_datetimes: &[DateTime<Local>]) {
+fn output_missing_days_table(
+  output: &Output,
+  missing_days: &[MissingDay],
+  expected: Option<Duration>,
+) {
   let mut table = Table::new();
   table.style = TableStyle::thin();
   table.separate_rows = false;
 
-  let header = Row::new(vec![TableCell::new("Date".bold().underline())]);
+  let mut header = vec![TableCell::new("Date".bold().underline())];
 
-  table.add_row(header);
+  if expected.is_some() {
+    header.push(TableCell::new("Logged".bold().underline()));
+  }
+
+  table.add_row(Row::new(header));
+
+  for missing_day in missing_days {
+    let mut row = vec![TableCell::new(
+      output.format_date(&missing_day.date.date_naive()),
+    )];
+
+    if expected.is_some() {
+      row.push(
+        TableCell::builder(missing_day.logged.hhmmss())
+          .alignment(Alignment::Right)
+          .build(),
+      );
+    }
 
-  for missing_datetime in missing_datetimes {
-    table.add_row(Row::new(vec![TableCell::new(
-      missing_datetime.date_naive(),
-    )]));
+    table.add_row(Row::new(row));
   }
 
   println!("{}", table.render());
 }
 
-fn output_missing_days_raw(missing_datetimes: &[DateTime<Local>]) {
-  for missing_datetime in missing_datetimes {
-    println!("{}", missing_datetime.date_naive());
+fn output_missing_days_raw(
+  output: &Output,
+  missing_days: &[MissingDay],
+  expected: Option<Duration>,
+) {
+  for missing_day in missing_days {
+    if expected.is_some() {
+      println!(
+        "{} {}",
+        output.format_date(&missing_day.date.date_naive()),
+        missing_day.logged.hhmmss()
+      );
+    } else {
+      println!("{}", output.format_date(&missing_day.date.date_naive()));
+    }
   }
 }
 
-fn output_values_raw(output_entries: &[OutputEntry]) {
+fn output_values_raw(output: &Output, output_entries: &[OutputEntry]) {
   for entry in output_entries {
     let duration_text = if entry.duration.is_zero() {
       "running ".to_string()
@@ -408,23 +2991,266 @@ fn output_values_raw(output_entries: &[OutputEntry]) {
 
     println!(
       "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
-      &entry.date,
+      output.format_date(&entry.date),
       duration_text,
       &entry.id,
       &entry.workspace,
       &entry.project,
       &entry.client,
       &entry.description,
-      if entry.billable {
-        "BILLABLE"
-      } else {
-        "NON_BILLABLE"
-      }
+      format_billable(
+        entry.billable,
+        output.billable_marker(BillableMarker::Words)
+      )
     );
   }
 }
 
-fn output_values_table(output_entries: &[OutputEntry]) {
+fn billable_cell(output: &Output, billable: bool) -> ColoredString {
+  match output.billable_marker(BillableMarker::Symbol) {
+    BillableMarker::Symbol if billable => "$".bold().green(),
+    BillableMarker::Symbol => "$".bold().red(),
+    marker => format_billable(billable, marker).normal(),
+  }
+}
+
+// Below this terminal width the workspace column is dropped
+const NARROW_TABLE_WIDTH: usize = 120;
+
+// Descriptions are never shortened below this width
+const MIN_DESCRIPTION_WIDTH: usize = 10;
+
+struct EntriesTableLayout {
+  show_workspace: bool,
+  description_width: Option<usize>,
+}
+
+impl EntriesTableLayout {
+  fn row(&self, mut cells: Vec<TableCell>) -> Row {
+    if !self.show_workspace && cells.len() > 3 {
+      cells.remove(3);
+    }
+
+    Row::new(cells)
+  }
+
+  fn description(&self, description: &str) -> String {
+    match self.description_width {
+      Some(width) => truncate(description, width),
+      None => description.to_string(),
+    }
+  }
+}
+
+fn entries_table_layout(
+  output_entries: &[OutputEntry],
+  width: Option<usize>,
+) -> EntriesTableLayout {
+  let Some(width) = width else {
+    return EntriesTableLayout {
+      show_workspace: true,
+      description_width: None,
+    };
+  };
+
+  let show_workspace = width >= NARROW_TABLE_WIDTH;
+
+  let column_width = |header: &str, values: Vec<usize>| {
+    values
+      .into_iter()
+      .max()
+      .unwrap_or_default()
+      .max(header.len())
+  };
+
+  let mut other_columns = vec![
+    // Date
+    10,
+    // Time
+    8,
+    column_width(
+      "Id",
+      output_entries
+        .iter()
+        .map(|e| e.id.to_string().len())
+        .collect(),
+    ),
+    column_width(
+      "Project",
+      output_entries
+        .iter()
+        .map(|e| e.project.chars().count())
+        .collect(),
+    ),
+    column_width(
+      "Customer",
+      output_entries
+        .iter()
+        .map(|e| e.client.chars().count())
+        .collect(),
+    ),
+    // Billable
+    8,
+  ];
+
+  if show_workspace {
+    other_columns.push(column_width(
+      "Workspace",
+      output_entries
+        .iter()
+        .map(|e| e.workspace.chars().count())
+        .collect(),
+    ));
+  }
+
+  // every column is padded by a space on both sides and followed by a border
+  let columns = other_columns.len() + 1;
+  let used_width = other_columns.iter().sum::<usize>() + columns * 3 + 1;
+
+  EntriesTableLayout {
+    show_workspace,
+    description_width: Some(
+      width.saturating_sub(used_width).max(MIN_DESCRIPTION_WIDTH),
+    ),
+  }
+}
+
+fn output_month_table(output: &Output, days: &[(NaiveDate, Duration)]) {
+  let mut table = Table::new();
+  table.style = TableStyle::thin();
+
+  // any week from Monday to Sunday
+  let monday = NaiveDate::from_isoywd_opt(2024, 1, Weekday::Mon).unwrap();
+
+  table.add_row(Row::new(monday.iter_days().take(7).map(|day| {
+    TableCell::new(output.date_format.short_weekday(&day).bold().underline())
+  })));
+
+  let colored = |text: String, logged: Duration| match heat(logged) {
+    Heat::Nothing => text.dimmed(),
+    Heat::Below4Hours => text.black().on_yellow(),
+    Heat::From4To8Hours => text.black().on_green(),
+    Heat::Above8Hours => text.white().on_red(),
+  };
+
+  for week in calendar_weeks(days) {
+    table.add_row(Row::new(week.iter().map(|day| {
+      let Some((date, logged)) = day else {
+        return TableCell::new("");
+      };
+
+      // colored per line, the cell is split at the line break
+      TableCell::new(format!(
+        "{}\n{}",
+        colored(format!("{:>5}", date.day()), *logged),
+        colored(
+          format!(
+            "{:>5}",
+            format!("{}:{:02}", logged.num_hours(), logged.num_minutes() % 60)
+          ),
+          *logged
+        )
+      ))
+    })));
+  }
+
+  let total = days
+    .iter()
+    .fold(Duration::zero(), |sum, (_, logged)| sum + *logged);
+
+  println!("{}", table.render());
+  println!(
+    "Total: {}   {} {} {} {}",
+    total.hhmmss().bold(),
+    "0h".dimmed(),
+    "<4h".black().on_yellow(),
+    "4-8h".black().on_green(),
+    ">8h".white().on_red()
+  );
+}
+
+fn output_week_table(
+  output: &Output,
+  monday: NaiveDate,
+  output_entries: &[OutputEntry],
+) {
+  let days = (0..7)
+    .map(|offset| monday + Duration::days(offset))
+    .collect::<Vec<NaiveDate>>();
+
+  let columns = days
+    .iter()
+    .map(|day| {
+      output_entries
+        .iter()
+        .filter(|entry| entry.start.date_naive() == *day)
+        .collect::<Vec<&OutputEntry>>()
+    })
+    .collect::<Vec<Vec<&OutputEntry>>>();
+
+  // every column is padded by a space on both sides and followed by a border
+  let cell_width = output
+    .table_width()
+    .map(|width| (width.saturating_sub(1) / days.len()).saturating_sub(3))
+    .map(|width| width.max(MIN_DESCRIPTION_WIDTH));
+
+  let shorten = |text: &str| match cell_width {
+    Some(width) => truncate(text, width),
+    None => text.to_string(),
+  };
+
+  let mut table = Table::new();
+  table.style = TableStyle::thin();
+
+  table.add_row(Row::new(days.iter().map(|day| {
+    // colored per line, the cell is split at the line break
+    TableCell::new(format!(
+      "{}\n{}",
+      output.date_format.weekday(day).bold().underline(),
+      output.format_date(day).bold().underline()
+    ))
+  })));
+
+  let rows = columns.iter().map(Vec::len).max().unwrap_or_default();
+
+  for index in 0..rows {
+    table.add_row(Row::new(columns.iter().map(|entries| {
+      let Some(entry) = entries.get(index) else {
+        return TableCell::new("");
+      };
+
+      let duration_text = if entry.stale {
+        "running".italic().red()
+      } else if entry.duration.is_zero() {
+        "running".italic()
+      } else {
+        entry.duration.hhmmss().italic()
+      };
+
+      TableCell::new(format!(
+        "{} {}\n{}\n{}",
+        entry.start.format("%H:%M"),
+        duration_text,
+        shorten(&entry.project),
+        shorten(&entry.description)
+      ))
+    })));
+  }
+
+  table.add_row(Row::new(columns.iter().map(|entries| {
+    let total = entries
+      .iter()
+      .fold(Duration::zero(), |sum, entry| sum + entry.duration);
+
+    TableCell::builder(total.hhmmss().bold())
+      .alignment(Alignment::Right)
+      .build()
+  })));
+
+  println!("{}", table.render());
+}
+
+fn output_values_table(output: &Output, output_entries: &[OutputEntry]) {
   let time_entry_buckets = output_entries
     .iter()
     .chunk_by(|e| &e.date)
@@ -433,11 +3259,14 @@ fn output_values_table(output_entries: &[OutputEntry]) {
     .collect::<Vec<(&NaiveDate, Vec<&OutputEntry>)>>();
 
   if !time_entry_buckets.is_empty() {
+    let layout = entries_table_layout(output_entries, output.table_width());
+    let row = |cells: Vec<TableCell>| layout.row(cells);
+
     let mut table = Table::new();
     table.style = TableStyle::thin();
     table.separate_rows = false;
 
-    let header = Row::new(vec![
+    let header = row(vec![
       TableCell::new("Date".bold().underline()),
       TableCell::new("Time".bold().underline()),
       TableCell::new("Id".bold().underline()),
@@ -450,7 +3279,7 @@ fn output_values_table(output_entries: &[OutputEntry]) {
 
     table.add_row(header);
 
-    table.add_row(Row::new(vec![
+    table.add_row(row(vec![
       TableCell::new(""),
       TableCell::new(""),
       TableCell::new(""),
@@ -469,8 +3298,8 @@ fn output_values_table(output_entries: &[OutputEntry]) {
 
       total_time_sum += time_sum;
 
-      let date_row = Row::new(vec![
-        TableCell::new(date.to_string().bold()),
+      let date_row = row(vec![
+        TableCell::new(output.format_date(date).bold()),
         TableCell::new(
           Duration::try_seconds(time_sum)
             .unwrap_or_default()
@@ -488,35 +3317,33 @@ fn output_values_table(output_entries: &[OutputEntry]) {
       table.add_row(date_row);
 
       for entry in entries {
-        let duration_text = if entry.duration.is_zero() {
+        let duration_text = if entry.stale {
+          "running".italic().red()
+        } else if entry.duration.is_zero() {
           "running".italic()
         } else {
           entry.duration.hhmmss().italic()
         };
 
-        let entry_row = Row::new(vec![
+        let entry_row = row(vec![
           TableCell::new(""),
           TableCell::new(duration_text),
           TableCell::new(entry.id),
           TableCell::new(&entry.workspace),
           TableCell::new(&entry.project),
           TableCell::new(&entry.client),
-          TableCell::new(&entry.description),
-          TableCell::builder(if entry.billable {
-            "$".bold().green()
-          } else {
-            "$".bold().red()
-          })
-          .col_span(1)
-          .alignment(Alignment::Center)
-          .build(),
+          TableCell::new(layout.description(&entry.description)),
+          TableCell::builder(billable_cell(output, entry.billable))
+            .col_span(1)
+            .alignment(Alignment::Center)
+            .build(),
         ]);
 
         table.add_row(entry_row);
       }
     }
 
-    table.add_row(Row::new(vec![
+    table.add_row(row(vec![
       TableCell::new(""),
       TableCell::new(""),
       TableCell::new(""),
@@ -525,7 +3352,7 @@ fn output_values_table(output_entries: &[OutputEntry]) {
       TableCell::new(""),
     ]));
 
-    let total_sum_row = Row::new(vec![
+    let total_sum_row = row(vec![
       TableCell::new("Total".bold()),
       TableCell::new(
         Duration::try_seconds(total_time_sum)
@@ -1,10 +1,43 @@
 use crate::{
-  cli::CreateTimeEntry,
+  cli::{
+    BulkEditTimeEntries, CreateTimeEntry, CurrentTimeEntry, EditTimeEntry,
+    Format, ListTimeEntries, Output,
+  },
   client::{TogglClient, CREATED_WITH},
+  commands::time_entries::bulk_edit,
+  commands::time_entries::bulk_edit_operations,
   commands::time_entries::calculate_duration,
+  commands::time_entries::calendar_weeks,
+  commands::time_entries::chained_start,
+  commands::time_entries::continue_candidates,
   commands::time_entries::create,
+  commands::time_entries::current,
+  commands::time_entries::duplicates,
+  commands::time_entries::edit,
+  commands::time_entries::edited_billable,
+  commands::time_entries::has_any_tag,
+  commands::time_entries::heat,
+  commands::time_entries::logged_by_date,
+  commands::time_entries::merged_time_entry,
+  commands::time_entries::missing_days,
+  commands::time_entries::notification_command,
+  commands::time_entries::overlaps,
+  commands::time_entries::parse_import,
+  commands::time_entries::parse_toggl_csv,
+  commands::time_entries::related_time_entries,
+  commands::time_entries::split_by_days,
+  commands::time_entries::split_durations,
+  commands::time_entries::split_time,
+  commands::time_entries::stop_before_delete,
+  commands::time_entries::stop_current,
+  commands::time_entries::timer_line,
+  commands::time_entries::week_start,
+  commands::time_entries::Heat,
+  commands::time_entries::Overlap,
+  commands::time_entries::TimeEntryFilter,
+  model::{Range, TimeEntry, TimeEntryId},
 };
-use chrono::{DateTime, Duration, Local};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone};
 use mockito::Matcher;
 use pretty_assertions::assert_eq;
 use serde_json::{json, Value};
@@ -27,14 +60,22 @@ fn teardown() {
 #[test]
 fn test_calculate_duration() -> anyhow::Result<()> {
   let time_entry_with_duration_but_without_end = CreateTimeEntry {
-    project: "fkbr".to_string(),
+    project: Some("fkbr".to_string()),
+    task: None,
     start: DateTime::<Local>::from_str("2021-11-21T22:58:09Z")?,
     end: None,
     duration: Some(Duration::try_hours(2).unwrap()),
     non_billable: false,
+    billable: false,
+    no_default_tags: false,
     lunch_break: false,
+    split_by_days: false,
     description: None,
     tags: None,
+    from_json: None,
+    from_toggl_csv: None,
+    create_missing_project: false,
+    client: None,
   };
 
   assert_eq!(
@@ -43,14 +84,22 @@ fn test_calculate_duration() -> anyhow::Result<()> {
   );
 
   let time_entry_without_duration_but_with_end = CreateTimeEntry {
-    project: "fkbr".to_string(),
+    project: Some("fkbr".to_string()),
+    task: None,
     start: DateTime::<Local>::from_str("2021-11-21T10:58:09Z")?,
     end: Some(DateTime::<Local>::from_str("2021-11-21T12:58:09Z")?),
     duration: None,
     non_billable: false,
+    billable: false,
+    no_default_tags: false,
     lunch_break: false,
+    split_by_days: false,
     description: None,
     tags: None,
+    from_json: None,
+    from_toggl_csv: None,
+    create_missing_project: false,
+    client: None,
   };
 
   assert_eq!(
@@ -59,14 +108,22 @@ fn test_calculate_duration() -> anyhow::Result<()> {
   );
 
   let time_entry_without_duration_and_without_end = CreateTimeEntry {
-    project: "fkbr".to_string(),
+    project: Some("fkbr".to_string()),
+    task: None,
     start: DateTime::<Local>::from_str("2021-11-21T10:58:09Z")?,
     end: None,
     duration: None,
     non_billable: false,
+    billable: false,
+    no_default_tags: false,
     lunch_break: false,
+    split_by_days: false,
     description: None,
     tags: None,
+    from_json: None,
+    from_toggl_csv: None,
+    create_missing_project: false,
+    client: None,
   };
 
   assert_eq!(
@@ -78,14 +135,22 @@ fn test_calculate_duration() -> anyhow::Result<()> {
 
   let time_entry_with_duration_but_without_end_and_lunch_break =
     CreateTimeEntry {
-      project: "fkbr".to_string(),
+      project: Some("fkbr".to_string()),
+      task: None,
       start: DateTime::<Local>::from_str("2021-11-21T10:58:09Z")?,
       end: Some(DateTime::<Local>::from_str("2021-11-21T12:58:09Z")?),
       duration: None,
       non_billable: false,
+      billable: false,
+      no_default_tags: false,
       lunch_break: true,
+      split_by_days: false,
       description: None,
       tags: None,
+      from_json: None,
+      from_toggl_csv: None,
+      create_missing_project: false,
+      client: None,
     };
 
   assert_eq!(
@@ -97,14 +162,22 @@ fn test_calculate_duration() -> anyhow::Result<()> {
 
   let time_entry_with_duration_but_without_end_and_lunch_break =
     CreateTimeEntry {
-      project: "fkbr".to_string(),
+      project: Some("fkbr".to_string()),
+      task: None,
       start: DateTime::<Local>::from_str("2021-11-21T22:58:09Z")?,
       end: None,
       duration: Duration::try_hours(2),
       non_billable: false,
+      billable: false,
+      no_default_tags: false,
       lunch_break: false,
+      split_by_days: false,
       description: None,
       tags: None,
+      from_json: None,
+      from_toggl_csv: None,
+      create_missing_project: false,
+      client: None,
     };
 
   assert_eq!(
@@ -115,14 +188,22 @@ fn test_calculate_duration() -> anyhow::Result<()> {
   );
 
   let time_entry_with_start_is_the_same_as_end = CreateTimeEntry {
-    project: "fkbr".to_string(),
+    project: Some("fkbr".to_string()),
+    task: None,
     start: DateTime::<Local>::from_str("2021-11-21T22:58:09Z")?,
     end: Some(DateTime::<Local>::from_str("2021-11-21T22:58:09Z")?),
     duration: None,
     non_billable: false,
+    billable: false,
+    no_default_tags: false,
     lunch_break: false,
+    split_by_days: false,
     description: None,
     tags: None,
+    from_json: None,
+    from_toggl_csv: None,
+    create_missing_project: false,
+    client: None,
   };
 
   assert_eq!(
@@ -135,14 +216,22 @@ fn test_calculate_duration() -> anyhow::Result<()> {
   );
 
   let time_entry_with_start_is_after_end = CreateTimeEntry {
-    project: "fkbr".to_string(),
+    project: Some("fkbr".to_string()),
+    task: None,
     start: DateTime::<Local>::from_str("2021-11-21T23:58:09Z")?,
     end: Some(DateTime::<Local>::from_str("2021-11-21T22:58:09Z")?),
     duration: None,
     non_billable: false,
+    billable: false,
+    no_default_tags: false,
     lunch_break: false,
+    split_by_days: false,
     description: None,
     tags: None,
+    from_json: None,
+    from_toggl_csv: None,
+    create_missing_project: false,
+    client: None,
   };
 
   assert_eq!(
@@ -153,14 +242,22 @@ fn test_calculate_duration() -> anyhow::Result<()> {
   );
 
   let time_entry_where_lunch_break_is_longer_than_duration = CreateTimeEntry {
-    project: "fkbr".to_string(),
+    project: Some("fkbr".to_string()),
+    task: None,
     start: DateTime::<Local>::from_str("2021-11-21T10:58:09Z")?,
     end: Some(DateTime::<Local>::from_str("2021-11-21T11:58:09Z")?),
     duration: None,
     non_billable: false,
+    billable: false,
+    no_default_tags: false,
     lunch_break: true,
+    split_by_days: false,
     description: None,
     tags: None,
+    from_json: None,
+    from_toggl_csv: None,
+    create_missing_project: false,
+    client: None,
   };
 
   assert_eq!(
@@ -173,6 +270,73 @@ fn test_calculate_duration() -> anyhow::Result<()> {
   Ok(())
 }
 
+#[test]
+fn test_split_by_days() -> anyhow::Result<()> {
+  // 2021-11-19 is a friday => weekend is skipped
+  let spans = split_by_days(
+    DateTime::<Local>::from_str("2021-11-19T09:00:00+01:00")?,
+    DateTime::<Local>::from_str("2021-11-22T17:00:00+01:00")?,
+    false,
+  )?;
+
+  assert_eq!(
+    spans,
+    vec![
+      (
+        DateTime::<Local>::from_str("2021-11-19T09:00:00+01:00")?,
+        Duration::try_hours(8).unwrap()
+      ),
+      (
+        DateTime::<Local>::from_str("2021-11-22T09:00:00+01:00")?,
+        Duration::try_hours(8).unwrap()
+      ),
+    ]
+  );
+
+  let spans_with_lunch_break = split_by_days(
+    DateTime::<Local>::from_str("2021-11-22T09:00:00+01:00")?,
+    DateTime::<Local>::from_str("2021-11-23T17:00:00+01:00")?,
+    true,
+  )?;
+
+  assert_eq!(
+    spans_with_lunch_break
+      .iter()
+      .map(|(_, duration)| *duration)
+      .collect::<Vec<Duration>>(),
+    vec![
+      Duration::try_hours(7).unwrap(),
+      Duration::try_hours(7).unwrap()
+    ]
+  );
+
+  assert_eq!(
+    split_by_days(
+      DateTime::<Local>::from_str("2021-11-22T17:00:00+01:00")?,
+      DateTime::<Local>::from_str("2021-11-23T09:00:00+01:00")?,
+      false,
+    )
+    .unwrap_err()
+    .to_string(),
+    "start time='17:00:00' is greater or equal than end time='09:00:00'"
+      .to_string()
+  );
+
+  // 2021-11-20 and 2021-11-21 are a saturday and a sunday
+  assert_eq!(
+    split_by_days(
+      DateTime::<Local>::from_str("2021-11-20T09:00:00+01:00")?,
+      DateTime::<Local>::from_str("2021-11-21T17:00:00+01:00")?,
+      false,
+    )
+    .unwrap_err()
+    .to_string(),
+    "No working days between 2021-11-20 and 2021-11-21".to_string()
+  );
+
+  Ok(())
+}
+
 #[test]
 fn test_create_workday_with_pause_2_hours() -> anyhow::Result<()> {
   let mut server = mockito::Server::new();
@@ -256,9 +420,17 @@ fn test_create_workday_with_pause_2_hours() -> anyhow::Result<()> {
       end: None,
       duration: Duration::try_hours(2),
       lunch_break: false,
-      project: "betamale gmbh".to_string(),
+      split_by_days: false,
+      project: Some("betamale gmbh".to_string()),
+      task: None,
       tags: None,
+      from_json: None,
+      from_toggl_csv: None,
+      create_missing_project: false,
+      client: None,
       non_billable: true,
+      billable: false,
+      no_default_tags: false,
     };
 
     let client = TogglClient::new_with_base_url(
@@ -268,7 +440,7 @@ fn test_create_workday_with_pause_2_hours() -> anyhow::Result<()> {
 
     create(
       false,
-      &crate::cli::Format::Json,
+      &Output::new(Format::Json),
       &workday_with_pause,
       &client,
     )?;
@@ -405,9 +577,17 @@ fn test_create_workday_with_pause_7_hours() -> anyhow::Result<()> {
       end: None,
       duration: Duration::try_hours(7),
       lunch_break: true,
-      project: "betamale gmbh".to_string(),
+      split_by_days: false,
+      project: Some("betamale gmbh".to_string()),
+      task: None,
       tags: None,
+      from_json: None,
+      from_toggl_csv: None,
+      create_missing_project: false,
+      client: None,
       non_billable: false,
+      billable: false,
+      no_default_tags: false,
     };
 
     let client = TogglClient::new_with_base_url(
@@ -417,7 +597,7 @@ fn test_create_workday_with_pause_7_hours() -> anyhow::Result<()> {
 
     create(
       false,
-      &crate::cli::Format::Json,
+      &Output::new(Format::Json),
       &workday_with_pause,
       &client,
     )?;
@@ -432,6 +612,193 @@ fn test_create_workday_with_pause_7_hours() -> anyhow::Result<()> {
   Ok(())
 }
 
+#[test]
+fn test_edit_time_entry() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let time_entry = json!(
+    {
+      "id": 123,
+      "pid": 123456789,
+      "wid": 1234567,
+      "billable": true,
+      "start": "2021-11-21T08:00:00Z",
+      "stop": "2021-11-21T10:00:00Z",
+      "duration": 7200,
+      "description": "fkbr",
+      "tags": ["a"]
+    }
+  );
+
+  let get_mock = server
+    .mock("GET", "/me/time_entries/123")
+    .with_status(200)
+    .with_body(time_entry.to_string())
+    .expect(1)
+    .create();
+
+  let request_body = json!(
+    {
+      "billable": false,
+      "description": "kaese",
+      "duration": 7200,
+      "project_id": 123456789,
+      "rate": 95.0,
+      "start": "2021-11-21T08:00:00Z",
+      "stop": "2021-11-21T10:00:00Z",
+      "tags": ["a"],
+      "workspace_id": 1234567
+    }
+  );
+
+  let update_mock = server
+    .mock("PUT", "/workspaces/1234567/time_entries/123")
+    .match_body(Matcher::Json(request_body))
+    .with_status(200)
+    .with_body(time_entry.to_string())
+    .expect(1)
+    .create();
+
+  {
+    let edit_time_entry = EditTimeEntry {
+      id: TimeEntryId::Id(123),
+      project: None,
+      description: Some("kaese".to_string()),
+      tags: None,
+      toggle_billable: true,
+      billable: false,
+      non_billable: false,
+      rate: Some(95.0),
+      keep_history: false,
+    };
+
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    edit(
+      false,
+      &Output::new(Format::Json),
+      &edit_time_entry,
+      false,
+      &client,
+    )?;
+  }
+
+  get_mock.assert();
+  update_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_bulk_edit_into_non_billable_project() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let me_mock = server
+    .mock("GET", "/me")
+    .with_status(200)
+    .with_body(me().to_string())
+    .expect_at_least(1)
+    .create();
+
+  let projects = json!(
+    [
+      { "id": 111, "wid": 1234567, "name": "fkbr", "status": "active" },
+      { "id": 222, "wid": 1234567, "name": "internal", "status": "active" }
+    ]
+  );
+
+  let projects_mock = server
+    .mock(
+      "GET",
+      Matcher::Regex("^/workspaces/1234567/projects".to_string()),
+    )
+    .with_status(200)
+    .with_body(projects.to_string())
+    .expect(2)
+    .create();
+
+  let tags_mock = server
+    .mock("GET", "/workspaces/1234567/tags")
+    .with_status(200)
+    .with_body("[]")
+    .create();
+
+  let time_entries_mock = server
+    .mock("GET", Matcher::Regex("^/me/time_entries".to_string()))
+    .with_status(200)
+    .with_body(
+      json!(
+        [
+          {
+            "id": 123,
+            "pid": 111,
+            "wid": 1234567,
+            "billable": true,
+            "start": "2021-11-21T08:00:00Z",
+            "stop": "2021-11-21T10:00:00Z",
+            "duration": 7200,
+            "description": "fkbr"
+          }
+        ]
+      )
+      .to_string(),
+    )
+    .create();
+
+  let request_body = json!(
+    [
+      { "op": "replace", "path": "/project_id", "value": 222 },
+      { "op": "replace", "path": "/billable", "value": false }
+    ]
+  );
+
+  let patch_mock = server
+    .mock("PATCH", "/workspaces/1234567/time_entries/123")
+    .match_body(Matcher::Json(request_body))
+    .with_status(200)
+    .with_body(json!({ "success": [123], "failure": [] }).to_string())
+    .expect(1)
+    .create();
+
+  {
+    let bulk_edit_time_entries = BulkEditTimeEntries {
+      range: Range::Today,
+      project: None,
+      filter_tag: vec![],
+      set_project: Some("internal".to_string()),
+      add_tag: vec![],
+      remove_tag: vec![],
+      set_billable: false,
+      set_non_billable: false,
+      yes: true,
+    };
+
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    bulk_edit(
+      false,
+      &Output::new(Format::Json),
+      &bulk_edit_time_entries,
+      &["internal".to_string()],
+      &client,
+    )?;
+  }
+
+  me_mock.assert();
+  projects_mock.assert();
+  tags_mock.assert();
+  time_entries_mock.assert();
+  patch_mock.assert();
+
+  Ok(())
+}
+
 fn me() -> Value {
   json!(
     {
@@ -482,3 +849,993 @@ fn projects() -> Value {
     ]
   )
 }
+
+#[test]
+fn test_missing_days() -> anyhow::Result<()> {
+  let dates = [
+    DateTime::<Local>::from_str("2021-11-22T00:00:00+01:00")?,
+    DateTime::<Local>::from_str("2021-11-23T00:00:00+01:00")?,
+    DateTime::<Local>::from_str("2021-11-24T00:00:00+01:00")?,
+  ];
+
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "start": "2021-11-22T08:00:00+01:00",
+      "duration": 28800
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-23T08:00:00+01:00",
+      "duration": 14400
+    }
+  ]))?;
+
+  let logged = logged_by_date(&time_entries);
+
+  let missing = missing_days(&dates, &logged, None);
+
+  assert_eq!(missing.len(), 1);
+  assert_eq!(missing[0].date, dates[2]);
+
+  let missing = missing_days(&dates, &logged, Duration::try_hours(7));
+
+  assert_eq!(missing.len(), 2);
+  assert_eq!(missing[0].date, dates[1]);
+  assert_eq!(missing[0].logged, Duration::try_hours(4).unwrap());
+  assert_eq!(missing[1].logged, Duration::zero());
+
+  Ok(())
+}
+
+#[test]
+fn test_current_check() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let running_mock = server
+    .mock("GET", "/me/time_entries/current")
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 1,
+        "wid": 1234567,
+        "start": "2021-11-22T08:00:00+01:00",
+        "duration": -1637564400
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let check = CurrentTimeEntry {
+    check: true,
+    watch: false,
+  };
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    assert!(current(false, &Output::new(Format::Raw), &check, &client)?);
+  }
+
+  running_mock.assert();
+  running_mock.remove();
+
+  let stopped_mock = server
+    .mock("GET", "/me/time_entries/current")
+    .with_status(200)
+    .with_body("null")
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    assert!(!current(false, &Output::new(Format::Raw), &check, &client)?);
+  }
+
+  stopped_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_parse_import() -> anyhow::Result<()> {
+  let time_entries = parse_import(
+    &json!([
+      {
+        "id": 1,
+        "wid": 1234567,
+        "pid": 123456789,
+        "billable": true,
+        "start": "2021-11-22T08:00:00+01:00",
+        "stop": "2021-11-22T10:00:00+01:00",
+        "duration": 7200,
+        "description": "fkbr",
+        "tags": ["a"]
+      },
+      {
+        "project": "fkbr.org",
+        "start": "2021-11-22T10:00:00Z",
+        "stop": "2021-11-22T10:30:00Z"
+      },
+      {
+        "project": "fkbr.org",
+        "start": "2021-11-22T11:00:00Z",
+        "duration": -1637578800
+      },
+      {
+        "start": "2021-11-22T11:00:00Z"
+      }
+    ])
+    .to_string(),
+  )?;
+
+  assert_eq!(time_entries.len(), 4);
+
+  assert_eq!(time_entries[0].project_selector()?, "123456789");
+  assert_eq!(
+    time_entries[0].stopped_duration()?,
+    Duration::try_hours(2).unwrap()
+  );
+
+  assert_eq!(time_entries[1].project_selector()?, "fkbr.org");
+  assert_eq!(
+    time_entries[1].stopped_duration()?,
+    Duration::try_minutes(30).unwrap()
+  );
+
+  assert!(time_entries[2].stopped_duration().is_err());
+  assert!(time_entries[3].project_selector().is_err());
+  assert!(time_entries[3].stopped_duration().is_err());
+
+  assert!(parse_import("{}").is_err());
+
+  Ok(())
+}
+
+#[test]
+fn test_parse_toggl_csv() -> anyhow::Result<()> {
+  let time_entries = parse_toggl_csv(
+    "User,Email,Client,Project,Task,Description,Billable,Start date,Start time,End date,End time,Duration,Tags,Amount ()
+Ferris,ferris@example.com,beta male gmbh,fkbr.org,,fkbr,Yes,2021-11-22,08:00:00,2021-11-22,10:00:00,02:00:00,\"a, b\",
+Ferris,ferris@example.com,,sxoe.org,,,No,2021-11-22,10:00:00,2021-11-22,10:30:00,00:30:00,,
+",
+  )?;
+
+  assert_eq!(time_entries.len(), 2);
+
+  assert_eq!(time_entries[0].project_selector()?, "fkbr.org");
+  assert_eq!(time_entries[0].description.as_deref(), Some("fkbr"));
+  assert_eq!(time_entries[0].billable, Some(true));
+  assert_eq!(
+    time_entries[0].tags,
+    Some(vec!["a".to_string(), "b".to_string()])
+  );
+  assert_eq!(
+    time_entries[0].start,
+    DateTime::parse_from_rfc3339("2021-11-22T08:00:00+01:00")?
+  );
+  assert_eq!(
+    time_entries[0].stopped_duration()?,
+    Duration::try_hours(2).unwrap()
+  );
+
+  assert_eq!(time_entries[1].project_selector()?, "sxoe.org");
+  assert_eq!(time_entries[1].description, None);
+  assert_eq!(time_entries[1].billable, Some(false));
+  assert_eq!(time_entries[1].tags, None);
+  assert_eq!(
+    time_entries[1].stopped_duration()?,
+    Duration::try_minutes(30).unwrap()
+  );
+
+  let time_entries = parse_toggl_csv(
+    "Description,Billable,Duration,Project,Start date,Start time,Stop date,Stop time
+fkbr,Yes,26:00:00,fkbr.org,2021-11-22,08:00:00,2021-11-23,10:00:00
+",
+  )?;
+
+  assert_eq!(
+    time_entries[0].stopped_duration()?,
+    Duration::try_hours(26).unwrap()
+  );
+
+  assert!(parse_toggl_csv("Project,Description\nfkbr.org,fkbr\n").is_err());
+  assert!(parse_toggl_csv(
+    "Project,Start date,Start time,Duration\nfkbr.org,2021-11-22,08:00:00,2h\n"
+  )
+  .is_err());
+
+  Ok(())
+}
+
+#[test]
+fn test_stop_before_delete() -> anyhow::Result<()> {
+  let running: TimeEntry = serde_json::from_value(json!({
+    "id": 1,
+    "wid": 1234567,
+    "start": "2021-11-22T08:00:00+01:00",
+    "duration": -1637564400
+  }))?;
+
+  let now = DateTime::<Local>::from_str("2021-11-22T09:30:00+01:00")?;
+
+  let error = stop_before_delete(&running, false, false, now).unwrap_err();
+
+  assert!(error.to_string().contains("Time entry 1 is running"));
+  assert!(error.to_string().contains("01:30:00"));
+
+  assert!(!stop_before_delete(&running, true, false, now)?);
+  assert!(stop_before_delete(&running, false, true, now)?);
+
+  Ok(())
+}
+
+#[test]
+fn test_edited_billable() {
+  let edit_time_entry =
+    |toggle_billable, billable, non_billable| EditTimeEntry {
+      id: TimeEntryId::Id(123),
+      project: None,
+      description: None,
+      tags: None,
+      toggle_billable,
+      billable,
+      non_billable,
+      rate: None,
+      keep_history: false,
+    };
+
+  for current in [None, Some(false), Some(true)] {
+    assert_eq!(
+      edited_billable(current, &edit_time_entry(false, true, false)),
+      Some(true)
+    );
+    assert_eq!(
+      edited_billable(current, &edit_time_entry(false, false, true)),
+      Some(false)
+    );
+    assert_eq!(
+      edited_billable(current, &edit_time_entry(false, false, false)),
+      current
+    );
+  }
+
+  assert_eq!(
+    edited_billable(Some(true), &edit_time_entry(true, false, false)),
+    Some(false)
+  );
+  assert_eq!(
+    edited_billable(None, &edit_time_entry(true, false, false)),
+    Some(true)
+  );
+}
+
+#[test]
+fn test_has_any_tag() -> anyhow::Result<()> {
+  let time_entry: TimeEntry = serde_json::from_value(json!({
+    "id": 1,
+    "wid": 1234567,
+    "start": "2021-11-22T08:00:00+01:00",
+    "duration": 3600,
+    "tags": ["On-Call", "night"]
+  }))?;
+
+  let untagged: TimeEntry = serde_json::from_value(json!({
+    "id": 2,
+    "wid": 1234567,
+    "start": "2021-11-22T10:00:00+01:00",
+    "duration": 3600
+  }))?;
+
+  let tags =
+    |tags: &[&str]| tags.iter().map(|tag| tag.to_string()).collect::<Vec<_>>();
+
+  assert!(has_any_tag(&time_entry, &tags(&["on-call"])));
+  assert!(has_any_tag(&time_entry, &tags(&["meeting", "NIGHT"])));
+  assert!(!has_any_tag(&time_entry, &tags(&["meeting"])));
+  assert!(!has_any_tag(&untagged, &tags(&["on-call"])));
+
+  Ok(())
+}
+
+#[test]
+fn test_time_entry_filter() -> anyhow::Result<()> {
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "start": "2021-11-22T09:00:00+01:00",
+      "duration": 900,
+      "description": "Daily Standup",
+      "tags": ["meeting"]
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-22T10:00:00+01:00",
+      "duration": 3600,
+      "description": "standup notes (v2)"
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "start": "2021-11-22T11:00:00+01:00",
+      "duration": 3600
+    }
+  ]))?;
+
+  let matching = |search: Option<&str>, regex: bool, tags: &[&str]| {
+    let filter = TimeEntryFilter::new(&ListTimeEntries {
+      range: Range::Today,
+      missing: false,
+      include_weekends: false,
+      expected_hours: None,
+      tags: tags.iter().map(|tag| tag.to_string()).collect(),
+      search: search.map(|search| search.to_string()),
+      regex,
+      overlaps: false,
+      round: None,
+      round_mode: None,
+      apply: false,
+      yes: false,
+    })
+    .unwrap();
+
+    time_entries
+      .iter()
+      .filter(|time_entry| filter.matches(time_entry))
+      .map(|time_entry| time_entry.id)
+      .collect::<Vec<u64>>()
+  };
+
+  assert_eq!(matching(None, false, &[]), vec![1, 2, 3]);
+  assert_eq!(matching(Some("STANDUP"), false, &[]), vec![1, 2]);
+  assert_eq!(matching(Some("(v2)"), false, &[]), vec![2]);
+  assert_eq!(matching(Some("^daily"), true, &[]), vec![1]);
+  assert_eq!(matching(Some("standup"), false, &["meeting"]), vec![1]);
+
+  Ok(())
+}
+
+#[test]
+fn test_continue_candidates() -> anyhow::Result<()> {
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T08:00:00+01:00",
+      "duration": 3600,
+      "description": "fkbr"
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-22T09:00:00+01:00",
+      "duration": 3600,
+      "description": "without project"
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "pid": 2,
+      "start": "2021-11-22T10:00:00+01:00",
+      "duration": 3600,
+      "description": "sxoe"
+    },
+    {
+      "id": 4,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T11:00:00+01:00",
+      "duration": 3600,
+      "description": "fkbr"
+    }
+  ]))?;
+
+  let ids = |candidates: Vec<TimeEntry>| {
+    candidates
+      .iter()
+      .map(|time_entry| time_entry.id)
+      .collect::<Vec<u64>>()
+  };
+
+  assert_eq!(
+    ids(continue_candidates(time_entries.clone(), 10)),
+    vec![4, 3]
+  );
+  assert_eq!(ids(continue_candidates(time_entries, 1)), vec![4]);
+
+  Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+#[test]
+fn test_notification_command() {
+  let command = notification_command("Pomodoro is over").unwrap();
+
+  assert_eq!(command.get_program(), "notify-send");
+  assert_eq!(
+    command.get_args().collect::<Vec<_>>(),
+    vec!["fbtoggl", "Pomodoro is over"]
+  );
+}
+
+#[test]
+fn test_timer_line() -> anyhow::Result<()> {
+  let now = DateTime::<chrono::Utc>::from_str("2021-11-22T10:00:00Z")?;
+
+  let time_entry: TimeEntry = serde_json::from_value(json!({
+    "id": 1,
+    "wid": 1234567,
+    "pid": 1,
+    "start": "2021-11-22T09:00:00Z",
+    "duration": -1637571600,
+    "description": "sxoe"
+  }))?;
+
+  let line = timer_line(
+    &Output::new(Format::Raw),
+    Some(&time_entry),
+    Some("fkbr"),
+    now,
+  );
+
+  assert!(line.contains("1:00:00"));
+  assert!(line.ends_with(" fkbr - sxoe"));
+
+  assert_eq!(
+    timer_line(&Output::new(Format::Raw), None, None, now),
+    "No time entry is running"
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_week_start() -> anyhow::Result<()> {
+  let monday = NaiveDate::from_ymd_opt(2021, 11, 22).unwrap();
+
+  assert_eq!(week_start(monday), monday);
+  assert_eq!(
+    week_start(NaiveDate::from_ymd_opt(2021, 11, 24).unwrap()),
+    monday
+  );
+  assert_eq!(
+    week_start(NaiveDate::from_ymd_opt(2021, 11, 28).unwrap()),
+    monday
+  );
+  assert_eq!(
+    week_start(NaiveDate::from_ymd_opt(2021, 11, 29).unwrap()),
+    NaiveDate::from_ymd_opt(2021, 11, 29).unwrap()
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_heat() {
+  assert_eq!(heat(Duration::zero()), Heat::Nothing);
+  assert_eq!(heat(Duration::try_minutes(239).unwrap()), Heat::Below4Hours);
+  assert_eq!(heat(Duration::try_hours(4).unwrap()), Heat::From4To8Hours);
+  assert_eq!(heat(Duration::try_hours(8).unwrap()), Heat::From4To8Hours);
+  assert_eq!(heat(Duration::try_minutes(481).unwrap()), Heat::Above8Hours);
+}
+
+#[test]
+fn test_calendar_weeks() {
+  // November 2021 starts on a Monday and ends on a Tuesday
+  let days = NaiveDate::from_ymd_opt(2021, 11, 1)
+    .unwrap()
+    .iter_days()
+    .take(30)
+    .map(|date| (date, ()))
+    .collect::<Vec<(NaiveDate, ())>>();
+
+  let weeks = calendar_weeks(&days);
+
+  assert_eq!(weeks.len(), 5);
+  assert_eq!(
+    weeks[0][0].map(|(date, _)| date),
+    NaiveDate::from_ymd_opt(2021, 11, 1)
+  );
+  assert_eq!(
+    weeks[4][1].map(|(date, _)| date),
+    NaiveDate::from_ymd_opt(2021, 11, 30)
+  );
+  assert_eq!(weeks[4][2], None);
+
+  // December 2021 starts on a Wednesday
+  let days = NaiveDate::from_ymd_opt(2021, 12, 1)
+    .unwrap()
+    .iter_days()
+    .take(31)
+    .map(|date| (date, ()))
+    .collect::<Vec<(NaiveDate, ())>>();
+
+  let weeks = calendar_weeks(&days);
+
+  assert_eq!(weeks.len(), 5);
+  assert_eq!(weeks[0][1], None);
+  assert_eq!(
+    weeks[0][2].map(|(date, _)| date),
+    NaiveDate::from_ymd_opt(2021, 12, 1)
+  );
+}
+
+#[test]
+fn test_stop_current() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let current_mock = server
+    .mock("GET", "/me/time_entries/current")
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 42,
+        "wid": 1234567,
+        "start": "2021-11-22T09:00:00Z",
+        "duration": -1637571600
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let stop_mock = server
+    .mock("PATCH", "/workspaces/1234567/time_entries/42/stop")
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 42,
+        "wid": 1234567,
+        "start": "2021-11-22T09:00:00Z",
+        "stop": "2021-11-22T10:00:00Z",
+        "duration": 3600
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let stopped = stop_current(false, &client)?;
+
+  assert_eq!(stopped.map(|time_entry| time_entry.duration), Some(3600));
+
+  current_mock.assert();
+  stop_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_stop_current_without_running_time_entry() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let current_mock = server
+    .mock("GET", "/me/time_entries/current")
+    .with_status(200)
+    .with_body("null")
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  assert!(stop_current(false, &client)?.is_none());
+
+  current_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_chained_start() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let stopped_mock = server
+    .mock("GET", "/me/time_entries/41")
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 41,
+        "wid": 1234567,
+        "start": "2021-11-22T08:00:00Z",
+        "stop": "2021-11-22T09:30:00Z",
+        "duration": 5400
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let running_mock = server
+    .mock("GET", "/me/time_entries/42")
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 42,
+        "wid": 1234567,
+        "start": "2021-11-22T09:30:00Z",
+        "duration": -1637573400
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let stop_mock = server
+    .mock("PATCH", "/workspaces/1234567/time_entries/42/stop")
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 42,
+        "wid": 1234567,
+        "start": "2021-11-22T09:30:00Z",
+        "stop": "2021-11-22T11:00:00Z",
+        "duration": 5400
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  assert_eq!(
+    chained_start(false, &TimeEntryId::Id(41), &client)?,
+    DateTime::parse_from_rfc3339("2021-11-22T09:30:00Z")?
+  );
+  assert_eq!(
+    chained_start(false, &TimeEntryId::Id(42), &client)?,
+    DateTime::parse_from_rfc3339("2021-11-22T11:00:00Z")?
+  );
+
+  stopped_mock.assert();
+  running_mock.assert();
+  stop_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_bulk_edit_operations() -> anyhow::Result<()> {
+  assert_eq!(
+    serde_json::to_value(bulk_edit_operations(
+      Some(123),
+      &["a".to_string()],
+      &["b".to_string(), "c".to_string()],
+      Some(false),
+    ))?,
+    json!([
+      { "op": "replace", "path": "/project_id", "value": 123 },
+      { "op": "add", "path": "/tags", "value": ["a"] },
+      { "op": "remove", "path": "/tags", "value": ["b", "c"] },
+      { "op": "replace", "path": "/billable", "value": false }
+    ])
+  );
+
+  assert!(bulk_edit_operations(None, &[], &[], None).is_empty());
+
+  Ok(())
+}
+
+#[test]
+fn test_duplicates() -> anyhow::Result<()> {
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 3,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T08:00:00Z",
+      "duration": 3600,
+      "description": "fkbr"
+    },
+    {
+      "id": 1,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T08:00:00Z",
+      "duration": 3600,
+      "description": "fkbr"
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T08:00:00Z",
+      "duration": 3600,
+      "description": "sxoe"
+    },
+    {
+      "id": 4,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T09:00:00Z",
+      "duration": 1800,
+      "description": "fkbr"
+    },
+    {
+      "id": 5,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T09:00:00Z",
+      "duration": 1800,
+      "description": "fkbr"
+    },
+    {
+      "id": 6,
+      "wid": 1234567,
+      "pid": 1,
+      "start": "2021-11-22T09:00:00Z",
+      "duration": 1800,
+      "description": "fkbr"
+    }
+  ]))?;
+
+  assert_eq!(
+    duplicates(&time_entries)
+      .iter()
+      .map(|time_entry| time_entry.id)
+      .collect::<Vec<u64>>(),
+    vec![3, 5, 6]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_split_time_entry() -> anyhow::Result<()> {
+  let start = Local.with_ymd_and_hms(2021, 11, 22, 8, 0, 0).unwrap();
+  let now = Local.with_ymd_and_hms(2021, 11, 22, 17, 0, 0).unwrap();
+
+  let time_entry: TimeEntry = serde_json::from_value(json!({
+    "id": 1,
+    "wid": 1234567,
+    "pid": 123,
+    "start": start,
+    "duration": 8 * 3600
+  }))?;
+
+  let at = split_time(
+    &time_entry,
+    NaiveTime::from_hms_opt(12, 30, 0).unwrap(),
+    now,
+  )?;
+
+  assert_eq!(at, Local.with_ymd_and_hms(2021, 11, 22, 12, 30, 0).unwrap());
+  assert_eq!(
+    split_durations(&time_entry, at),
+    (Duration::minutes(270), Some(Duration::minutes(210)))
+  );
+
+  assert!(split_time(
+    &time_entry,
+    NaiveTime::from_hms_opt(16, 30, 0).unwrap(),
+    now
+  )
+  .is_err());
+  assert!(split_time(
+    &time_entry,
+    NaiveTime::from_hms_opt(7, 30, 0).unwrap(),
+    now
+  )
+  .is_err());
+
+  let running = TimeEntry {
+    duration: -1,
+    ..time_entry
+  };
+
+  let at =
+    split_time(&running, NaiveTime::from_hms_opt(16, 30, 0).unwrap(), now)?;
+
+  assert_eq!(
+    split_durations(&running, at),
+    (Duration::minutes(510), None)
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_merged_time_entry() -> anyhow::Result<()> {
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 2,
+      "wid": 1234567,
+      "pid": 123,
+      "start": "2021-11-22T09:00:30Z",
+      "duration": 1800,
+      "description": "review",
+      "tags": ["b"]
+    },
+    {
+      "id": 1,
+      "wid": 1234567,
+      "pid": 123,
+      "start": "2021-11-22T08:00:00Z",
+      "duration": 3600,
+      "description": "fkbr",
+      "tags": ["a", "b"]
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "pid": 123,
+      "start": "2021-11-22T09:30:30Z",
+      "duration": 600,
+      "description": "fkbr"
+    }
+  ]))?;
+
+  let merged = merged_time_entry(&time_entries, Duration::minutes(1))?;
+
+  assert_eq!(merged.id, 1);
+  assert_eq!(merged.duration, 6000);
+  assert_eq!(merged.stop, Some(merged.start + Duration::seconds(6000)));
+  assert_eq!(merged.description.as_deref(), Some("fkbr; review"));
+  assert_eq!(merged.tags, Some(vec!["a".to_string(), "b".to_string()]));
+
+  assert!(merged_time_entry(&time_entries, Duration::seconds(10)).is_err());
+
+  let other_project = TimeEntry {
+    pid: Some(456),
+    ..time_entries[0].clone()
+  };
+
+  assert!(merged_time_entry(
+    &[time_entries[1].clone(), other_project],
+    Duration::minutes(1)
+  )
+  .is_err());
+
+  // 08:00:00 - 09:02:00 overlaps 09:00:30 - 09:30:30 by 90 seconds
+  let overlapping = TimeEntry {
+    duration: 3720,
+    ..time_entries[1].clone()
+  };
+
+  let merged = merged_time_entry(
+    &[time_entries[0].clone(), overlapping],
+    Duration::minutes(2),
+  )?;
+
+  assert_eq!(merged.id, 1);
+  assert_eq!(merged.duration, 5430);
+
+  Ok(())
+}
+
+#[test]
+fn test_overlaps() -> anyhow::Result<()> {
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "start": "2021-11-22T08:00:00Z",
+      "duration": 7200
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-22T09:30:00Z",
+      "duration": 3600
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "start": "2021-11-22T10:30:00Z",
+      "duration": 1800
+    },
+    {
+      "id": 4,
+      "wid": 1234567,
+      "start": "2021-11-22T12:00:00Z",
+      "duration": 1800
+    },
+    {
+      "id": 5,
+      "wid": 1234567,
+      "start": "2021-11-22T12:15:00Z",
+      "duration": -1
+    }
+  ]))?;
+
+  assert_eq!(
+    overlaps(&time_entries, "2021-11-22T13:00:00Z".parse()?),
+    vec![
+      Overlap {
+        first: 1,
+        second: 2,
+        duration: Duration::minutes(30),
+      },
+      Overlap {
+        first: 4,
+        second: 5,
+        duration: Duration::minutes(15),
+      },
+    ]
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_related_time_entries() -> anyhow::Result<()> {
+  let time_entries: Vec<TimeEntry> = serde_json::from_value(json!([
+    {
+      "id": 1,
+      "wid": 1234567,
+      "start": "2021-11-22T08:00:00Z",
+      "duration": 3600
+    },
+    {
+      "id": 2,
+      "wid": 1234567,
+      "start": "2021-11-22T09:30:00Z",
+      "duration": 3600
+    },
+    {
+      "id": 3,
+      "wid": 1234567,
+      "start": "2021-11-22T10:00:00Z",
+      "duration": -1
+    },
+    {
+      "id": 4,
+      "wid": 1234567,
+      "start": "2021-11-22T13:00:00Z",
+      "duration": 1800
+    }
+  ]))?;
+
+  let ids = |time_entries: &[TimeEntry]| {
+    time_entries
+      .iter()
+      .map(|time_entry| time_entry.id)
+      .collect::<Vec<u64>>()
+  };
+
+  let related = related_time_entries(
+    &time_entries[1],
+    &time_entries,
+    "2021-11-22T12:00:00Z".parse()?,
+  );
+
+  assert_eq!(related.previous.map(|time_entry| time_entry.id), Some(1));
+  assert_eq!(related.next.map(|time_entry| time_entry.id), Some(3));
+  assert_eq!(ids(&related.overlapping), vec![3]);
+
+  let related = related_time_entries(
+    &time_entries[0],
+    &time_entries,
+    "2021-11-22T12:00:00Z".parse()?,
+  );
+
+  assert_eq!(related.previous.map(|time_entry| time_entry.id), None);
+  assert_eq!(related.next.map(|time_entry| time_entry.id), Some(2));
+  assert_eq!(ids(&related.overlapping), Vec::<u64>::new());
+
+  Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::{cli::Version, client::CREATED_WITH};
+
+pub fn version(version: &Version) {
+  println!("fbtoggl {}", env!("CARGO_PKG_VERSION"));
+
+  if version.verbose {
+    for (key, value) in build_info() {
+      println!("{key}: {value}");
+    }
+  }
+}
+
+pub(super) fn build_info() -> Vec<(&'static str, String)> {
+  let features = [("otel", cfg!(feature = "otel"))]
+    .into_iter()
+    .filter(|(_, enabled)| *enabled)
+    .map(|(feature, _)| feature)
+    .collect::<Vec<_>>();
+
+  vec![
+    ("git sha", env!("FBTOGGL_GIT_SHA").to_string()),
+    ("target", env!("FBTOGGL_TARGET").to_string()),
+    (
+      "features",
+      if features.is_empty() {
+        "none".to_string()
+      } else {
+        features.join(", ")
+      },
+    ),
+    ("created with", CREATED_WITH.to_string()),
+  ]
+}
@@ -0,0 +1,20 @@
+use crate::commands::version::build_info;
+
+#[test]
+fn test_build_info() {
+  let build_info = build_info();
+
+  let value = |key: &str| {
+    build_info
+      .iter()
+      .find(|(info_key, _)| *info_key == key)
+      .map(|(_, value)| value.as_str())
+  };
+
+  assert!(value("git sha").is_some_and(|sha| !sha.is_empty()));
+  assert!(value("target").is_some_and(|target| !target.is_empty()));
+  assert!(
+    value("created with").is_some_and(|created_with| created_with
+      .starts_with(concat!("fbtoggl/", env!("CARGO_PKG_VERSION"))))
+  );
+}
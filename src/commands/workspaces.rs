@@ -2,48 +2,84 @@ use colored::Colorize;
 use term_table::{row::Row, table_cell::TableCell, Table, TableStyle};
 
 use crate::{
-  cli::{output_values_json, Format},
-  client::TogglClient,
+  cli::{output_values_json, Format, Output},
+  client::TogglApi,
   model::Workspace,
+  resolver::Resolver,
 };
 
 pub fn list(
   debug: bool,
-  format: &Format,
-  client: &TogglClient,
+  output: &Output,
+  client: &dyn TogglApi,
 ) -> anyhow::Result<()> {
   let workspaces = client.get_workspaces(debug)?;
 
-  match format {
-    Format::Json => output_values_json(&workspaces),
+  match output.format {
+    Format::Json => output_values_json(output, &workspaces),
     Format::Raw => output_values_raw(&workspaces),
-    Format::Table => output_values_table(&workspaces),
+    Format::Table => {
+      // only admins may see the members of a workspace
+      let member_counts = workspaces
+        .iter()
+        .map(|workspace| {
+          client
+            .get_workspace_users(debug, workspace.id)
+            .ok()
+            .map(|users| users.len())
+        })
+        .collect::<Vec<Option<usize>>>();
+
+      output_values_table(&workspaces, &member_counts)
+    }
   }
 
   Ok(())
 }
 
+/// Workspace selected via --workspace, otherwise the default workspace
+pub fn selected_workspace_id(
+  debug: bool,
+  client: &dyn TogglApi,
+) -> anyhow::Result<u64> {
+  Resolver::new(debug, client).workspace_id()
+}
+
 fn output_values_raw(values: &[Workspace]) {
   for workspace in values {
     println!("\"{}\"", workspace.name);
   }
 }
 
-fn output_values_table(values: &[Workspace]) {
+fn output_values_table(values: &[Workspace], member_counts: &[Option<usize>]) {
   let mut table = Table::new();
   table.style = TableStyle::thin();
 
   let header = Row::new(vec![
     TableCell::new("ID".bold().white()),
     TableCell::new("Name".bold().white()),
+    TableCell::new("Role".bold().white()),
+    TableCell::new("Members".bold().white()),
   ]);
 
   table.add_row(header);
 
-  for workspace in values {
+  for (workspace, member_count) in values.iter().zip(member_counts) {
+    let role = if workspace.admin {
+      workspace.role().green()
+    } else {
+      workspace.role().normal()
+    };
+
     let row = Row::new(vec![
       TableCell::new(workspace.id),
       TableCell::new(&workspace.name),
+      TableCell::new(role),
+      TableCell::new(
+        member_count
+          .map(|count| count.to_string())
+          .unwrap_or_else(|| "-".to_string()),
+      ),
     ]);
 
     table.add_row(row);
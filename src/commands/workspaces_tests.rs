@@ -0,0 +1,53 @@
+use crate::{
+  client::TogglApi, commands::workspaces::selected_workspace_id,
+  fake_client::FakeClient, model::Workspace,
+};
+use pretty_assertions::assert_eq;
+
+fn workspaces() -> Vec<Workspace> {
+  vec![
+    Workspace {
+      id: 1,
+      name: "ACME Corp".to_string(),
+      ..Default::default()
+    },
+    Workspace {
+      id: 2,
+      name: "ACME Labs".to_string(),
+      ..Default::default()
+    },
+    Workspace {
+      id: 3,
+      name: "fkbr".to_string(),
+      ..Default::default()
+    },
+  ]
+}
+
+#[test]
+fn test_selected_workspace_id() -> anyhow::Result<()> {
+  let client = FakeClient {
+    workspaces: workspaces(),
+    default_workspace_id: 42,
+    ..Default::default()
+  };
+
+  assert_eq!(selected_workspace_id(false, &client)?, 42);
+  assert_eq!(
+    selected_workspace_id(
+      false,
+      client.with_workspace(Some("fkbr".into())).as_ref()
+    )?,
+    3
+  );
+  assert_eq!(
+    selected_workspace_id(
+      false,
+      client.with_workspace(Some("nope".into())).as_ref()
+    )
+    .is_err(),
+    true
+  );
+
+  Ok(())
+}
@@ -0,0 +1,172 @@
+use clap::{Command, CommandFactory};
+
+use crate::{
+  cli::{Options, Shell},
+  client::TogglApi,
+  resolver::Resolver,
+};
+
+/// Values of --range besides ISO 8601 dates
+const RANGES: [&str; 9] = [
+  "today",
+  "yesterday",
+  "this-week",
+  "last-week",
+  "this-month",
+  "last-month",
+  "this-billing-month",
+  "last-billing-month",
+  "all",
+];
+
+/// What the word under the cursor completes to
+#[derive(Debug, PartialEq)]
+pub enum Target {
+  /// Subcommands and options of the current command
+  Names(Vec<String>),
+
+  /// Value of the option with this long name
+  Value(String),
+}
+
+/// Target of the last word, the words before are the arguments
+/// after 'fbtoggl' up to the cursor
+pub fn target(command: &Command, words: &[String]) -> Target {
+  let (before, _) = words.split_at(words.len().saturating_sub(1));
+
+  let mut command = command;
+  let mut pending_value: Option<String> = None;
+
+  for word in before {
+    if pending_value.take().is_some() {
+      continue;
+    }
+
+    if let Some(long) = word.strip_prefix("--") {
+      let takes_value = command
+        .get_arguments()
+        .find(|arg| arg.get_long() == Some(long))
+        .is_some_and(|arg| arg.get_action().takes_values());
+
+      if takes_value {
+        pending_value = Some(long.to_string());
+      }
+    } else if let Some(subcommand) = command.find_subcommand(word) {
+      command = subcommand;
+    }
+  }
+
+  if let Some(long) = pending_value {
+    return Target::Value(long);
+  }
+
+  let names = command
+    .get_subcommands()
+    .filter(|subcommand| !subcommand.is_hide_set())
+    .map(|subcommand| subcommand.get_name().to_string())
+    .chain(
+      command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set())
+        .filter_map(|arg| arg.get_long())
+        .map(|long| format!("--{long}")),
+    )
+    .collect();
+
+  Target::Names(names)
+}
+
+/// Candidates of the last word, values are looked up via the (cached) API
+pub fn complete(
+  debug: bool,
+  words: &[String],
+  client: impl FnOnce() -> anyhow::Result<Box<dyn TogglApi>>,
+) -> anyhow::Result<Vec<String>> {
+  let prefix = words.last().map(|word| word.as_str()).unwrap_or_default();
+
+  let candidates = match target(&Options::command(), words) {
+    Target::Names(names) => names,
+    Target::Value(long) => values(debug, &long, client)?,
+  };
+
+  Ok(
+    candidates
+      .into_iter()
+      .filter(|candidate| candidate.starts_with(prefix))
+      .collect(),
+  )
+}
+
+fn values(
+  debug: bool,
+  long: &str,
+  client: impl FnOnce() -> anyhow::Result<Box<dyn TogglApi>>,
+) -> anyhow::Result<Vec<String>> {
+  if long == "range" || long == "against" {
+    return Ok(RANGES.iter().map(|range| range.to_string()).collect());
+  }
+
+  if !["project", "client", "from-template"].contains(&long) {
+    return Ok(vec![]);
+  }
+
+  let client = client()?;
+  let workspace_id = Resolver::new(debug, client.as_ref()).workspace_id()?;
+
+  let names = match long {
+    "project" => client
+      .get_workspace_projects(debug, false, workspace_id)?
+      .into_iter()
+      .map(|project| project.name)
+      .collect(),
+    "client" => client
+      .get_workspace_clients(debug, false, workspace_id)?
+      .unwrap_or_default()
+      .into_iter()
+      .map(|client| client.name)
+      .collect(),
+    _ => client
+      .get_project_templates(debug, workspace_id)?
+      .into_iter()
+      .map(|template| template.name)
+      .collect(),
+  };
+
+  Ok(names)
+}
+
+/// Completion script of the shell, it asks 'fbtoggl completions dynamic'
+/// for the candidates of the word under the cursor
+pub fn script(shell: &Shell) -> &'static str {
+  match shell {
+    Shell::Bash => {
+      r#"_fbtoggl() {
+  local IFS=$'\n'
+  COMPREPLY=($(fbtoggl completions dynamic -- "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null))
+}
+
+complete -o default -F _fbtoggl fbtoggl
+"#
+    }
+    Shell::Zsh => {
+      r#"#compdef fbtoggl
+
+_fbtoggl() {
+  local -a candidates
+  candidates=("${(@f)$(fbtoggl completions dynamic -- "${(@)words[2,CURRENT]}" 2>/dev/null)}")
+  compadd -a candidates
+}
+
+if [ "$funcstack[1]" = "_fbtoggl" ]; then
+  _fbtoggl "$@"
+else
+  compdef _fbtoggl fbtoggl
+fi
+"#
+    }
+    Shell::Fish => {
+      r#"complete -c fbtoggl -f -a '(fbtoggl completions dynamic -- (commandline -opc)[2..-1] (commandline -ct) 2>/dev/null)'
+"#
+    }
+  }
+}
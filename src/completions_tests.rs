@@ -0,0 +1,46 @@
+use crate::{
+  cli::Options,
+  completions::{target, Target},
+};
+use clap::CommandFactory;
+use pretty_assertions::assert_eq;
+
+fn words(line: &str) -> Vec<String> {
+  line.split(' ').map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn test_target() {
+  let command = Options::command();
+
+  assert_eq!(
+    target(&command, &words("time-entries start --project ")),
+    Target::Value("project".to_string())
+  );
+  assert_eq!(
+    target(&command, &words("--format json reports summary --range ")),
+    Target::Value("range".to_string())
+  );
+
+  let Target::Names(names) =
+    target(&command, &words("time-entries start --project fkbr --"))
+  else {
+    panic!("expected names");
+  };
+
+  assert!(names.contains(&"--description".to_string()));
+  assert!(!names.contains(&"--range".to_string()));
+
+  let Target::Names(names) = target(&command, &words("--debug pro")) else {
+    panic!("expected names");
+  };
+
+  assert!(names.contains(&"projects".to_string()));
+
+  let Target::Names(names) = target(&command, &words("completions ")) else {
+    panic!("expected names");
+  };
+
+  // hidden subcommands are no candidates
+  assert_eq!(names, vec!["script".to_string()]);
+}
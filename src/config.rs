@@ -1,19 +1,318 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
 use std::path::Path;
 
 use config::Config;
 use dialoguer::{Confirm, Password};
 use serde::{Deserialize, Serialize};
+use toml_edit::{DocumentMut, ImDocument, Item, TableLike, Value};
 
-use crate::cli::APP_NAME;
+use crate::cache::DEFAULT_CACHE_TTL;
+use crate::cli::{BillableMarker, RotateToken, RoundingMode, SetSetting};
+use crate::client::{TogglApi, TogglClient};
+use crate::http_client::Fixtures;
+use crate::model::Project;
+use crate::paths::settings_file;
+use crate::paths::Paths;
+use crate::routing::ProjectRule;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct Settings {
+  /// Token of every request without a more specific token in [tokens]
+  #[serde(default)]
   pub api_token: String,
+
+  #[serde(default)]
+  pub tokens: Tokens,
+
+  #[serde(default)]
+  pub compliance: Compliance,
+
+  #[serde(default)]
+  pub timesheet: Timesheet,
+
+  /// First day (1-28) of the billing month, used by the ranges 'this-billing-month' and 'last-billing-month'
+  #[serde(default)]
+  pub billing_cycle_start_day: Option<u32>,
+
+  #[serde(default)]
+  pub aliases: Aliases,
+
+  #[serde(default)]
+  pub calendar: Calendar,
+
+  /// Projects of 'fbtoggl time-entries start' without --project by time of day and weekday
+  #[serde(default)]
+  pub project_rules: Vec<ProjectRule>,
+
+  /// Default of --date-format
+  #[serde(default)]
+  pub date_format: Option<String>,
+
+  /// Default of --locale
+  #[serde(default)]
+  pub locale: Option<String>,
+
+  /// Default of --billable-marker
+  #[serde(default)]
+  pub billable_marker: Option<BillableMarker>,
+
+  /// External formatters of '--format ext:<name>' (name = "executable"), they get the JSON output on stdin
+  #[serde(default)]
+  pub formatters: HashMap<String, String>,
+
+  /// Running time entries started more hours ago are highlighted as forgotten (default: 12)
+  #[serde(default)]
+  pub stale_timer_hours: Option<i64>,
+
+  /// Lifetime of cached workspaces, projects and clients, e.g. '1h' (default: '10m', '0s' disables the cache)
+  #[serde(default)]
+  pub cache_ttl: Option<String>,
+
+  #[serde(default)]
+  pub paths: Paths,
+
+  /// Keep the previous description of every 'time-entries edit' in a local history file, see 'fbtoggl history'
+  #[serde(default)]
+  pub description_history: bool,
+
+  /// Projects (names or ids) whose time entries are never billable, 'create' and 'start' ignore --billable for them and 'doctor' lists billable time entries on them
+  #[serde(default)]
+  pub non_billable_projects: Vec<String>,
+
+  /// Default of --round and --round-mode of 'time-entries list' and 'reports summary'
+  #[serde(default)]
+  pub rounding: Rounding,
+
+  /// Create missing projects of 'create' and 'start' instead of failing, like --create-missing-project
+  #[serde(default)]
+  pub create_missing_projects: bool,
+
+  /// Project of 'fbtoggl break' entries (default: "Break"), reports count them as break
+  #[serde(default)]
+  pub break_project: Option<String>,
+
+  /// Value of one unit of a currency in a common reference currency (e.g. EUR = 1.0, USD = 0.92) for --convert-to
+  #[serde(default)]
+  pub exchange_rates: HashMap<String, f64>,
+
+  /// Workspace of all commands without --workspace (default: the default workspace of the account)
+  #[serde(default)]
+  pub workspace_id: Option<u64>,
+
+  /// Project (id or name) of 'create' and 'start' without --project, 'start' prefers a matching [[project_rules]] entry
+  #[serde(default)]
+  pub default_project: Option<String>,
+
+  /// Tags of every time entry of 'create' and 'start', added to --tags unless --no-default-tags is given
+  #[serde(default)]
+  pub default_tags: Vec<String>,
+
+  /// Billable flag of 'create' and 'start' without --billable or --non-billable (default: true)
+  #[serde(default)]
+  pub default_billable: Option<bool>,
+}
+
+pub const DEFAULT_BREAK_PROJECT: &str = "Break";
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Rounding {
+  /// Increment like '15m', durations are not rounded without
+  #[serde(default)]
+  pub increment: Option<String>,
+
+  #[serde(default)]
+  pub mode: RoundingMode,
+}
+
+impl Settings {
+  pub fn cache_ttl(&self) -> anyhow::Result<std::time::Duration> {
+    match &self.cache_ttl {
+      Some(ttl) => humantime::parse_duration(ttl)
+        .map_err(|err| anyhow::anyhow!("Invalid cache_ttl '{ttl}': {err}")),
+      None => Ok(DEFAULT_CACHE_TTL),
+    }
+  }
+
+  /// --tags with the 'default_tags' which are not part of them yet
+  pub fn tags_with_defaults(
+    &self,
+    tags: Option<Vec<String>>,
+  ) -> Option<Vec<String>> {
+    if self.default_tags.is_empty() {
+      return tags;
+    }
+
+    let mut tags = tags.unwrap_or_default();
+
+    for default_tag in &self.default_tags {
+      if !tags.iter().any(|tag| tag.eq_ignore_ascii_case(default_tag)) {
+        tags.push(default_tag.to_owned());
+      }
+    }
+
+    Some(tags)
+  }
+
+  /// --non-billable, otherwise 'default_billable' unless --billable is given
+  pub fn is_non_billable(&self, billable: bool, non_billable: bool) -> bool {
+    non_billable || (!billable && self.default_billable == Some(false))
+  }
+
+  pub fn break_project(&self) -> &str {
+    self
+      .break_project
+      .as_deref()
+      .unwrap_or(DEFAULT_BREAK_PROJECT)
+  }
+}
+
+/// Tokens with less (read) or more (admin) privileges than `api_token`,
+/// reading requests use `read`, all others `admin`
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tokens {
+  pub read: Option<String>,
+  pub admin: Option<String>,
+}
+
+/// Projects of calendar events (`fbtoggl time-entries start --from-ical`),
+/// keyword in the event title = "project name"
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Calendar {
+  pub projects: HashMap<String, String>,
+}
+
+/// Whether the project is one of 'non_billable_projects' (by name or id)
+pub fn is_non_billable_project(
+  non_billable_projects: &[String],
+  project: &Project,
+) -> bool {
+  non_billable_projects.iter().any(|selector| {
+    selector.eq_ignore_ascii_case(&project.name)
+      || *selector == project.id.to_string()
+  })
+}
+
+/// Short names (alias = "real name") accepted wherever a name is expected
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Aliases {
+  pub workspaces: HashMap<String, String>,
+  pub projects: HashMap<String, String>,
+  pub clients: HashMap<String, String>,
+  pub tags: HashMap<String, String>,
+}
+
+/// Header fields of the printable timesheet (`fbtoggl export pdf`)
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Timesheet {
+  pub employee: Option<String>,
+  pub company: Option<String>,
+}
+
+/// Rules of the detailed report, defaults follow the german Arbeitszeitgesetz (ArbZG)
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Compliance {
+  pub max_work: MaxWorkRule,
+  pub earliest_start: EarliestStartRule,
+  pub latest_end: LatestEndRule,
+  pub breaks: BreakRules,
+}
+
+/// Work must not exceed `hours` per day
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaxWorkRule {
+  pub enabled: bool,
+  pub hours: i64,
+}
+
+impl Default for MaxWorkRule {
+  fn default() -> Self {
+    MaxWorkRule {
+      enabled: true,
+      hours: 10,
+    }
+  }
+}
+
+/// Work must not start before `hour`
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EarliestStartRule {
+  pub enabled: bool,
+  pub hour: u32,
+}
+
+impl Default for EarliestStartRule {
+  fn default() -> Self {
+    EarliestStartRule {
+      enabled: true,
+      hour: 6,
+    }
+  }
+}
+
+/// Work must not end after `hour`
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LatestEndRule {
+  pub enabled: bool,
+  pub hour: u32,
+}
+
+impl Default for LatestEndRule {
+  fn default() -> Self {
+    LatestEndRule {
+      enabled: true,
+      hour: 22,
+    }
+  }
+}
+
+/// https://www.gesetze-im-internet.de/arbzg/__4.html
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BreakRules {
+  pub enabled: bool,
+  pub rules: Vec<BreakRule>,
+}
+
+impl Default for BreakRules {
+  fn default() -> Self {
+    BreakRules {
+      enabled: true,
+      rules: vec![
+        BreakRule {
+          after_hours: 6,
+          minutes: 30,
+        },
+        BreakRule {
+          after_hours: 9,
+          minutes: 45,
+        },
+      ],
+    }
+  }
+}
+
+/// Working more than `after_hours` requires a break of at least `minutes`
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BreakRule {
+  pub after_hours: i64,
+  pub minutes: i64,
 }
 
 pub fn init_settings_file() -> anyhow::Result<()> {
-  let xdg_dirs = xdg::BaseDirectories::with_prefix(APP_NAME)?;
-  let settings_file = xdg_dirs.get_config_file("settings.toml");
+  let settings_file = settings_file()?;
 
   if settings_file.exists() {
     if Confirm::new()
@@ -27,7 +326,10 @@ pub fn init_settings_file() -> anyhow::Result<()> {
       println!("Do nothing!");
     }
   } else {
-    xdg_dirs.place_config_file(&settings_file)?;
+    if let Some(dir) = settings_file.parent() {
+      std::fs::create_dir_all(dir)?;
+    }
+
     write_config_file(&settings_file)?;
   }
 
@@ -40,7 +342,10 @@ fn write_config_file(path: &Path) -> anyhow::Result<()> {
     .allow_empty_password(false)
     .interact()?;
 
-  let settings = Settings { api_token };
+  let settings = Settings {
+    api_token,
+    ..Default::default()
+  };
   let content = toml::to_string_pretty(&settings)?;
 
   std::fs::write(path, content)?;
@@ -50,13 +355,520 @@ fn write_config_file(path: &Path) -> anyhow::Result<()> {
   Ok(())
 }
 
+/// Resets the API token in use at Toggl, replaces it in settings.toml and
+/// verifies the new token with '/me'
+pub fn rotate_api_token(
+  debug: bool,
+  rotate_token: &RotateToken,
+  settings: &Settings,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  if let Some(name) = API_TOKEN_VARIABLES
+    .iter()
+    .find(|name| std::env::var_os(name).is_some())
+  {
+    return Err(anyhow::anyhow!(
+      "The API token is set by {name}, rotate it on the profile page of Toggl and update {name}"
+    ));
+  }
+
+  let settings_file = settings_file()?;
+  let content = std::fs::read_to_string(&settings_file).map_err(|err| {
+    anyhow::anyhow!("Cannot read {}: {err}", settings_file.display())
+  })?;
+
+  // resetting is no read-only request, so it is sent with the admin token
+  let old_token = settings
+    .tokens
+    .admin
+    .as_deref()
+    .unwrap_or(&settings.api_token);
+
+  // fails before the reset, if the token cannot be replaced afterwards
+  replace_api_token(&content, old_token, old_token)?;
+
+  if !rotate_token.yes
+    && !Confirm::new()
+      .with_prompt(
+        "Reset the API token? The current one stops working immediately",
+      )
+      .default(false)
+      .interact()?
+  {
+    println!("Do nothing!");
+    return Ok(());
+  }
+
+  let new_token = client.reset_api_token(debug)?;
+
+  // the old token is dead already, the new one must not get lost
+  replace_api_token(&content, old_token, &new_token)
+    .and_then(|content| write_settings_file(&settings_file, &content))
+    .map_err(|err| {
+      anyhow::anyhow!(
+        "Cannot save the new API token {new_token} in {}, replace the old one manually: {err}",
+        settings_file.display()
+      )
+    })?;
+
+  println!("Replaced the API token in {}", settings_file.display());
+
+  let me = TogglClient::new(new_token.into(), Fixtures::None)?
+    .get_me(debug)
+    .map_err(|err| anyhow::anyhow!("The new API token does not work: {err}"))?;
+
+  println!(
+    "Verified the new API token of {}",
+    me.fullname.as_deref().unwrap_or("your account")
+  );
+
+  Ok(())
+}
+
+/// settings.toml with the `old` token of 'api_token', 'tokens.read' and
+/// 'tokens.admin' replaced by `new`, everything else is kept as it is
+pub fn replace_api_token(
+  content: &str,
+  old: &str,
+  new: &str,
+) -> anyhow::Result<String> {
+  let mut document = content.parse::<DocumentMut>()?;
+  let mut replaced = false;
+
+  let mut replace = |item: Option<&mut Item>| {
+    if let Some(item) = item.filter(|item| item.as_str() == Some(old)) {
+      *item = toml_edit::value(new);
+      replaced = true;
+    }
+  };
+
+  replace(document.get_mut("api_token"));
+
+  if let Some(tokens) =
+    document.get_mut("tokens").and_then(Item::as_table_like_mut)
+  {
+    replace(tokens.get_mut("read"));
+    replace(tokens.get_mut("admin"));
+  }
+
+  if !replaced {
+    return Err(anyhow::anyhow!(
+      "The API token in use is not part of settings.toml"
+    ));
+  }
+
+  Ok(document.to_string())
+}
+
+/// Sets `key` of settings.toml to `value`, invalid settings are not written
+pub fn set_setting(set_setting: &SetSetting) -> anyhow::Result<()> {
+  let settings_file = settings_file()?;
+  let content = std::fs::read_to_string(&settings_file).unwrap_or_default();
+
+  let content = with_setting(&content, &set_setting.key, &set_setting.value)?;
+
+  if let Some(problem) = validate_settings(&content).first() {
+    return Err(anyhow::anyhow!(
+      "Cannot set '{}': {problem}",
+      set_setting.key
+    ));
+  }
+
+  if let Some(dir) = settings_file.parent() {
+    std::fs::create_dir_all(dir)?;
+  }
+
+  write_settings_file(&settings_file, &content)?;
+
+  println!("Set '{}' in {}", set_setting.key, settings_file.display());
+
+  Ok(())
+}
+
+/// settings.toml with `key` (nested keys separated by '.') set to `value`,
+/// which is a TOML value like `42` or `true`, otherwise a string
+pub fn with_setting(
+  content: &str,
+  key: &str,
+  value: &str,
+) -> anyhow::Result<String> {
+  let mut document = content.parse::<DocumentMut>()?;
+  let value = value
+    .parse::<Value>()
+    .unwrap_or_else(|_| Value::from(value));
+
+  let mut item = document.as_item_mut();
+
+  for part in key.split('.') {
+    if item.is_none() {
+      *item = toml_edit::table();
+    }
+
+    let Some(table) = item.as_table_like_mut() else {
+      return Err(anyhow::anyhow!("'{key}' is no table of settings.toml"));
+    };
+
+    item = table.entry(part).or_insert(Item::None);
+  }
+
+  *item = Item::Value(value);
+
+  Ok(document.to_string())
+}
+
+/// Writes a temporary file next to settings.toml and renames it, so the
+/// settings are never half written
+fn write_settings_file(path: &Path, content: &str) -> anyhow::Result<()> {
+  let temporary_file = path.with_extension("toml.tmp");
+
+  std::fs::write(&temporary_file, content)?;
+  std::fs::rename(&temporary_file, path)?;
+
+  Ok(())
+}
+
+/// Environment variables of the API token, the first one set wins
+pub const API_TOKEN_VARIABLES: [&str; 2] =
+  ["FBTOGGL_API_TOKEN", "TOGGL_API_TOKEN"];
+
+/// API token of the environment, e.g. in containers without settings.toml
+pub fn api_token_from(
+  variable: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+  API_TOKEN_VARIABLES
+    .iter()
+    .filter_map(|name| variable(name))
+    .find(|token| !token.trim().is_empty())
+}
+
+/// settings.toml, an API token of the environment takes precedence over
+/// 'api_token' and makes the file optional
 pub fn read_settings() -> anyhow::Result<Settings> {
-  let xdg_dirs = xdg::BaseDirectories::with_prefix(APP_NAME)?;
-  let settings_file = xdg_dirs.get_config_file("settings.toml");
+  let settings_file = settings_file()?;
+  let api_token = api_token_from(|name| std::env::var(name).ok());
+
+  let mut builder = Config::builder().add_source(
+    config::File::from(settings_file).required(api_token.is_none()),
+  );
+
+  if let Some(api_token) = api_token {
+    builder = builder.set_override("api_token", api_token)?;
+  }
+
+  let settings = builder.build()?;
+
+  settings.try_deserialize().map_err(|err| {
+    anyhow::anyhow!("{err}, see 'fbtoggl settings validate' for all problems")
+  })
+}
+
+/// Prints every problem of settings.toml, fails if there is any
+pub fn validate_settings_file() -> anyhow::Result<()> {
+  let settings_file = settings_file()?;
+
+  let content = std::fs::read_to_string(&settings_file).map_err(|err| {
+    anyhow::anyhow!("Cannot read {}: {err}", settings_file.display())
+  })?;
+
+  let problems = validate_settings(&content);
+
+  if problems.is_empty() {
+    println!("{} is valid", settings_file.display());
+    return Ok(());
+  }
+
+  for problem in &problems {
+    println!("{problem}");
+  }
+
+  Err(anyhow::anyhow!(
+    "{} problem(s) in {}",
+    problems.len(),
+    settings_file.display()
+  ))
+}
+
+/// Problem of settings.toml, found by 'fbtoggl settings validate'
+#[derive(Debug, PartialEq)]
+pub struct SettingsProblem {
+  pub line: Option<usize>,
+
+  /// Dotted path of the key, e.g. 'compliance.max_work.hours'
+  pub key: Option<String>,
+
+  pub message: String,
+
+  /// Closest known key of an unknown one
+  pub suggestion: Option<String>,
+}
+
+impl Display for SettingsProblem {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    if let Some(line) = self.line {
+      write!(f, "line {line}: ")?;
+    }
+
+    if let Some(key) = &self.key {
+      write!(f, "{key}: ")?;
+    }
+
+    write!(f, "{}", self.message)?;
+
+    if let Some(suggestion) = &self.suggestion {
+      write!(f, " (did you mean '{suggestion}'?)")?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Stops the validation of settings files full of errors
+const MAX_SETTINGS_PROBLEMS: usize = 100;
+
+/// Unknown keys, type errors and syntax errors of the settings, every problem
+/// is left out to find the next one until the rest is valid
+pub fn validate_settings(content: &str) -> Vec<SettingsProblem> {
+  let original = KeySpans::new(content);
+
+  let mut rest = content.to_string();
+  let mut problems = vec![];
+
+  while problems.len() < MAX_SETTINGS_PROBLEMS {
+    let Err(err) = toml::from_str::<Settings>(&rest) else {
+      break;
+    };
+
+    let path = err
+      .span()
+      .and_then(|span| KeySpans::new(&rest).path_at(&span));
+
+    // lines of the original content, the rest lacks the earlier problems
+    let line = path
+      .as_ref()
+      .and_then(|path| original.line_of(path))
+      .or_else(|| err.span().map(|span| line_at(&rest, span.start)));
+
+    let suggestion = suggested_key(err.message());
+
+    // the suggestion replaces the long list of expected keys
+    let message = match (&suggestion, err.message().split_once(", expected")) {
+      (Some(_), Some((unknown, _))) => unknown.to_string(),
+      _ => err.message().to_string(),
+    };
+
+    problems.push(SettingsProblem {
+      line,
+      key: path.as_ref().map(|path| format_key_path(path)),
+      message,
+      suggestion,
+    });
+
+    match path.and_then(|path| without_key(&rest, &path)) {
+      Some(without) => rest = without,
+      None => break,
+    }
+  }
+
+  problems
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum KeySegment {
+  Key(String),
+  Index(usize),
+}
+
+fn format_key_path(path: &[KeySegment]) -> String {
+  path
+    .iter()
+    .fold(String::new(), |formatted, segment| match segment {
+      KeySegment::Key(key) if formatted.is_empty() => key.to_owned(),
+      KeySegment::Key(key) => format!("{formatted}.{key}"),
+      KeySegment::Index(index) => format!("{formatted}[{index}]"),
+    })
+}
+
+fn line_at(content: &str, offset: usize) -> usize {
+  content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+struct KeySpan {
+  path: Vec<KeySegment>,
+  key: Option<Range<usize>>,
+  value: Option<Range<usize>>,
+}
+
+/// Spans of every key and value of a TOML document
+struct KeySpans<'a> {
+  content: &'a str,
+  entries: Vec<KeySpan>,
+}
+
+impl<'a> KeySpans<'a> {
+  fn new(content: &'a str) -> KeySpans<'a> {
+    let mut key_spans = KeySpans {
+      content,
+      entries: vec![],
+    };
+
+    if let Ok(document) = ImDocument::parse(content) {
+      key_spans.collect_table(document.as_table(), &[]);
+    }
+
+    key_spans
+  }
+
+  fn collect_table(&mut self, table: &dyn TableLike, path: &[KeySegment]) {
+    for (key, item) in table.iter() {
+      let mut item_path = path.to_vec();
+      item_path.push(KeySegment::Key(key.to_string()));
+
+      let key_span = table.key(key).and_then(|key| key.span());
+
+      self.entries.push(KeySpan {
+        path: item_path.to_owned(),
+        key: key_span,
+        value: item.span(),
+      });
+      self.collect_item(item, &item_path);
+    }
+  }
+
+  fn collect_item(&mut self, item: &Item, path: &[KeySegment]) {
+    match item {
+      Item::ArrayOfTables(tables) => {
+        for (index, table) in tables.iter().enumerate() {
+          let mut table_path = path.to_vec();
+          table_path.push(KeySegment::Index(index));
+
+          self.entries.push(KeySpan {
+            path: table_path.to_owned(),
+            key: None,
+            value: table.span(),
+          });
+          self.collect_table(table, &table_path);
+        }
+      }
+      Item::Value(Value::Array(values)) => {
+        for (index, value) in values.iter().enumerate() {
+          let mut value_path = path.to_vec();
+          value_path.push(KeySegment::Index(index));
+
+          self.entries.push(KeySpan {
+            path: value_path.to_owned(),
+            key: None,
+            value: value.span(),
+          });
+
+          if let Some(table) = value.as_inline_table() {
+            self.collect_table(table, &value_path);
+          }
+        }
+      }
+      item => {
+        if let Some(table) = item.as_table_like() {
+          self.collect_table(table, path);
+        }
+      }
+    }
+  }
+
+  /// Key of the span, unknown keys point to the key and type errors to
+  /// the (innermost) value
+  fn path_at(&self, span: &Range<usize>) -> Option<Vec<KeySegment>> {
+    self
+      .entries
+      .iter()
+      .find(|entry| entry.key.as_ref() == Some(span))
+      .or_else(|| {
+        self
+          .entries
+          .iter()
+          .filter(|entry| {
+            entry.value.as_ref().is_some_and(|value| {
+              value.start <= span.start && span.end <= value.end
+            })
+          })
+          .min_by_key(|entry| entry.value.as_ref().map(|value| value.len()))
+      })
+      .map(|entry| entry.path.to_owned())
+  }
+
+  fn line_of(&self, path: &[KeySegment]) -> Option<usize> {
+    self
+      .entries
+      .iter()
+      .find(|entry| entry.path == path)
+      .and_then(|entry| entry.key.as_ref().or(entry.value.as_ref()))
+      .map(|span| line_at(self.content, span.start))
+  }
+}
+
+/// The content without the key (and its value)
+fn without_key(content: &str, path: &[KeySegment]) -> Option<String> {
+  let mut document = content.parse::<DocumentMut>().ok()?;
+
+  remove_from_table(document.as_table_mut(), path).then(|| document.to_string())
+}
+
+fn remove_from_table(table: &mut dyn TableLike, path: &[KeySegment]) -> bool {
+  match path {
+    [KeySegment::Key(key)] => table.remove(key).is_some(),
+    [KeySegment::Key(key), rest @ ..] => table
+      .get_mut(key)
+      .is_some_and(|item| remove_from_item(item, rest)),
+    _ => false,
+  }
+}
+
+fn remove_from_item(item: &mut Item, path: &[KeySegment]) -> bool {
+  match (item, path) {
+    (Item::ArrayOfTables(tables), [KeySegment::Index(index), rest @ ..]) => {
+      if !rest.is_empty() {
+        tables
+          .get_mut(*index)
+          .is_some_and(|table| remove_from_table(table, rest))
+      } else if *index < tables.len() {
+        tables.remove(*index);
+        true
+      } else {
+        false
+      }
+    }
+    (
+      Item::Value(Value::Array(values)),
+      [KeySegment::Index(index), rest @ ..],
+    ) => {
+      if !rest.is_empty() {
+        values
+          .get_mut(*index)
+          .and_then(|value| value.as_inline_table_mut())
+          .is_some_and(|table| remove_from_table(table, rest))
+      } else if *index < values.len() {
+        values.remove(*index);
+        true
+      } else {
+        false
+      }
+    }
+    (item, path) => item
+      .as_table_like_mut()
+      .is_some_and(|table| remove_from_table(table, path)),
+  }
+}
 
-  let settings = Config::builder()
-    .add_source(config::File::from(settings_file))
-    .build()?;
+/// Closest expected key of an 'unknown field' error, e.g. 'api_token' for
+/// 'api_tokn'
+fn suggested_key(message: &str) -> Option<String> {
+  let rest = message.strip_prefix("unknown field `")?;
+  let (unknown, expected) = rest.split_once('`')?;
 
-  Ok(settings.try_deserialize()?)
+  expected
+    .split('`')
+    .skip(1)
+    .step_by(2)
+    .map(|key| (key, strsim::jaro_winkler(unknown, key)))
+    .filter(|(_, similarity)| *similarity >= 0.8)
+    .max_by(|a, b| a.1.total_cmp(&b.1))
+    .map(|(key, _)| key.to_string())
 }
@@ -0,0 +1,214 @@
+use crate::config::{
+  api_token_from, replace_api_token, validate_settings, with_setting, Settings,
+  SettingsProblem,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_validate_settings() {
+  let problems = validate_settings(
+    r#"api_tokn = "cb7bf7efa6d652046abd2f7d84ee18c1"
+stale_timer_hours = "twelve"
+
+[compliance.max_work]
+hours = 10
+enabled = true
+hourz = 8
+
+[[project_rules]]
+project = "Standup"
+befor = "10:00"
+
+[fkbr]
+sxoe = true
+"#,
+  );
+
+  assert_eq!(
+    problems,
+    vec![
+      SettingsProblem {
+        line: Some(1),
+        key: Some("api_tokn".to_string()),
+        message: "unknown field `api_tokn`".to_string(),
+        suggestion: Some("api_token".to_string()),
+      },
+      SettingsProblem {
+        line: Some(2),
+        key: Some("stale_timer_hours".to_string()),
+        message: "invalid type: string \"twelve\", expected i64".to_string(),
+        suggestion: None,
+      },
+      SettingsProblem {
+        line: Some(7),
+        key: Some("compliance.max_work.hourz".to_string()),
+        message: "unknown field `hourz`".to_string(),
+        suggestion: Some("hours".to_string()),
+      },
+      SettingsProblem {
+        line: Some(11),
+        key: Some("project_rules[0].befor".to_string()),
+        message: "unknown field `befor`".to_string(),
+        suggestion: Some("before".to_string()),
+      },
+      SettingsProblem {
+        line: Some(13),
+        key: Some("fkbr".to_string()),
+        message: "unknown field `fkbr`, expected one of `api_token`, `tokens`, `compliance`, `timesheet`, `billing_cycle_start_day`, `aliases`, `calendar`, `project_rules`, `date_format`, `locale`, `billable_marker`, `formatters`, `stale_timer_hours`, `cache_ttl`, `paths`, `description_history`, `non_billable_projects`, `rounding`, `create_missing_projects`, `break_project`, `exchange_rates`, `workspace_id`, `default_project`, `default_tags`, `default_billable`".to_string(),
+        suggestion: None,
+      },
+    ]
+  );
+
+  assert_eq!(
+    problems[0].to_string(),
+    "line 1: api_tokn: unknown field `api_tokn` (did you mean 'api_token'?)"
+  );
+}
+
+#[test]
+fn test_validate_valid_settings() {
+  assert_eq!(
+    validate_settings(
+      r#"api_token = "cb7bf7efa6d652046abd2f7d84ee18c1"
+
+[aliases.projects]
+fkbr = "fkbr.org"
+
+[compliance.breaks]
+rules = [{ after_hours = 6, minutes = 30 }]
+"#
+    ),
+    vec![]
+  );
+}
+
+#[test]
+fn test_validate_settings_syntax_error() {
+  let problems =
+    validate_settings("api_token = \"fkbr\"\nstale_timer_hours =\n");
+
+  assert_eq!(problems.len(), 1);
+  assert_eq!(problems[0].line, Some(2));
+  assert_eq!(problems[0].key, None);
+}
+
+#[test]
+fn test_api_token_from_environment() {
+  let env = |variables: &'static [(&'static str, &'static str)]| {
+    move |name: &str| {
+      variables
+        .iter()
+        .find(|(variable, _)| *variable == name)
+        .map(|(_, value)| value.to_string())
+    }
+  };
+
+  assert_eq!(api_token_from(env(&[])), None);
+  assert_eq!(
+    api_token_from(env(&[("TOGGL_API_TOKEN", "toggl")])),
+    Some("toggl".to_string())
+  );
+  assert_eq!(
+    api_token_from(env(&[
+      ("TOGGL_API_TOKEN", "toggl"),
+      ("FBTOGGL_API_TOKEN", "fbtoggl")
+    ])),
+    Some("fbtoggl".to_string())
+  );
+  assert_eq!(
+    api_token_from(env(&[
+      ("FBTOGGL_API_TOKEN", " "),
+      ("TOGGL_API_TOKEN", "toggl")
+    ])),
+    Some("toggl".to_string())
+  );
+}
+
+#[test]
+fn test_replace_api_token() -> anyhow::Result<()> {
+  let content = r#"# personal token
+api_token = "old"
+
+[tokens]
+read = "read-only"
+admin = "old"
+"#;
+
+  assert_eq!(
+    replace_api_token(content, "old", "new")?,
+    r#"# personal token
+api_token = "new"
+
+[tokens]
+read = "read-only"
+admin = "new"
+"#
+  );
+
+  assert_eq!(
+    replace_api_token(content, "unknown", "new")
+      .unwrap_err()
+      .to_string(),
+    "The API token in use is not part of settings.toml"
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_with_setting() -> anyhow::Result<()> {
+  let content = r#"# personal token
+api_token = "token"
+"#;
+
+  let content = with_setting(content, "workspace_id", "1234567")?;
+  let content = with_setting(&content, "tokens.read", "read-only")?;
+  let content = with_setting(&content, "api_token", "new")?;
+
+  assert_eq!(
+    content,
+    r#"# personal token
+api_token = "new"
+workspace_id = 1234567
+
+[tokens]
+read = "read-only"
+"#
+  );
+
+  assert_eq!(validate_settings(&content), vec![]);
+
+  assert_eq!(
+    with_setting(&content, "api_token.read", "x")
+      .unwrap_err()
+      .to_string(),
+    "'api_token.read' is no table of settings.toml"
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_defaults_of_time_entries() {
+  let settings = Settings {
+    default_tags: vec!["cost-center-42".to_string(), "remote".to_string()],
+    default_billable: Some(false),
+    ..Default::default()
+  };
+
+  assert_eq!(
+    settings.tags_with_defaults(None),
+    Some(vec!["cost-center-42".to_string(), "remote".to_string()])
+  );
+  assert_eq!(
+    settings.tags_with_defaults(Some(vec!["Remote".to_string()])),
+    Some(vec!["Remote".to_string(), "cost-center-42".to_string()])
+  );
+  assert_eq!(Settings::default().tags_with_defaults(None), None);
+
+  assert!(settings.is_non_billable(false, false));
+  assert!(!settings.is_non_billable(true, false));
+  assert!(!Settings::default().is_non_billable(false, false));
+  assert!(Settings::default().is_non_billable(false, true));
+}
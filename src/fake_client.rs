@@ -0,0 +1,329 @@
+use std::rc::Rc;
+
+use crate::client::TogglApi;
+use crate::config::Settings;
+use crate::model::BulkEditResult;
+use crate::model::Client;
+use crate::model::Me;
+use crate::model::PatchOperation;
+use crate::model::Project;
+use crate::model::ProjectUpdate;
+use crate::model::Range;
+use crate::model::Tag;
+use crate::model::Task;
+use crate::model::TimeEntry;
+use crate::model::Workspace;
+use crate::model::WorkspaceUser;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Local;
+use serde_json::json;
+
+fn unsupported<T>(call: &str) -> anyhow::Result<T> {
+  Err(anyhow::anyhow!("{call} is not supported"))
+}
+
+/// Toggl Track API without HTTP for tests: answers the workspaces and the
+/// user from its fields, every other call fails
+#[derive(Default)]
+pub struct FakeClient {
+  pub workspace: Option<String>,
+  pub workspaces: Vec<Workspace>,
+  pub default_workspace_id: u64,
+}
+
+impl TogglApi for FakeClient {
+  fn workspace(&self) -> Option<&str> {
+    self.workspace.as_deref()
+  }
+
+  fn with_workspace(&self, workspace: Option<String>) -> Box<dyn TogglApi> {
+    Box::new(FakeClient {
+      workspace,
+      workspaces: self.workspaces.clone(),
+      default_workspace_id: self.default_workspace_id,
+    })
+  }
+
+  fn settings(&self) -> Rc<Settings> {
+    Rc::default()
+  }
+
+  fn get_workspace_clients(
+    &self,
+    _debug: bool,
+    _include_archived: bool,
+    _workspace_id: u64,
+  ) -> anyhow::Result<Option<Vec<Client>>> {
+    unsupported("get_workspace_clients")
+  }
+
+  fn get_workspace_tags(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+  ) -> anyhow::Result<Vec<Tag>> {
+    unsupported("get_workspace_tags")
+  }
+
+  fn create_tag(
+    &self,
+    _debug: bool,
+    _name: &str,
+    _workspace_id: u64,
+  ) -> anyhow::Result<Tag> {
+    unsupported("create_tag")
+  }
+
+  fn update_tag(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _tag_id: u64,
+    _name: &str,
+  ) -> anyhow::Result<Tag> {
+    unsupported("update_tag")
+  }
+
+  fn delete_tag(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _tag_id: u64,
+  ) -> anyhow::Result<()> {
+    unsupported("delete_tag")
+  }
+
+  fn get_project_tasks(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _project_id: u64,
+  ) -> anyhow::Result<Vec<Task>> {
+    unsupported("get_project_tasks")
+  }
+
+  fn create_task(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _project_id: u64,
+    _name: &str,
+  ) -> anyhow::Result<Task> {
+    unsupported("create_task")
+  }
+
+  fn get_time_entries(
+    &self,
+    _debug: bool,
+    _range: &Range,
+  ) -> anyhow::Result<Vec<TimeEntry>> {
+    unsupported("get_time_entries")
+  }
+
+  fn get_workspaces(&self, _debug: bool) -> anyhow::Result<Vec<Workspace>> {
+    Ok(self.workspaces.clone())
+  }
+
+  fn get_me(&self, _debug: bool) -> anyhow::Result<Me> {
+    Ok(serde_json::from_value(json!({
+      "default_workspace_id": self.default_workspace_id
+    }))?)
+  }
+
+  fn reset_api_token(&self, _debug: bool) -> anyhow::Result<String> {
+    unsupported("reset_api_token")
+  }
+
+  fn get_workspace_users(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+  ) -> anyhow::Result<Vec<WorkspaceUser>> {
+    unsupported("get_workspace_users")
+  }
+
+  fn get_workspace_projects(
+    &self,
+    _debug: bool,
+    _include_archived: bool,
+    _workspace_id: u64,
+  ) -> anyhow::Result<Vec<Project>> {
+    unsupported("get_workspace_projects")
+  }
+
+  fn get_project_templates(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+  ) -> anyhow::Result<Vec<Project>> {
+    unsupported("get_project_templates")
+  }
+
+  fn create_project(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _name: &str,
+    _client_id: Option<u64>,
+    _template_id: Option<u64>,
+  ) -> anyhow::Result<Project> {
+    unsupported("create_project")
+  }
+
+  fn update_project(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _project_id: u64,
+    _project: &ProjectUpdate,
+  ) -> anyhow::Result<Project> {
+    unsupported("update_project")
+  }
+
+  fn delete_project(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _project_id: u64,
+  ) -> anyhow::Result<()> {
+    unsupported("delete_project")
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn create_time_entry(
+    &self,
+    _debug: bool,
+    _description: &Option<String>,
+    _workspace_id: u64,
+    _tags: &Option<Vec<String>>,
+    _duration: Duration,
+    _start: DateTime<Local>,
+    _project_id: u64,
+    _task_id: Option<u64>,
+    _non_billable: bool,
+  ) -> anyhow::Result<TimeEntry> {
+    unsupported("create_time_entry")
+  }
+
+  fn create_client(
+    &self,
+    _debug: bool,
+    _name: &str,
+    _workspace_id: u64,
+  ) -> anyhow::Result<Client> {
+    unsupported("create_client")
+  }
+
+  fn get_client(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _client_id: u64,
+  ) -> anyhow::Result<Client> {
+    unsupported("get_client")
+  }
+
+  fn delete_client(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _client_id: u64,
+  ) -> anyhow::Result<()> {
+    unsupported("delete_client")
+  }
+
+  fn update_client(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _client_id: u64,
+    _name: &str,
+  ) -> anyhow::Result<Client> {
+    unsupported("update_client")
+  }
+
+  fn archive_client(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _client_id: u64,
+  ) -> anyhow::Result<()> {
+    unsupported("archive_client")
+  }
+
+  fn restore_client(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _client_id: u64,
+    _restore_projects: bool,
+  ) -> anyhow::Result<Client> {
+    unsupported("restore_client")
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn start_time_entry(
+    &self,
+    _debug: bool,
+    _start: DateTime<Local>,
+    _workspace_id: u64,
+    _description: &Option<String>,
+    _tags: &Option<Vec<String>>,
+    _project_id: u64,
+    _task_id: Option<u64>,
+    _non_billable: bool,
+  ) -> anyhow::Result<TimeEntry> {
+    unsupported("start_time_entry")
+  }
+
+  fn stop_time_entry(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _time_entry_id: u64,
+  ) -> anyhow::Result<TimeEntry> {
+    unsupported("stop_time_entry")
+  }
+
+  fn delete_time_entry(
+    &self,
+    _debug: bool,
+    _time_entry_id: u64,
+  ) -> anyhow::Result<()> {
+    unsupported("delete_time_entry")
+  }
+
+  fn get_time_entry(
+    &self,
+    _debug: bool,
+    _time_entry_id: u64,
+  ) -> anyhow::Result<TimeEntry> {
+    unsupported("get_time_entry")
+  }
+
+  fn get_current_time_entry(
+    &self,
+    _debug: bool,
+  ) -> anyhow::Result<Option<TimeEntry>> {
+    unsupported("get_current_time_entry")
+  }
+
+  fn update_time_entry(
+    &self,
+    _debug: bool,
+    _time_entry: &TimeEntry,
+  ) -> anyhow::Result<TimeEntry> {
+    unsupported("update_time_entry")
+  }
+
+  fn bulk_edit_time_entries(
+    &self,
+    _debug: bool,
+    _workspace_id: u64,
+    _time_entry_ids: &[u64],
+    _operations: &[PatchOperation],
+  ) -> anyhow::Result<BulkEditResult> {
+    unsupported("bulk_edit_time_entries")
+  }
+}
@@ -0,0 +1,22 @@
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+
+use anyhow::anyhow;
+
+/// Pipes the JSON output of the command into the external formatter,
+/// returns the exit code of the formatter
+pub fn run(formatter: &str, json: &str) -> anyhow::Result<i32> {
+  let mut formatted = Command::new(formatter)
+    .stdin(Stdio::piped())
+    .spawn()
+    .map_err(|err| anyhow!("Cannot run formatter '{formatter}': {err}"))?;
+
+  formatted
+    .stdin
+    .take()
+    .ok_or_else(|| anyhow!("Cannot write to formatter '{formatter}'"))?
+    .write_all(json.as_bytes())?;
+
+  Ok(formatted.wait()?.code().unwrap_or(1))
+}
@@ -0,0 +1,42 @@
+use crate::{
+  cli::{parse_format, Format, OutputFormat},
+  formatter::run,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_parse_format() -> anyhow::Result<()> {
+  assert_eq!(parse_format("table")?, OutputFormat::Builtin(Format::Table));
+  assert_eq!(parse_format("JSON")?, OutputFormat::Builtin(Format::Json));
+  assert_eq!(
+    parse_format("ext:md")?,
+    OutputFormat::External("md".to_string())
+  );
+
+  assert!(parse_format("ext:").is_err());
+  assert!(parse_format("markdown").is_err());
+
+  Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_pipes_json_into_formatter() -> anyhow::Result<()> {
+  use std::os::unix::fs::PermissionsExt;
+
+  // 'grep -q' fails unless the JSON arrives on stdin
+  let script = std::env::temp_dir()
+    .join(format!("fbtoggl-formatter-{}.sh", std::process::id()));
+  std::fs::write(&script, "#!/bin/sh\ngrep -q '\"id\": 42'\n")?;
+  std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+
+  let formatter = script.to_string_lossy();
+
+  assert_eq!(run(&formatter, "{\n  \"id\": 42\n}\n")?, 0);
+  assert_eq!(run(&formatter, "{}\n")?, 1);
+  assert!(run("/nonexistent/fbtoggl-formatter", "{}").is_err());
+
+  std::fs::remove_file(&script)?;
+
+  Ok(())
+}
@@ -0,0 +1,73 @@
+use std::io::Write;
+use std::path::Path;
+
+use chrono::DateTime;
+use chrono::Local;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::paths::history_file;
+
+/// Description of a time entry before it was changed by 'time-entries edit',
+/// Toggl itself keeps no history of time entries
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct DescriptionVersion {
+  pub time_entry_id: u64,
+  pub changed_at: DateTime<Local>,
+  pub description: Option<String>,
+}
+
+pub fn append(version: &DescriptionVersion) -> anyhow::Result<()> {
+  append_to(&history_file()?, version)
+}
+
+/// The history file is append-only, one JSON line per version
+pub(crate) fn append_to(
+  path: &Path,
+  version: &DescriptionVersion,
+) -> anyhow::Result<()> {
+  if let Some(dir) = path.parent() {
+    std::fs::create_dir_all(dir)?;
+  }
+
+  let mut file = std::fs::OpenOptions::new()
+    .create(true)
+    .append(true)
+    .open(path)?;
+
+  writeln!(file, "{}", serde_json::to_string(version)?)?;
+
+  Ok(())
+}
+
+pub fn versions(time_entry_id: u64) -> anyhow::Result<Vec<DescriptionVersion>> {
+  versions_from(&history_file()?, time_entry_id)
+}
+
+/// Previous descriptions of the time entry, oldest first
+pub(crate) fn versions_from(
+  path: &Path,
+  time_entry_id: u64,
+) -> anyhow::Result<Vec<DescriptionVersion>> {
+  if !path.exists() {
+    return Ok(vec![]);
+  }
+
+  let mut versions = vec![];
+
+  for line in std::fs::read_to_string(path)?.lines() {
+    if line.trim().is_empty() {
+      continue;
+    }
+
+    let version = serde_json::from_str::<DescriptionVersion>(line)?;
+
+    if version.time_entry_id == time_entry_id {
+      versions.push(version);
+    }
+  }
+
+  versions.sort_by_key(|version| version.changed_at);
+
+  Ok(versions)
+}
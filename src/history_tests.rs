@@ -0,0 +1,38 @@
+use crate::history::{append_to, versions_from, DescriptionVersion};
+use chrono::{DateTime, Local};
+use pretty_assertions::assert_eq;
+use std::str::FromStr;
+
+#[test]
+fn test_versions_of_time_entry() -> anyhow::Result<()> {
+  let path = std::env::temp_dir()
+    .join(format!("fbtoggl-history-{}.jsonl", std::process::id()));
+
+  let version = |time_entry_id, changed_at, description: &str| {
+    Ok::<_, anyhow::Error>(DescriptionVersion {
+      time_entry_id,
+      changed_at: DateTime::<Local>::from_str(changed_at)?,
+      description: Some(description.to_string()),
+    })
+  };
+
+  assert_eq!(versions_from(&path, 1)?, vec![]);
+
+  append_to(&path, &version(1, "2021-11-22T10:00:00+01:00", "fkbr")?)?;
+  append_to(&path, &version(2, "2021-11-22T11:00:00+01:00", "sxoe")?)?;
+  append_to(&path, &version(1, "2021-11-23T09:00:00+01:00", "kaese")?)?;
+
+  let versions = versions_from(&path, 1)?;
+
+  std::fs::remove_file(&path)?;
+
+  assert_eq!(
+    versions,
+    vec![
+      version(1, "2021-11-22T10:00:00+01:00", "fkbr")?,
+      version(1, "2021-11-23T09:00:00+01:00", "kaese")?,
+    ]
+  );
+
+  Ok(())
+}
@@ -0,0 +1,606 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::config::Settings;
+use crate::progress;
+use anyhow::anyhow;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use colored::Colorize;
+use minreq::Method;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use url::Url;
+
+const AUTHORIZATION: &str = "Authorization";
+
+// Response fields which must not end up in recorded fixtures
+const REDACTED_KEYS: [&str; 6] = [
+  "api_token",
+  "email",
+  "fullname",
+  "ical_url",
+  "image_url",
+  "openid_email",
+];
+
+const REDACTED: &str = "REDACTED";
+
+/// Attempts of a request before a rate limit or server error is returned
+const REQUEST_ATTEMPTS: u32 = 4;
+
+/// Upper bound of the delay between two attempts, `Retry-After` included
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+// Deprecated endpoints are reported once per run, not per request
+static DEPRECATION_WARNED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Default)]
+pub enum Fixtures {
+  #[default]
+  None,
+  /// Save every response (redacted) into the directory
+  Record(PathBuf),
+  /// Serve responses from the directory instead of hitting the network
+  Replay(PathBuf),
+}
+
+impl Fixtures {
+  pub fn new(record: Option<PathBuf>, replay: Option<PathBuf>) -> Fixtures {
+    match (record, replay) {
+      (Some(dir), _) => Fixtures::Record(dir),
+      (_, Some(dir)) => Fixtures::Replay(dir),
+      _ => Fixtures::None,
+    }
+  }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct HttpResponse {
+  pub status_code: i32,
+  pub headers: HashMap<String, String>,
+  pub body: String,
+
+  /// Binary body (e.g. PDF) which is no valid text `body`
+  #[serde(skip)]
+  pub raw_body: Vec<u8>,
+}
+
+impl HttpResponse {
+  /// Received body, text or binary
+  pub fn bytes(&self) -> &[u8] {
+    if self.raw_body.is_empty() {
+      self.body.as_bytes()
+    } else {
+      &self.raw_body
+    }
+  }
+
+  pub fn header(&self, key: &str) -> Option<&str> {
+    self.headers.get(key).map(|value| value.as_str())
+  }
+
+  /// Warning about a `Deprecation` (RFC 9745) or `Sunset` (RFC 8594) header
+  pub fn deprecation_warning(
+    &self,
+    method: &Method,
+    url: &Url,
+  ) -> Option<String> {
+    let deprecation = self.header("deprecation");
+    let sunset = self.header("sunset");
+
+    if deprecation.is_none() && sunset.is_none() {
+      return None;
+    }
+
+    let removal = sunset
+      .map(|sunset| format!(" and removes it after {sunset}"))
+      .unwrap_or_default();
+
+    Some(format!(
+      "Warning: the Toggl API deprecated {method} {}{removal}, please update fbtoggl",
+      url.path()
+    ))
+  }
+
+  /// Delay of a `Retry-After` header, either seconds or an HTTP date
+  pub fn retry_after(&self) -> Option<Duration> {
+    let value = self.header("retry-after")?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+      return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+      .to_std()
+      .ok()
+      .or(Some(Duration::ZERO))
+  }
+}
+
+/// Tokens of the settings, requests use the least-privileged token which
+/// suffices and fall back to the default `api_token`
+#[derive(Debug, Clone, Default)]
+pub struct ApiTokens {
+  pub default: String,
+  pub read: Option<String>,
+  pub admin: Option<String>,
+}
+
+impl From<String> for ApiTokens {
+  fn from(default: String) -> Self {
+    ApiTokens {
+      default,
+      ..Default::default()
+    }
+  }
+}
+
+impl ApiTokens {
+  pub fn for_request(&self, read_only: bool) -> anyhow::Result<&str> {
+    let (token, scope) = if read_only {
+      (&self.read, "read")
+    } else {
+      (&self.admin, "admin")
+    };
+
+    match token.as_deref().unwrap_or(&self.default) {
+      "" => Err(anyhow!(
+        "No API token for this command, set FBTOGGL_API_TOKEN, 'api_token' or 'tokens.{scope}' in settings.toml"
+      )),
+      token => Ok(token),
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct HttpClient {
+  base_url: Url,
+  api_tokens: ApiTokens,
+  fixtures: Fixtures,
+  read_only: bool,
+  attempts: u32,
+  retry_delay: Duration,
+}
+
+/// Replaying fixtures works without settings file, as no request hits the network
+pub fn api_tokens(settings: &Settings) -> ApiTokens {
+  ApiTokens {
+    default: settings.api_token.clone(),
+    read: settings.tokens.read.clone(),
+    admin: settings.tokens.admin.clone(),
+  }
+}
+
+impl HttpClient {
+  pub fn new(base_url: Url, api_tokens: ApiTokens, fixtures: Fixtures) -> Self {
+    HttpClient {
+      base_url,
+      api_tokens,
+      fixtures,
+      read_only: false,
+      attempts: REQUEST_ATTEMPTS,
+      retry_delay: Duration::from_millis(500),
+    }
+  }
+
+  /// Every request only reads data (e.g. reports), even POST requests
+  pub fn read_only(self) -> Self {
+    HttpClient {
+      read_only: true,
+      ..self
+    }
+  }
+
+  /// File name of a cached GET response, separate per account
+  pub fn cache_key(&self, uri: &str) -> String {
+    let key = format!(
+      "{} {} {uri}",
+      self.api_tokens.default,
+      self.api_tokens.read.as_deref().unwrap_or_default()
+    );
+
+    format!("{:016x}.json", fnv1a(&format!("{}{key}", self.base_url)))
+  }
+
+  /// Every request is sent once, for callers which retry on their own
+  pub fn without_retries(self) -> Self {
+    HttpClient {
+      attempts: 1,
+      ..self
+    }
+  }
+
+  #[cfg(test)]
+  pub fn with_retry_delay(self, retry_delay: Duration) -> Self {
+    HttpClient {
+      retry_delay,
+      ..self
+    }
+  }
+
+  // POST requests may have been processed despite a server error,
+  // only rate limited ones are safe to repeat
+  fn is_idempotent(&self, method: &Method) -> bool {
+    self.read_only || *method != Method::Post
+  }
+
+  /// Delay before the next attempt, `None` if the result is final
+  fn retry_delay(
+    &self,
+    method: &Method,
+    result: &anyhow::Result<HttpResponse>,
+    attempt: u32,
+  ) -> Option<Duration> {
+    if attempt >= self.attempts {
+      return None;
+    }
+
+    let backoff = self.retry_delay * 2u32.pow(attempt - 1);
+
+    let delay = match result {
+      Ok(response) if response.status_code == 429 => {
+        response.retry_after().unwrap_or(backoff)
+      }
+      Ok(response)
+        if response.status_code >= 500 && self.is_idempotent(method) =>
+      {
+        response.retry_after().unwrap_or(backoff)
+      }
+      Err(err) if is_connection_reset(err) && self.is_idempotent(method) => {
+        backoff
+      }
+      _ => return None,
+    };
+
+    Some(delay.min(MAX_RETRY_DELAY))
+  }
+
+  fn basic_auth(&self, method: &Method) -> anyhow::Result<(String, String)> {
+    let api_token = self
+      .api_tokens
+      .for_request(self.read_only || *method == Method::Get)?;
+
+    Ok((
+      AUTHORIZATION.to_string(),
+      format!(
+        "Basic {}",
+        STANDARD.encode(format!("{api_token}:api_token"))
+      ),
+    ))
+  }
+
+  #[cfg_attr(
+    feature = "otel",
+    tracing::instrument(
+      name = "toggl.request",
+      skip_all,
+      err,
+      fields(
+        http.request.method = %method,
+        server.address = self.base_url.host_str().unwrap_or_default(),
+        url.path = uri,
+        http.response.status_code = tracing::field::Empty,
+      )
+    )
+  )]
+  pub fn send(
+    &self,
+    debug: bool,
+    method: Method,
+    uri: &str,
+    body: Option<Value>,
+  ) -> anyhow::Result<HttpResponse> {
+    let url = self.base_url.join(uri)?;
+
+    if let Fixtures::Replay(dir) = &self.fixtures {
+      return read_fixture(dir, &fixture_name(&method, &url, &body));
+    }
+
+    let mut attempt = 1;
+
+    loop {
+      let result = self.send_once(debug, &method, &url, &body);
+
+      match self.retry_delay(&method, &result, attempt) {
+        Some(delay) if progress::is_json() => {
+          let rate_limited =
+            matches!(&result, Ok(response) if response.status_code == 429);
+
+          progress::emit(&progress::retry_event(
+            format!("{method} {}", url.path()),
+            rate_limited,
+            attempt,
+            self.attempts - 1,
+            delay,
+          ));
+
+          std::thread::sleep(delay);
+
+          attempt += 1;
+        }
+        Some(delay) => {
+          eprintln!(
+            "{}",
+            format!(
+              "{method} {} failed, retrying in {:.1}s ({attempt}/{})",
+              url.path(),
+              delay.as_secs_f64(),
+              self.attempts - 1
+            )
+            .yellow()
+          );
+
+          std::thread::sleep(delay);
+
+          attempt += 1;
+        }
+        None => return result,
+      }
+    }
+  }
+
+  fn send_once(
+    &self,
+    debug: bool,
+    method: &Method,
+    url: &Url,
+    body: &Option<Value>,
+  ) -> anyhow::Result<HttpResponse> {
+    let (key, value) = self.basic_auth(method)?;
+
+    let mut request =
+      minreq::Request::new(method.clone(), url.clone()).with_header(key, value);
+
+    if let Some(body) = body {
+      request = request.with_json(body)?;
+    }
+
+    if debug {
+      println!("{}", "Request:".bold().underline());
+      println!("{request:?}");
+      println!();
+
+      if let Some(body) = body {
+        println!("{body:?}");
+        println!();
+      }
+    }
+
+    let response = request.send()?;
+
+    let response = HttpResponse {
+      status_code: response.status_code,
+      headers: response.headers.clone(),
+      body: response.as_str().unwrap_or_default().to_string(),
+      raw_body: match response.as_str() {
+        Ok(_) => vec![],
+        Err(_) => response.as_bytes().to_vec(),
+      },
+    };
+
+    #[cfg(feature = "otel")]
+    tracing::Span::current()
+      .record("http.response.status_code", response.status_code);
+
+    if let Some(warning) = response.deprecation_warning(method, url) {
+      if !DEPRECATION_WARNED.swap(true, Ordering::Relaxed) {
+        eprintln!("{}", warning.yellow());
+      }
+    }
+
+    if debug {
+      println!("{}", "Response:".bold().underline());
+      println!("{response:?}");
+      println!();
+    }
+
+    if let Fixtures::Record(dir) = &self.fixtures {
+      write_fixture(dir, &fixture_name(method, url, body), &response)?;
+    }
+
+    Ok(response)
+  }
+}
+
+/// Transport errors (no connection, DNS, TLS, timeouts) in contrast to error responses of the API
+pub fn is_network_error(err: &anyhow::Error) -> bool {
+  err.downcast_ref::<minreq::Error>().is_some()
+}
+
+// Connections closed by the server are worth another attempt, unlike a missing network
+fn is_connection_reset(err: &anyhow::Error) -> bool {
+  matches!(
+    err.downcast_ref::<minreq::Error>(),
+    Some(minreq::Error::IoError(err)) if matches!(
+      err.kind(),
+      std::io::ErrorKind::ConnectionReset
+        | std::io::ErrorKind::ConnectionAborted
+        | std::io::ErrorKind::BrokenPipe
+    )
+  )
+}
+
+pub fn json_response<D: DeserializeOwned + Debug>(
+  debug: bool,
+  response: HttpResponse,
+) -> anyhow::Result<D> {
+  match response.status_code {
+    200 | 201 if debug => match serde_json::from_str(&response.body) {
+      Ok(json) => {
+        println!("{}", "Received JSON response:".bold().underline());
+        println!("{json:?}");
+        println!();
+
+        Ok(json)
+      }
+      Err(err) => Err(anyhow!("Failed to deserialize JSON: {}", err)),
+    },
+    200 | 201 => Ok(serde_json::from_str(&response.body)?),
+    status => Err(error_response(status, &response)),
+  }
+}
+
+/// Body of a file download, e.g. an exported report
+pub fn bytes_response(response: HttpResponse) -> anyhow::Result<Vec<u8>> {
+  match response.status_code {
+    200 => Ok(response.bytes().to_vec()),
+    status => Err(error_response(status, &response)),
+  }
+}
+
+pub fn empty_response(response: HttpResponse) -> anyhow::Result<()> {
+  match response.status_code {
+    200 | 201 => Ok(()),
+    status => Err(error_response(status, &response)),
+  }
+}
+
+/// Error response of the API
+#[derive(Debug)]
+pub struct StatusError {
+  pub status: i32,
+  pub body: String,
+  pub retry_after: Option<Duration>,
+}
+
+impl std::fmt::Display for StatusError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.body.is_empty() {
+      write!(f, "{}", self.status)
+    } else {
+      write!(f, "{} - {}", self.status, self.body)
+    }
+  }
+}
+
+impl std::error::Error for StatusError {}
+
+fn error_response(status: i32, response: &HttpResponse) -> anyhow::Error {
+  StatusError {
+    status,
+    body: response.body.to_owned(),
+    retry_after: response.retry_after(),
+  }
+  .into()
+}
+
+/// Network errors, rate limits and server errors, which may succeed when retried
+pub fn is_transient_error(err: &anyhow::Error) -> bool {
+  is_network_error(err)
+    || err
+      .downcast_ref::<StatusError>()
+      .is_some_and(|err| err.status == 429 || err.status >= 500)
+}
+
+/// The API answered 429 Too Many Requests
+pub fn is_rate_limited(err: &anyhow::Error) -> bool {
+  err
+    .downcast_ref::<StatusError>()
+    .is_some_and(|err| err.status == 429)
+}
+
+/// Delay the API asked for via `Retry-After` before the next request
+pub fn retry_after(err: &anyhow::Error) -> Option<Duration> {
+  err
+    .downcast_ref::<StatusError>()
+    .and_then(|err| err.retry_after)
+    .map(|delay| delay.min(MAX_RETRY_DELAY))
+}
+
+// Fixture files are named after the request, so a replay finds the response
+// of the same request independent of the order of requests
+fn fixture_name(method: &Method, url: &Url, body: &Option<Value>) -> String {
+  let key = format!(
+    "{method} {}?{} {}",
+    url.path(),
+    url.query().unwrap_or_default(),
+    body
+      .as_ref()
+      .map(|body| body.to_string())
+      .unwrap_or_default()
+  );
+
+  let readable = format!("{method}{}", url.path())
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+    .take(100)
+    .collect::<String>();
+
+  format!("{readable}_{:016x}.json", fnv1a(&key))
+}
+
+// Stable across Rust versions and platforms, unlike `DefaultHasher`
+fn fnv1a(value: &str) -> u64 {
+  value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+    (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+  })
+}
+
+fn write_fixture(
+  dir: &Path,
+  name: &str,
+  response: &HttpResponse,
+) -> anyhow::Result<()> {
+  std::fs::create_dir_all(dir)?;
+
+  let body = match serde_json::from_str::<Value>(&response.body) {
+    Ok(mut json) => {
+      redact(&mut json);
+      json.to_string()
+    }
+    Err(_) => response.body.to_owned(),
+  };
+
+  let headers = response
+    .headers
+    .iter()
+    .filter(|(key, _)| key.as_str() != "set-cookie")
+    .map(|(key, value)| (key.to_owned(), value.to_owned()))
+    .collect();
+
+  let fixture = HttpResponse {
+    status_code: response.status_code,
+    headers,
+    body,
+    ..Default::default()
+  };
+
+  std::fs::write(dir.join(name), serde_json::to_string_pretty(&fixture)?)?;
+
+  Ok(())
+}
+
+fn read_fixture(dir: &Path, name: &str) -> anyhow::Result<HttpResponse> {
+  let path = dir.join(name);
+
+  let content = std::fs::read_to_string(&path)
+    .map_err(|err| anyhow!("No recorded response {path:?}: {err}"))?;
+
+  Ok(serde_json::from_str(&content)?)
+}
+
+pub(crate) fn redact(value: &mut Value) {
+  match value {
+    Value::Object(map) => {
+      for (key, value) in map.iter_mut() {
+        if REDACTED_KEYS.contains(&key.as_str()) && !value.is_null() {
+          *value = Value::String(REDACTED.to_string());
+        } else {
+          redact(value);
+        }
+      }
+    }
+    Value::Array(values) => values.iter_mut().for_each(redact),
+    _ => {}
+  }
+}
@@ -0,0 +1,252 @@
+use crate::{
+  client::{TogglApi, TogglClient},
+  http_client::redact,
+  http_client::ApiTokens,
+  http_client::Fixtures,
+  http_client::HttpResponse,
+};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[test]
+fn test_redact() {
+  let mut value = json!([
+    {
+      "id": 1234567,
+      "name": "Ralph Bower Workspace",
+      "api_token": "febb91e4d84e2aca80532c4bc0adce53",
+      "ical_url": null,
+      "owner": {
+        "email": "ralph@bower.org",
+        "fullname": "Ralph Bower"
+      }
+    }
+  ]);
+
+  redact(&mut value);
+
+  assert_eq!(
+    value,
+    json!([
+      {
+        "id": 1234567,
+        "name": "Ralph Bower Workspace",
+        "api_token": "REDACTED",
+        "ical_url": null,
+        "owner": {
+          "email": "REDACTED",
+          "fullname": "REDACTED"
+        }
+      }
+    ])
+  );
+}
+
+#[test]
+fn test_record_and_replay() -> anyhow::Result<()> {
+  let dir = std::env::temp_dir()
+    .join(format!("fbtoggl-fixtures-{}", std::process::id()));
+
+  let body = json!(
+    [
+      {
+        "id": 1234567,
+        "name": "Ralph Bower Workspace",
+        "api_token": "febb91e4d84e2aca80532c4bc0adce53"
+      }
+    ]
+  );
+
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("GET", "/workspaces")
+    .with_status(200)
+    .with_body(body.to_string())
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglClient::new_with_fixtures(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+      Fixtures::Record(dir.clone()),
+    )?;
+
+    let workspaces = client.get_workspaces(false)?;
+
+    assert_eq!(workspaces.first().unwrap().id, 1234567);
+  }
+
+  mock.assert();
+
+  // the server is gone, all responses come from the recorded fixtures
+  drop(server);
+
+  {
+    let client = TogglClient::new_with_fixtures(
+      "".to_string(),
+      "http://127.0.0.1:1/".parse()?,
+      Fixtures::Replay(dir.clone()),
+    )?;
+
+    let workspaces = client.get_workspaces(false)?;
+
+    assert_eq!(workspaces.first().unwrap().name, "Ralph Bower Workspace");
+
+    assert_eq!(client.get_me(false).is_err(), true);
+  }
+
+  let recorded = std::fs::read_dir(&dir)?
+    .map(|entry| std::fs::read_to_string(entry?.path()))
+    .collect::<Result<Vec<String>, _>>()?;
+
+  assert_eq!(recorded.len(), 1);
+  assert_eq!(
+    recorded[0].contains("febb91e4d84e2aca80532c4bc0adce53"),
+    false
+  );
+
+  std::fs::remove_dir_all(&dir)?;
+
+  Ok(())
+}
+
+#[test]
+fn test_api_tokens_for_request() -> anyhow::Result<()> {
+  let tokens = ApiTokens {
+    default: "default".to_string(),
+    read: Some("read".to_string()),
+    admin: None,
+  };
+
+  assert_eq!(tokens.for_request(true)?, "read");
+  assert_eq!(tokens.for_request(false)?, "default");
+
+  let read_only = ApiTokens {
+    read: Some("read".to_string()),
+    ..Default::default()
+  };
+
+  assert_eq!(read_only.for_request(true)?, "read");
+  assert!(read_only.for_request(false).is_err());
+
+  Ok(())
+}
+
+#[test]
+fn test_deprecation_warning() -> anyhow::Result<()> {
+  let url = "https://api.track.toggl.com/api/v9/me".parse()?;
+
+  let response = |headers: &[(&str, &str)]| HttpResponse {
+    status_code: 200,
+    headers: headers
+      .iter()
+      .map(|(key, value)| (key.to_string(), value.to_string()))
+      .collect(),
+    body: String::new(),
+    ..Default::default()
+  };
+
+  assert_eq!(
+    response(&[]).deprecation_warning(&minreq::Method::Get, &url),
+    None
+  );
+
+  assert_eq!(
+    response(&[("deprecation", "@1688169599")])
+      .deprecation_warning(&minreq::Method::Get, &url),
+    Some(
+      "Warning: the Toggl API deprecated GET /api/v9/me, please update fbtoggl"
+        .to_string()
+    )
+  );
+
+  assert_eq!(
+    response(&[("sunset", "Wed, 11 Nov 2026 23:59:59 GMT")])
+      .deprecation_warning(&minreq::Method::Get, &url),
+    Some(
+      "Warning: the Toggl API deprecated GET /api/v9/me and removes it after Wed, 11 Nov 2026 23:59:59 GMT, please update fbtoggl"
+        .to_string()
+    )
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_retry_after() {
+  let response = |retry_after: &str| HttpResponse {
+    status_code: 429,
+    headers: HashMap::from([(
+      "retry-after".to_string(),
+      retry_after.to_string(),
+    )]),
+    body: String::new(),
+    ..Default::default()
+  };
+
+  assert_eq!(response("7").retry_after(), Some(Duration::from_secs(7)));
+  assert_eq!(
+    response("Wed, 21 Oct 2015 07:28:00 GMT").retry_after(),
+    Some(Duration::ZERO)
+  );
+  assert_eq!(response("soon").retry_after(), None);
+}
+
+#[test]
+fn test_retries_rate_limited_requests() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let rate_limited_mock = server
+    .mock("GET", "/workspaces")
+    .with_status(429)
+    .with_header("Retry-After", "0")
+    .expect(2)
+    .create();
+
+  let mock = server
+    .mock("GET", "/workspaces")
+    .with_status(200)
+    .with_body(json!([{ "id": 1234567, "name": "fkbr" }]).to_string())
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let workspaces = client.get_workspaces(false)?;
+
+  rate_limited_mock.assert();
+  mock.assert();
+
+  assert_eq!(workspaces.first().unwrap().id, 1234567);
+
+  Ok(())
+}
+
+#[test]
+fn test_does_not_retry_post_requests_on_server_errors() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("POST", "/workspaces/123/clients")
+    .with_status(502)
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  assert_eq!(client.create_client(false, "fkbr", 123).is_err(), true);
+
+  mock.assert();
+
+  Ok(())
+}
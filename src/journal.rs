@@ -0,0 +1,316 @@
+use std::path::Path;
+
+use anyhow::anyhow;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Local;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::cli::CreateTimeEntry;
+use crate::cli::DateFormat;
+use crate::client::TogglApi;
+use crate::commands::time_entries::create_time_entries;
+use crate::commands::time_entries::start_time_entry;
+use crate::commands::time_entries::stop_time_entry_at;
+use crate::http_client::is_network_error;
+use crate::model::TimeEntryId;
+use crate::paths::failed_journal_file;
+use crate::paths::journal_file;
+
+/// Operations which failed because of a missing network connection
+/// and are replayed with their original timestamps later on
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum PendingOperation {
+  Start {
+    start: DateTime<Local>,
+    workspace: Option<String>,
+    project: String,
+
+    #[serde(default)]
+    task: Option<String>,
+
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+    non_billable: bool,
+
+    #[serde(default)]
+    create_missing_project: bool,
+
+    #[serde(default)]
+    client: Option<String>,
+  },
+  /// Without id the time entry of the last replayed start is stopped,
+  /// otherwise the running one
+  Stop {
+    stop: DateTime<Local>,
+    workspace: Option<String>,
+
+    #[serde(default)]
+    id: Option<String>,
+  },
+  Create {
+    workspace: Option<String>,
+    project: String,
+    task: Option<String>,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+    start: DateTime<Local>,
+    end: Option<DateTime<Local>>,
+    duration_seconds: Option<i64>,
+    lunch_break: bool,
+    non_billable: bool,
+    split_by_days: bool,
+
+    #[serde(default)]
+    create_missing_project: bool,
+
+    #[serde(default)]
+    client: Option<String>,
+  },
+}
+
+/// Pending operation which was rejected on replay, e.g. because its project
+/// does not exist anymore
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct FailedOperation {
+  pub operation: PendingOperation,
+  pub error: String,
+}
+
+pub fn push(operation: PendingOperation) -> anyhow::Result<()> {
+  push_to(&journal_file()?, operation)
+}
+
+pub(crate) fn push_to(
+  path: &Path,
+  operation: PendingOperation,
+) -> anyhow::Result<()> {
+  let mut operations: Vec<PendingOperation> = read(path)?;
+  operations.push(operation);
+
+  write(path, &operations)
+}
+
+/// Replays pending operations, returns the number of replayed operations.
+/// Rejected operations are moved to the failed journal.
+pub fn sync(
+  debug: bool,
+  date_format: &DateFormat,
+  client: &dyn TogglApi,
+) -> anyhow::Result<usize> {
+  sync_from(
+    debug,
+    &journal_file()?,
+    &failed_journal_file()?,
+    date_format,
+    client,
+  )
+}
+
+/// Operations of the failed journal
+pub fn failed() -> anyhow::Result<Vec<FailedOperation>> {
+  read(&failed_journal_file()?)
+}
+
+pub(crate) fn sync_from(
+  debug: bool,
+  path: &Path,
+  failed_path: &Path,
+  date_format: &DateFormat,
+  client: &dyn TogglApi,
+) -> anyhow::Result<usize> {
+  let operations: Vec<PendingOperation> = read(path)?;
+
+  if operations.is_empty() {
+    return Ok(0);
+  }
+
+  let mut replayed = 0;
+  let mut remaining = vec![];
+  let mut failed = vec![];
+  let mut offline = false;
+  let mut last_started = None;
+
+  for operation in operations {
+    if offline {
+      remaining.push(operation);
+      continue;
+    }
+
+    match replay(debug, &operation, &mut last_started, date_format, client) {
+      Ok(()) => replayed += 1,
+      // keep the order of operations, everything after a network error stays pending
+      Err(err) if is_network_error(&err) => {
+        offline = true;
+        remaining.push(operation);
+      }
+      Err(err) => {
+        eprintln!(
+          "Failed pending operation {operation:?}: {err}, see 'fbtoggl sync'"
+        );
+
+        failed.push(FailedOperation {
+          operation,
+          error: err.to_string(),
+        });
+      }
+    }
+  }
+
+  if !failed.is_empty() {
+    let mut failed_operations: Vec<FailedOperation> = read(failed_path)?;
+    failed_operations.extend(failed);
+
+    write(failed_path, &failed_operations)?;
+  }
+
+  write(path, &remaining)?;
+
+  Ok(replayed)
+}
+
+fn replay(
+  debug: bool,
+  operation: &PendingOperation,
+  last_started: &mut Option<u64>,
+  date_format: &DateFormat,
+  client: &dyn TogglApi,
+) -> anyhow::Result<()> {
+  match operation {
+    PendingOperation::Start {
+      start,
+      workspace,
+      project,
+      task,
+      description,
+      tags,
+      non_billable,
+      create_missing_project,
+      client: project_client,
+    } => {
+      let client = client.with_workspace(workspace.to_owned());
+
+      let time_entry = start_time_entry(
+        debug,
+        *start,
+        project,
+        task,
+        description,
+        tags,
+        *non_billable,
+        *create_missing_project,
+        project_client,
+        client.as_ref(),
+      )?;
+
+      *last_started = Some(time_entry.id);
+
+      eprintln!(
+        "Started pending time entry {} at {}",
+        time_entry.id,
+        start.format("%Y-%m-%d %H:%M:%S")
+      );
+    }
+    PendingOperation::Stop {
+      stop,
+      workspace,
+      id,
+    } => {
+      let client = client.with_workspace(workspace.to_owned());
+
+      let id = match (id, last_started.take()) {
+        (Some(id), _) => id.parse()?,
+        (None, Some(started)) => TimeEntryId::Id(started),
+        (None, None) => client
+          .get_current_time_entry(debug)?
+          .map(|running| TimeEntryId::Id(running.id))
+          .ok_or_else(|| anyhow!("No time entry is running"))?,
+      };
+
+      let time_entry = stop_time_entry_at(debug, &id, *stop, client.as_ref())?;
+
+      eprintln!(
+        "Stopped pending time entry {} at {}",
+        time_entry.id,
+        stop.format("%Y-%m-%d %H:%M:%S")
+      );
+    }
+    PendingOperation::Create {
+      workspace,
+      project,
+      task,
+      description,
+      tags,
+      start,
+      end,
+      duration_seconds,
+      lunch_break,
+      non_billable,
+      split_by_days,
+      create_missing_project,
+      client: project_client,
+    } => {
+      let client = client.with_workspace(workspace.to_owned());
+
+      create_time_entries(
+        debug,
+        &CreateTimeEntry {
+          project: Some(project.to_owned()),
+          task: task.to_owned(),
+          description: description.to_owned(),
+          tags: tags.to_owned(),
+          duration: duration_seconds.map(Duration::seconds),
+          lunch_break: *lunch_break,
+          start: *start,
+          end: *end,
+          non_billable: *non_billable,
+          // the defaults of the settings were applied before remembering it
+          billable: false,
+          no_default_tags: true,
+          split_by_days: *split_by_days,
+          from_json: None,
+          from_toggl_csv: None,
+          create_missing_project: *create_missing_project,
+          client: project_client.to_owned(),
+        },
+        date_format,
+        // entries created before the network error must not be duplicated
+        true,
+        client.as_ref(),
+      )?;
+
+      eprintln!(
+        "Created pending time entry of project '{}' at {}",
+        project,
+        start.format("%Y-%m-%d %H:%M:%S")
+      );
+    }
+  }
+
+  Ok(())
+}
+
+pub(crate) fn read<T: DeserializeOwned>(path: &Path) -> anyhow::Result<Vec<T>> {
+  if !path.exists() {
+    return Ok(vec![]);
+  }
+
+  Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn write<T: Serialize>(path: &Path, operations: &[T]) -> anyhow::Result<()> {
+  if operations.is_empty() {
+    if path.exists() {
+      std::fs::remove_file(path)?;
+    }
+
+    return Ok(());
+  }
+
+  std::fs::write(path, serde_json::to_string_pretty(operations)?)?;
+
+  Ok(())
+}
@@ -0,0 +1,478 @@
+use crate::{
+  cli::DateFormat,
+  client::TogglClient,
+  journal::{push_to, read, sync_from, FailedOperation, PendingOperation},
+};
+use chrono::{DateTime, Local};
+use mockito::Matcher;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::str::FromStr;
+
+fn pending_start() -> anyhow::Result<PendingOperation> {
+  Ok(PendingOperation::Start {
+    start: DateTime::<Local>::from_str("2021-11-21T23:58:09+01:00")?,
+    workspace: None,
+    project: "fkbr".to_string(),
+    task: None,
+    description: Some("sxoe".to_string()),
+    tags: None,
+    non_billable: false,
+    create_missing_project: false,
+    client: None,
+  })
+}
+
+#[test]
+fn test_sync_creates_backdated_time_entry() -> anyhow::Result<()> {
+  let path = std::env::temp_dir()
+    .join(format!("fbtoggl-journal-{}.json", std::process::id()));
+  let failed_path = path.with_extension("failed.json");
+
+  push_to(&path, pending_start()?)?;
+
+  let mut server = mockito::Server::new();
+
+  let me_mock = server
+    .mock("GET", "/me")
+    .with_status(200)
+    .with_body(json!({ "default_workspace_id": 123 }).to_string())
+    .expect(1)
+    .create();
+
+  let projects_mock = server
+    .mock("GET", "/workspaces/123/projects?active=true")
+    .with_status(200)
+    .with_body(
+      json!([
+        {
+          "id": 456,
+          "wid": 123,
+          "name": "fkbr",
+          "billable": true,
+          "is_private": false,
+          "active": true,
+          "at": "2021-11-16T08:52:59+00:00",
+          "created_at": "2021-11-16T08:52:59+00:00",
+          "color": "5",
+          "auto_estimates": false,
+          "actual_hours": 1,
+          "template": false,
+          "hex_color": "#2da608",
+          "status": "active"
+        }
+      ])
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let start_mock = server
+    .mock("POST", "/time_entries")
+    .match_body(Matcher::PartialJson(json!({
+      "start": "2021-11-21T23:58:09+01:00",
+      "pid": 456,
+      "wid": 123
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 789,
+        "wid": 123,
+        "pid": 456,
+        "start": "2021-11-21T22:58:09+00:00",
+        "duration": -1637535489,
+        "description": "sxoe"
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let synced =
+    sync_from(false, &path, &failed_path, &DateFormat::default(), &client)?;
+
+  me_mock.assert();
+  projects_mock.assert();
+  start_mock.assert();
+
+  assert_eq!(synced, 1);
+  assert_eq!(path.exists(), false);
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_keeps_pending_operations_without_network() -> anyhow::Result<()> {
+  let path = std::env::temp_dir().join(format!(
+    "fbtoggl-journal-offline-{}.json",
+    std::process::id()
+  ));
+  let failed_path = path.with_extension("failed.json");
+
+  push_to(&path, pending_start()?)?;
+
+  // nothing listens on port 1
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    "http://127.0.0.1:1".parse()?,
+  )?;
+
+  let synced =
+    sync_from(false, &path, &failed_path, &DateFormat::default(), &client)?;
+
+  assert_eq!(synced, 0);
+  assert_eq!(path.exists(), true);
+
+  std::fs::remove_file(&path)?;
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_stops_time_entry_at_original_time() -> anyhow::Result<()> {
+  let path = std::env::temp_dir()
+    .join(format!("fbtoggl-journal-stop-{}.json", std::process::id()));
+  let failed_path = path.with_extension("failed.json");
+
+  push_to(
+    &path,
+    PendingOperation::Stop {
+      stop: DateTime::<Local>::from_str("2021-11-22T01:58:09+01:00")?,
+      workspace: None,
+      id: Some("789".to_string()),
+    },
+  )?;
+
+  let mut server = mockito::Server::new();
+
+  let me_mock = server
+    .mock("GET", "/me")
+    .with_status(200)
+    .with_body(json!({ "default_workspace_id": 123 }).to_string())
+    .expect(1)
+    .create();
+
+  let time_entry_mock = server
+    .mock("GET", "/me/time_entries/789")
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 789,
+        "wid": 123,
+        "pid": 456,
+        "start": "2021-11-21T22:58:09+00:00",
+        "duration": -1637535489,
+        "description": "sxoe"
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let update_mock = server
+    .mock("PUT", "/workspaces/123/time_entries/789")
+    .match_body(Matcher::PartialJson(json!({
+      "stop": "2021-11-22T00:58:09Z",
+      "duration": 7200
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 789,
+        "wid": 123,
+        "pid": 456,
+        "start": "2021-11-21T22:58:09+00:00",
+        "stop": "2021-11-22T00:58:09+00:00",
+        "duration": 7200,
+        "description": "sxoe"
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let synced =
+    sync_from(false, &path, &failed_path, &DateFormat::default(), &client)?;
+
+  me_mock.assert();
+  time_entry_mock.assert();
+  update_mock.assert();
+
+  assert_eq!(synced, 1);
+  assert_eq!(path.exists(), false);
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_stops_pending_start() -> anyhow::Result<()> {
+  let path = std::env::temp_dir().join(format!(
+    "fbtoggl-journal-start-stop-{}.json",
+    std::process::id()
+  ));
+  let failed_path = path.with_extension("failed.json");
+
+  push_to(&path, pending_start()?)?;
+  push_to(
+    &path,
+    PendingOperation::Stop {
+      stop: DateTime::<Local>::from_str("2021-11-22T01:58:09+01:00")?,
+      workspace: None,
+      id: None,
+    },
+  )?;
+
+  let mut server = mockito::Server::new();
+
+  let me_mock = server
+    .mock("GET", "/me")
+    .with_status(200)
+    .with_body(json!({ "default_workspace_id": 123 }).to_string())
+    .expect_at_least(1)
+    .create();
+
+  let projects_mock = server
+    .mock("GET", "/workspaces/123/projects?active=true")
+    .with_status(200)
+    .with_body(
+      json!([{ "id": 456, "wid": 123, "name": "fkbr", "status": "active" }])
+        .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let running = json!({
+    "id": 789,
+    "wid": 123,
+    "pid": 456,
+    "start": "2021-11-21T22:58:09+00:00",
+    "duration": -1637535489,
+    "description": "sxoe"
+  });
+
+  let start_mock = server
+    .mock("POST", "/time_entries")
+    .with_status(200)
+    .with_body(running.to_string())
+    .expect(1)
+    .create();
+
+  // the started time entry is stopped, no lookup of the running one
+  let current_mock = server
+    .mock("GET", "/me/time_entries/current")
+    .expect(0)
+    .create();
+
+  let time_entry_mock = server
+    .mock("GET", "/me/time_entries/789")
+    .with_status(200)
+    .with_body(running.to_string())
+    .expect(1)
+    .create();
+
+  let update_mock = server
+    .mock("PUT", "/workspaces/123/time_entries/789")
+    .match_body(Matcher::PartialJson(json!({
+      "stop": "2021-11-22T00:58:09Z",
+      "duration": 7200
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 789,
+        "wid": 123,
+        "pid": 456,
+        "start": "2021-11-21T22:58:09+00:00",
+        "stop": "2021-11-22T00:58:09+00:00",
+        "duration": 7200,
+        "description": "sxoe"
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let synced =
+    sync_from(false, &path, &failed_path, &DateFormat::default(), &client)?;
+
+  me_mock.assert();
+  projects_mock.assert();
+  start_mock.assert();
+  current_mock.assert();
+  time_entry_mock.assert();
+  update_mock.assert();
+
+  assert_eq!(synced, 2);
+  assert_eq!(path.exists(), false);
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_keeps_failed_operations() -> anyhow::Result<()> {
+  let path = std::env::temp_dir().join(format!(
+    "fbtoggl-journal-failed-{}.json",
+    std::process::id()
+  ));
+  let failed_path = path.with_extension("failed.json");
+
+  push_to(&path, pending_start()?)?;
+
+  let mut server = mockito::Server::new();
+
+  let me_mock = server
+    .mock("GET", "/me")
+    .with_status(200)
+    .with_body(json!({ "default_workspace_id": 123 }).to_string())
+    .create();
+
+  // the project was deleted meanwhile
+  let projects_mock = server
+    .mock("GET", "/workspaces/123/projects?active=true")
+    .with_status(200)
+    .with_body("[]")
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let synced =
+    sync_from(false, &path, &failed_path, &DateFormat::default(), &client)?;
+
+  me_mock.assert();
+  projects_mock.assert();
+
+  assert_eq!(synced, 0);
+  assert_eq!(path.exists(), false);
+
+  let failed: Vec<FailedOperation> = read(&failed_path)?;
+
+  assert_eq!(failed.len(), 1);
+  assert_eq!(failed[0].operation, pending_start()?);
+  assert_eq!(failed[0].error, "Cannot find project='fkbr'");
+
+  std::fs::remove_file(&failed_path)?;
+
+  Ok(())
+}
+
+#[test]
+fn test_sync_skips_already_created_time_entries() -> anyhow::Result<()> {
+  let path = std::env::temp_dir().join(format!(
+    "fbtoggl-journal-create-{}.json",
+    std::process::id()
+  ));
+  let failed_path = path.with_extension("failed.json");
+
+  push_to(
+    &path,
+    PendingOperation::Create {
+      workspace: None,
+      project: "fkbr".to_string(),
+      task: None,
+      description: Some("sxoe".to_string()),
+      tags: None,
+      start: DateTime::<Local>::from_str("2021-11-22T09:00:00+01:00")?,
+      end: Some(DateTime::<Local>::from_str("2021-11-22T17:00:00+01:00")?),
+      duration_seconds: None,
+      lunch_break: true,
+      non_billable: false,
+      split_by_days: false,
+      create_missing_project: false,
+      client: None,
+    },
+  )?;
+
+  let mut server = mockito::Server::new();
+
+  let me_mock = server
+    .mock("GET", "/me")
+    .with_status(200)
+    .with_body(json!({ "default_workspace_id": 123 }).to_string())
+    .create();
+
+  let projects_mock = server
+    .mock("GET", "/workspaces/123/projects?active=true")
+    .with_status(200)
+    .with_body(
+      json!([{ "id": 456, "wid": 123, "name": "fkbr", "status": "active" }])
+        .to_string(),
+    )
+    .create();
+
+  // the part before the lunch break was created before the network error
+  let time_entries_mock = server
+    .mock("GET", Matcher::Regex("^/me/time_entries\\?".to_string()))
+    .with_status(200)
+    .with_body(
+      json!([{
+        "id": 789,
+        "wid": 123,
+        "pid": 456,
+        "start": "2021-11-22T08:00:00+00:00",
+        "duration": 12600,
+        "description": "sxoe"
+      }])
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let create_mock = server
+    .mock("POST", "/workspaces/123/time_entries")
+    .match_body(Matcher::PartialJson(json!({
+      "start": "2021-11-22T13:30:00+01:00",
+      "duration": 12600
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "id": 790,
+        "wid": 123,
+        "pid": 456,
+        "start": "2021-11-22T12:30:00+00:00",
+        "duration": 12600,
+        "description": "sxoe"
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let synced =
+    sync_from(false, &path, &failed_path, &DateFormat::default(), &client)?;
+
+  me_mock.assert();
+  projects_mock.assert();
+  time_entries_mock.assert();
+  create_mock.assert();
+
+  assert_eq!(synced, 1);
+  assert_eq!(path.exists(), false);
+  assert_eq!(failed_path.exists(), false);
+
+  Ok(())
+}
@@ -1,107 +1,992 @@
-use crate::cli::{Clients, Options, SubCommand, TimeEntries};
-use crate::config::init_settings_file;
-use clap::Parser;
-use cli::{Projects, Reports, Settings};
-use client::init_client;
+use crate::cli::{
+  Clients, CurrentTimeEntry, DateFormat, Options, Output, OutputFormat,
+  SubCommand, TimeEntries,
+};
+use crate::config::{
+  init_settings_file, read_settings, rotate_api_token, set_setting,
+  validate_settings_file,
+};
+use anyhow::anyhow;
+use cli::{Break, Export, Projects, Reports, Settings, Tags, Tasks};
+use client::{init_client, TogglClient};
+use commands::time_entries::TimeEntryFilter;
+use http_client::Fixtures;
 use report_client::init_report_client;
+use std::process::ExitCode;
+use std::rc::Rc;
 
+mod cache;
+mod calendar;
 mod cli;
 mod client;
 mod commands;
+mod completions;
 mod config;
+#[cfg(test)]
+mod fake_client;
+mod formatter;
+mod history;
+mod http_client;
+mod journal;
+mod manpages;
 mod model;
+#[cfg(test)]
+mod model_tests;
+mod paths;
+mod progress;
 mod report_client;
+mod resolver;
+mod rounding;
+mod routing;
+#[cfg(feature = "otel")]
+mod telemetry;
+mod template;
 
+#[cfg(test)]
+mod calendar_tests;
+#[cfg(test)]
+mod cli_tests;
 #[cfg(test)]
 mod client_tests;
+#[cfg(test)]
+mod completions_tests;
+#[cfg(test)]
+mod config_tests;
+#[cfg(test)]
+mod formatter_tests;
+#[cfg(test)]
+mod history_tests;
+#[cfg(test)]
+mod http_client_tests;
+#[cfg(test)]
+mod journal_tests;
+#[cfg(test)]
+mod manpages_tests;
+#[cfg(test)]
+mod progress_tests;
+#[cfg(test)]
+mod report_client_tests;
+#[cfg(test)]
+mod resolver_tests;
+#[cfg(test)]
+mod rounding_tests;
+#[cfg(test)]
+mod routing_tests;
+#[cfg(test)]
+mod template_tests;
+
+// Time entries started while offline are created before any other command,
+// so they show up in its output
+fn init_synced_client(
+  debug: bool,
+  workspace: &Option<String>,
+  fixtures: &Fixtures,
+  settings: &anyhow::Result<Rc<config::Settings>>,
+  date_format: &DateFormat,
+) -> anyhow::Result<TogglClient> {
+  let client =
+    init_client(workspace, fixtures, client_settings(settings, fixtures)?)?;
+
+  if let Fixtures::None = fixtures {
+    if let Err(err) = journal::sync(debug, date_format, &client) {
+      eprintln!("Failed to sync pending time entries: {err}");
+    }
+  }
+
+  client.pin_workspace(debug)
+}
+
+// settings.toml is read once in main, commands which need it fail with the
+// error of reading it
+fn required(
+  settings: &anyhow::Result<Rc<config::Settings>>,
+) -> anyhow::Result<Rc<config::Settings>> {
+  settings
+    .as_ref()
+    .map(Rc::clone)
+    .map_err(|err| anyhow!("{err:#}"))
+}
+
+// replaying fixtures works without settings file
+fn client_settings(
+  settings: &anyhow::Result<Rc<config::Settings>>,
+  fixtures: &Fixtures,
+) -> anyhow::Result<Rc<config::Settings>> {
+  match (settings, fixtures) {
+    (Err(_), Fixtures::Replay(_)) => Ok(Rc::default()),
+    _ => required(settings),
+  }
+}
+
+// Exit code of 'time-entries stop' without --id if no time entry is running,
+// so scripts can tell it apart from errors (1)
+const NOT_RUNNING_EXIT_CODE: u8 = 3;
+
+// Exit codes for scripts and shell prompts: 0 if a time entry is running,
+// 1 if not and 2 if it cannot be determined
+fn check_current(
+  debug: bool,
+  workspace: &Option<String>,
+  fixtures: &Fixtures,
+  settings: &anyhow::Result<Rc<config::Settings>>,
+  current_time_entry: &CurrentTimeEntry,
+) -> ExitCode {
+  let running = client_settings(settings, fixtures)
+    .and_then(|settings| init_client(workspace, fixtures, settings))
+    .and_then(|client| {
+      commands::time_entries::current(
+        debug,
+        &Output::new(cli::Format::Raw),
+        current_time_entry,
+        &client,
+      )
+    });
+
+  match running {
+    Ok(true) => ExitCode::SUCCESS,
+    Ok(false) => ExitCode::from(1),
+    Err(err) => {
+      eprintln!("Error: {err:?}");
+      ExitCode::from(2)
+    }
+  }
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+  #[cfg(feature = "otel")]
+  let _telemetry = telemetry::init();
+  #[cfg(feature = "otel")]
+  let _span = tracing::info_span!("fbtoggl").entered();
 
-fn main() -> anyhow::Result<()> {
-  let options = Options::parse();
-  let format = options.format;
+  // settings are optional here, e.g. 'fbtoggl init' runs without settings file
+  let settings = read_settings().map(Rc::new);
+
+  let options = Options::parse_with_billing_cycle(
+    settings
+      .as_ref()
+      .ok()
+      .and_then(|settings| settings.billing_cycle_start_day)
+      .unwrap_or(1),
+  );
   let debug = options.debug;
+  let workspace = options.workspace;
+  let fixtures = Fixtures::new(options.record, options.replay);
+
+  if options.refresh {
+    cache::clear()?;
+  }
+
+  progress::set_progress_format(&options.progress);
+
+  let (mut output, formatter) = match options.format {
+    OutputFormat::Builtin(format) => (Output::new(format), None),
+    OutputFormat::External(name) => {
+      let formatter = settings
+        .as_ref()
+        .ok()
+        .and_then(|settings| settings.formatters.get(&name))
+        .ok_or_else(|| {
+          anyhow!("No formatter '{name}' in [formatters] of settings.toml")
+        })?;
+
+      (Output::collected(), Some(formatter.to_owned()))
+    }
+  };
+
+  output.wide = options.wide;
+  output.utc = options.utc;
+  output.billable_marker = options.billable_marker.or_else(|| {
+    settings
+      .as_ref()
+      .ok()
+      .and_then(|settings| settings.billable_marker)
+  });
+  output.date_format = DateFormat::new(
+    options.date_format.or_else(|| {
+      settings
+        .as_ref()
+        .ok()
+        .and_then(|settings| settings.date_format.to_owned())
+    }),
+    options.locale.or_else(|| {
+      settings
+        .as_ref()
+        .ok()
+        .and_then(|settings| settings.locale.to_owned())
+    }),
+  )?;
+
+  if let Some(hours) = settings
+    .as_ref()
+    .ok()
+    .and_then(|settings| settings.stale_timer_hours)
+  {
+    output.stale_after_hours = hours;
+  }
+
+  // commands may end with another exit code than 0 without an error
+  let mut exit_code = ExitCode::SUCCESS;
 
   match options.subcommand {
     SubCommand::Init => init_settings_file()?,
     SubCommand::Settings(action) => match action {
       Settings::Init => init_settings_file()?,
+      Settings::Validate => validate_settings_file()?,
+      Settings::RotateToken(rotate_token) => {
+        // recorded fixtures would contain the new token
+        let settings = required(&settings)?;
+        let client = init_client(&None, &Fixtures::None, settings.clone())?;
+
+        rotate_api_token(debug, &rotate_token, &settings, &client)?;
+      }
+      Settings::Set(setting) => set_setting(&setting)?,
     },
     SubCommand::Projects(action) => match action {
       Projects::List(list_projects) => {
-        let client = init_client()?;
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
 
         commands::projects::list(
           debug,
           list_projects.include_archived,
-          &format,
+          list_projects.resolve,
+          &output,
           &client,
         )?;
       }
+      Projects::Create(create_project) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+
+        commands::projects::create(debug, &output, &create_project, &client)?;
+      }
+      Projects::Templates => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+
+        commands::projects::templates(debug, &output, &client)?;
+      }
+      Projects::Edit(edit_project) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+
+        commands::projects::edit(debug, &output, &edit_project, &client)?;
+      }
+      Projects::Archive(archive_project) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+
+        commands::projects::archive(debug, &output, &archive_project, &client)?;
+      }
+      Projects::Delete(delete_project) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+
+        commands::projects::delete(debug, &output, &delete_project, &client)?;
+      }
     },
     SubCommand::Workspaces(_action) => {
-      let client = init_client()?;
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
 
-      commands::workspaces::list(debug, &format, &client)?;
+      commands::workspaces::list(debug, &output, &client)?;
     }
 
     SubCommand::TimeEntries(action) => match action {
-      TimeEntries::Create(time_entry) => {
-        let client = init_client()?;
-        commands::time_entries::create(debug, &format, &time_entry, &client)?
+      TimeEntries::Create(mut time_entry) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+
+        let settings = required(&settings)?;
+
+        // imports bring their own projects and tags, 'default_billable' only
+        // applies to imported entries without a billable flag
+        if time_entry.from_json.is_none() && time_entry.from_toggl_csv.is_none()
+        {
+          if time_entry.project.is_none() {
+            time_entry.project = settings.default_project.to_owned();
+          }
+
+          if !time_entry.no_default_tags {
+            time_entry.tags = settings.tags_with_defaults(time_entry.tags);
+          }
+        }
+
+        time_entry.non_billable = settings
+          .is_non_billable(time_entry.billable, time_entry.non_billable);
+
+        commands::time_entries::create(debug, &output, &time_entry, &client)?
       }
       TimeEntries::List(list_time_entries) => {
-        let client = init_client()?;
-        commands::time_entries::list(
+        let client = init_synced_client(
           debug,
-          &format,
-          &list_time_entries.range,
-          list_time_entries.missing,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        if list_time_entries.missing {
+          let report_client = init_report_client(
+            &fixtures,
+            &*client_settings(&settings, &fixtures)?,
+          )?;
+
+          commands::time_entries::missing(
+            debug,
+            &output,
+            &list_time_entries,
+            &client,
+            &report_client,
+          )?
+        } else {
+          let filter = TimeEntryFilter::new(&list_time_entries)?;
+          let rounding = rounding::rounding(
+            list_time_entries.round,
+            list_time_entries.round_mode,
+            &required(&settings)?.rounding,
+          )?;
+
+          if list_time_entries.apply {
+            let rounding = rounding.ok_or_else(|| {
+              anyhow!("--apply needs --round or 'increment' of [rounding] in settings.toml")
+            })?;
+
+            commands::time_entries::apply_rounding(
+              debug,
+              &list_time_entries.range,
+              &filter,
+              &rounding,
+              list_time_entries.yes,
+              &client,
+            )?;
+          }
+
+          commands::time_entries::list(
+            debug,
+            &output,
+            &list_time_entries.range,
+            &filter,
+            rounding.as_ref(),
+            &client,
+          )?
+        }
+      }
+      TimeEntries::Start(mut time_entry) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        let settings = required(&settings)?;
+
+        // the tags of a recent time entry already contain the defaults
+        let recent_tags = time_entry.recent && time_entry.tags.is_none();
+
+        if !(time_entry.no_default_tags || recent_tags) {
+          time_entry.tags = settings.tags_with_defaults(time_entry.tags);
+        }
+
+        time_entry.non_billable = settings
+          .is_non_billable(time_entry.billable, time_entry.non_billable);
+
+        commands::time_entries::start(
+          debug,
+          &output,
+          &time_entry,
+          &settings.calendar,
+          &settings.project_rules,
+          settings.default_project.as_deref(),
           &client,
         )?
       }
-      TimeEntries::Start(time_entry) => {
-        let client = init_client()?;
-        commands::time_entries::start(debug, &format, &time_entry, &client)?
-      }
       TimeEntries::Stop(time_entry) => {
-        let client = init_client()?;
-        commands::time_entries::stop(debug, &format, &time_entry, &client)?
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        if !commands::time_entries::stop(debug, &output, &time_entry, &client)?
+        {
+          exit_code = ExitCode::from(NOT_RUNNING_EXIT_CODE);
+        }
       }
       TimeEntries::Delete(time_entry) => {
-        let client = init_client()?;
-        commands::time_entries::delete(debug, &format, &time_entry, &client)?
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::time_entries::delete(debug, &output, &time_entry, &client)?
+      }
+      TimeEntries::Details(time_entry) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::time_entries::details(debug, &output, &time_entry, &client)?
+      }
+      TimeEntries::Edit(time_entry) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        let keep_history =
+          time_entry.keep_history || required(&settings)?.description_history;
+
+        commands::time_entries::edit(
+          debug,
+          &output,
+          &time_entry,
+          keep_history,
+          &client,
+        )?
+      }
+      TimeEntries::Continue(time_entry) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::time_entries::continue_time_entry(
+          debug,
+          &output,
+          &time_entry,
+          &client,
+        )?
+      }
+      TimeEntries::BulkEdit(bulk_edit) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        let settings = required(&settings)?;
+
+        commands::time_entries::bulk_edit(
+          debug,
+          &output,
+          &bulk_edit,
+          &settings.non_billable_projects,
+          &client,
+        )?
+      }
+      TimeEntries::Split(split) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::time_entries::split(debug, &output, &split, &client)?
+      }
+
+      TimeEntries::Merge(merge) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::time_entries::merge(debug, &output, &merge, &client)?
+      }
+
+      TimeEntries::BulkDelete(bulk_delete) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::time_entries::bulk_delete(
+          debug,
+          &output,
+          &bulk_delete,
+          &client,
+        )?
+      }
+      TimeEntries::Current(current_time_entry) if current_time_entry.check => {
+        exit_code = check_current(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &current_time_entry,
+        );
+      }
+      TimeEntries::Current(current_time_entry) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::time_entries::current(
+          debug,
+          &output,
+          &current_time_entry,
+          &client,
+        )?;
       }
     },
 
     SubCommand::Clients(action) => match action {
       Clients::Create(create_client) => {
-        let client = init_client()?;
-        commands::clients::create(debug, &format, &create_client, &client)?
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::clients::create(debug, &output, &create_client, &client)?
       }
       Clients::List(list_clients) => {
-        let client = init_client()?;
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
         commands::clients::list(
           debug,
           list_clients.include_archived,
-          &format,
+          &output,
           &client,
         )?;
       }
+      Clients::Show(client_details) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::clients::show(debug, &output, &client_details, &client)?
+      }
+      Clients::Delete(delete_client) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::clients::delete(debug, &output, &delete_client, &client)?
+      }
+      Clients::Edit(edit_client) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::clients::edit(debug, &output, &edit_client, &client)?
+      }
+      Clients::Archive(archive_client) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::clients::archive(debug, &output, &archive_client, &client)?
+      }
+      Clients::Restore(restore_client) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        commands::clients::restore(debug, &output, &restore_client, &client)?
+      }
     },
 
+    SubCommand::Tags(action) => {
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
+
+      match action {
+        Tags::List => commands::tags::list(debug, &output, &client)?,
+        Tags::Create(create_tag) => {
+          commands::tags::create(debug, &output, &create_tag, &client)?
+        }
+        Tags::Rename(rename_tag) => {
+          commands::tags::rename(debug, &output, &rename_tag, &client)?
+        }
+        Tags::Delete(delete_tag) => {
+          commands::tags::delete(debug, &output, &delete_tag, &client)?
+        }
+      }
+    }
+    SubCommand::Tasks(action) => {
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
+
+      match action {
+        Tasks::List(list_tasks) => {
+          commands::tasks::list(debug, &output, &list_tasks, &client)?
+        }
+        Tasks::Create(create_task) => {
+          commands::tasks::create(debug, &output, &create_task, &client)?
+        }
+      }
+    }
+
     SubCommand::Reports(action) => match action {
       Reports::Detailed(detailed) => {
-        let client = init_client()?;
-        let report_client = init_report_client()?;
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        let report_client = init_report_client(
+          &fixtures,
+          &*client_settings(&settings, &fixtures)?,
+        )?;
+
+        let settings = required(&settings)?;
 
         commands::reports::detailed(
           debug,
+          &settings.compliance,
+          settings.break_project(),
+          &output.date_format,
           &client,
-          &detailed.range,
+          &detailed,
+          &report_client,
+        )?;
+      }
+      Reports::Summary(summary) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        let report_client = init_report_client(
+          &fixtures,
+          &*client_settings(&settings, &fixtures)?,
+        )?;
+        let settings = required(&settings)?;
+        let rounding = rounding::rounding(
+          summary.round,
+          summary.round_mode,
+          &settings.rounding,
+        )?;
+
+        commands::reports::summary(
+          debug,
+          &output,
+          &client,
+          &summary,
+          rounding.as_ref(),
+          &settings.exchange_rates,
           &report_client,
         )?;
       }
     },
+
+    SubCommand::Export(action) => match action {
+      Export::Pdf(export_pdf) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+        let settings = required(&settings)?;
+
+        commands::export::pdf(
+          debug,
+          &settings.timesheet,
+          &output.date_format,
+          &export_pdf,
+          &client,
+        )?;
+      }
+      Export::All(export_all) => {
+        let client = init_synced_client(
+          debug,
+          &workspace,
+          &fixtures,
+          &settings,
+          &output.date_format,
+        )?;
+
+        commands::export::all(debug, &export_all, &client)?;
+      }
+    },
+
+    SubCommand::Break(action) => {
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
+      let settings = required(&settings)?;
+
+      match action {
+        Break::Start => commands::breaks::start(
+          debug,
+          &output,
+          settings.break_project(),
+          &client,
+        )?,
+        Break::Stop(stop_break) => commands::breaks::stop(
+          debug,
+          &output,
+          &stop_break,
+          settings.break_project(),
+          &client,
+        )?,
+      }
+    }
+
+    SubCommand::Compare(compare) => {
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
+
+      commands::compare::compare(debug, &output, &compare, &client)?;
+    }
+
+    SubCommand::Week(week) => {
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
+
+      commands::time_entries::week(debug, &output, &week, &client)?;
+    }
+
+    SubCommand::Month(month) => {
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
+
+      commands::time_entries::month(debug, &output, &month, &client)?;
+    }
+
+    SubCommand::Stats(stats) => {
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
+
+      commands::stats::stats(debug, &output, &stats, &client)?;
+    }
+
+    SubCommand::Gaps(gaps) => {
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
+
+      commands::gaps::gaps(debug, &output, &gaps, &client)?;
+    }
+
+    SubCommand::History(history) => {
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
+
+      commands::history::history(debug, &output, &history, &client)?;
+    }
+
+    SubCommand::Version(version) => commands::version::version(&version),
+
+    SubCommand::Sync => {
+      let client = init_client(
+        &workspace,
+        &fixtures,
+        client_settings(&settings, &fixtures)?,
+      )?;
+      let synced = journal::sync(debug, &output.date_format, &client)?;
+
+      println!("Replayed {synced} pending operations");
+
+      let failed = journal::failed()?;
+
+      if !failed.is_empty() {
+        println!(
+          "{} pending operations failed, they are kept in {} until it is deleted:",
+          failed.len(),
+          paths::failed_journal_file()?.display()
+        );
+
+        for failed_operation in failed {
+          println!(
+            "{:?}: {}",
+            failed_operation.operation, failed_operation.error
+          );
+        }
+      }
+    }
+
+    SubCommand::Completions(completions) => match completions {
+      cli::Completions::Script(script) => {
+        print!("{}", completions::script(&script.shell))
+      }
+      cli::Completions::Dynamic(dynamic) => {
+        let candidates = completions::complete(debug, &dynamic.words, || {
+          Ok(Box::new(init_client(
+            &workspace,
+            &fixtures,
+            client_settings(&settings, &fixtures)?,
+          )?))
+        })?;
+
+        for candidate in candidates {
+          println!("{candidate}");
+        }
+      }
+    },
+
+    SubCommand::Mangen(mangen) => {
+      manpages::generate(&mangen.out_dir)?;
+
+      println!("Wrote man pages to {}", mangen.out_dir.display());
+    }
+
+    SubCommand::Paths => {
+      for (name, path) in paths::all()? {
+        println!("{name}: {}", path.display());
+      }
+    }
+
+    SubCommand::Doctor => {
+      let client = init_synced_client(
+        debug,
+        &workspace,
+        &fixtures,
+        &settings,
+        &output.date_format,
+      )?;
+
+      let settings = required(&settings)?;
+
+      commands::doctor::doctor(
+        debug,
+        &output,
+        &settings.non_billable_projects,
+        &client,
+      )?;
+    }
+  }
+
+  if let Some(formatter) = formatter {
+    let formatter_exit_code =
+      formatter::run(&formatter, &output.collected_json())?;
+
+    if formatter_exit_code != 0 {
+      return Ok(ExitCode::from(
+        u8::try_from(formatter_exit_code).unwrap_or(1),
+      ));
+    }
   }
 
-  Ok(())
+  Ok(exit_code)
 }
@@ -0,0 +1,13 @@
+use std::path::Path;
+
+use clap::CommandFactory;
+
+use crate::cli::Options;
+
+/// One man page per (visible) subcommand, e.g. 'fbtoggl-time-entries-list.1'
+pub fn generate(out_dir: &Path) -> anyhow::Result<()> {
+  std::fs::create_dir_all(out_dir)?;
+  clap_mangen::generate_to(Options::command(), out_dir)?;
+
+  Ok(())
+}
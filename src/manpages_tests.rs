@@ -0,0 +1,20 @@
+use crate::manpages::generate;
+
+#[test]
+fn test_generate() -> anyhow::Result<()> {
+  let out_dir =
+    std::env::temp_dir().join(format!("fbtoggl-man-{}", std::process::id()));
+
+  generate(&out_dir)?;
+
+  assert!(out_dir.join("fbtoggl.1").exists());
+  assert!(out_dir.join("fbtoggl-time-entries-list.1").exists());
+  assert!(!out_dir.join("fbtoggl-completions-dynamic.1").exists());
+
+  let page = std::fs::read_to_string(out_dir.join("fbtoggl-gaps.1"))?;
+  assert!(page.contains("\\-\\-fill"));
+
+  std::fs::remove_dir_all(out_dir)?;
+
+  Ok(())
+}
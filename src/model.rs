@@ -7,6 +7,7 @@ use chrono::TimeZone;
 use chrono::Utc;
 use chrono::Weekday;
 use chronoutil::shift_months;
+
 use now::DateTimeNow;
 use serde::Deserialize;
 use serde::Serialize;
@@ -15,10 +16,28 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::str::FromStr;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct Workspace {
   pub id: u64,
   pub name: String,
+
+  /// Whether the current user administers the workspace
+  #[serde(default)]
+  pub admin: bool,
+
+  /// Role of the current user, e.g. 'admin' or 'user'
+  #[serde(default)]
+  pub role: Option<String>,
+}
+
+impl Workspace {
+  pub fn role(&self) -> &str {
+    match &self.role {
+      Some(role) => role,
+      None if self.admin => "admin",
+      None => "-",
+    }
+  }
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -28,14 +47,52 @@ pub struct Project {
   pub wid: u64,
   pub status: String,
   pub cid: Option<u64>,
+
+  #[serde(default)]
+  pub rate: Option<f64>,
+
+  #[serde(default)]
+  pub currency: Option<String>,
+
+  /// Project is a template for new projects
+  #[serde(default)]
+  pub template: Option<bool>,
+}
+
+/// Changed fields of a project, the others are kept
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct ProjectUpdate {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub client_id: Option<u64>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub billable: Option<bool>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub color: Option<String>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub active: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Me {
+  #[serde(default)]
+  pub id: Option<u64>,
+
   pub default_workspace_id: u64,
+
+  #[serde(default)]
+  pub created_at: Option<DateTime<Utc>>,
+
+  #[serde(default)]
+  pub fullname: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct TimeEntry {
   pub id: u64,
   pub wid: u64,
@@ -51,6 +108,125 @@ pub struct TimeEntry {
 
   #[serde(default)]
   pub duronly: bool,
+
+  /// Billable rate override of the time entry (per hour)
+  #[serde(default)]
+  pub rate: Option<f64>,
+
+  #[serde(default)]
+  pub guid: Option<String>,
+
+  #[serde(default)]
+  pub task_id: Option<u64>,
+}
+
+impl TimeEntry {
+  /// Rate override of the time entry, otherwise the rate of the project
+  pub fn effective_rate(&self, project: Option<&Project>) -> Option<f64> {
+    self
+      .rate
+      .or_else(|| project.and_then(|project| project.rate))
+  }
+
+  /// Billable amount, running and non-billable time entries have no amount
+  pub fn amount(&self, project: Option<&Project>) -> Option<f64> {
+    if self.duration.is_negative() || !self.billable.unwrap_or_default() {
+      return None;
+    }
+
+    self
+      .effective_rate(project)
+      .map(|rate| rate * self.duration as f64 / 3600.0)
+  }
+}
+
+/// Time entry of `time-entries create --from-json` in the shape of the JSON
+/// output (unknown fields like `id` are ignored), `project` (name) can replace `pid`
+#[derive(Deserialize, Debug)]
+pub struct ImportTimeEntry {
+  #[serde(default)]
+  pub wid: Option<u64>,
+
+  #[serde(default)]
+  pub pid: Option<u64>,
+
+  #[serde(default)]
+  pub project: Option<String>,
+
+  #[serde(default)]
+  pub billable: Option<bool>,
+
+  pub start: DateTime<Utc>,
+
+  #[serde(default)]
+  pub stop: Option<DateTime<Utc>>,
+
+  #[serde(default)]
+  pub duration: Option<i64>,
+
+  #[serde(default)]
+  pub description: Option<String>,
+
+  #[serde(default)]
+  pub tags: Option<Vec<String>>,
+}
+
+impl ImportTimeEntry {
+  /// Positive duration from `duration` or `stop` - `start`, running entries
+  /// (negative duration) cannot be created
+  pub fn stopped_duration(&self) -> anyhow::Result<Duration> {
+    let duration = match (self.duration, self.stop) {
+      (Some(duration), _) if duration < 0 => {
+        return Err(anyhow::anyhow!("running time entries cannot be imported"))
+      }
+      (Some(duration), _) => {
+        Duration::try_seconds(duration).unwrap_or_default()
+      }
+      (None, Some(stop)) => stop - self.start,
+      (None, None) => {
+        return Err(anyhow::anyhow!("'duration' or 'stop' is missing"))
+      }
+    };
+
+    if duration <= Duration::zero() {
+      return Err(anyhow::anyhow!("duration must be positive"));
+    }
+
+    Ok(duration)
+  }
+
+  /// Project selector (id or name) for the resolver
+  pub fn project_selector(&self) -> anyhow::Result<String> {
+    match (self.pid, &self.project) {
+      (Some(pid), _) => Ok(pid.to_string()),
+      (None, Some(project)) => Ok(project.to_owned()),
+      (None, None) => Err(anyhow::anyhow!("'pid' or 'project' is missing")),
+    }
+  }
+}
+
+/// JSON Patch operation of the bulk edit of time entries
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PatchOperation {
+  pub op: String,
+  pub path: String,
+  pub value: serde_json::Value,
+}
+
+/// Answer of the bulk edit of time entries
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct BulkEditResult {
+  #[serde(default)]
+  pub success: Vec<u64>,
+
+  #[serde(default)]
+  pub failure: Vec<BulkEditFailure>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct BulkEditFailure {
+  pub id: u64,
+  pub message: String,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -60,6 +236,31 @@ pub struct Client {
   pub archived: bool,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct WorkspaceUser {
+  pub id: u64,
+
+  #[serde(alias = "name")]
+  pub fullname: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Tag {
+  pub id: u64,
+  pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Task {
+  pub id: u64,
+  pub name: String,
+  pub project_id: u64,
+  pub workspace_id: u64,
+
+  #[serde(default)]
+  pub active: bool,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Range {
   Today,
@@ -70,10 +271,17 @@ pub enum Range {
   LastMonth,
   FromTo(NaiveDate, NaiveDate),
   Date(NaiveDate),
+  All,
+  /// Billing months start at 'billing_cycle_start_day' of settings.toml
+  ThisBillingMonth(u32),
+  LastBillingMonth(u32),
 }
 
 impl Range {
-  pub fn get_datetimes(self) -> anyhow::Result<Vec<DateTime<Local>>> {
+  pub fn get_datetimes(
+    self,
+    include_weekends: bool,
+  ) -> anyhow::Result<Vec<DateTime<Local>>> {
     let (start, end) = self.as_range()?;
 
     // range "today" and "yesterday" have different start and end dates,
@@ -89,7 +297,9 @@ impl Range {
     while it <= end {
       let weekday = it.date_naive().weekday();
 
-      if weekday != Weekday::Sat && weekday != Weekday::Sun {
+      if include_weekends
+        || (weekday != Weekday::Sat && weekday != Weekday::Sun)
+      {
         missing_days.push(it);
       }
 
@@ -181,12 +391,79 @@ impl Range {
 
         let end = start + Duration::try_days(1).unwrap();
 
+        Ok((start, end))
+      }
+      Range::ThisBillingMonth(start_day)
+      | Range::LastBillingMonth(start_day) => {
+        let offset = if let Range::LastBillingMonth(_) = self {
+          -1
+        } else {
+          0
+        };
+
+        let (start_date, end_date) =
+          billing_month(Local::now().date_naive(), start_day, offset);
+
+        let start = Local
+          .from_local_datetime(&start_date.and_hms_opt(0, 0, 0).unwrap())
+          .single()
+          .ok_or_else(|| anyhow::anyhow!("Could not create start datetime"))?;
+
+        let end = Local
+          .from_local_datetime(&end_date.and_hms_opt(0, 0, 0).unwrap())
+          .single()
+          .ok_or_else(|| anyhow::anyhow!("Could not create end datetime"))?
+          .end_of_day();
+
+        Ok((start, end))
+      }
+      Range::All => {
+        // track.toggl.com did not exist before 2006
+        let start = Local
+          .with_ymd_and_hms(2006, 1, 1, 0, 0, 0)
+          .single()
+          .ok_or_else(|| anyhow::anyhow!("Could not create start datetime"))?;
+
+        let (_, end) = Range::Today.as_range()?;
+
         Ok((start, end))
       }
     }
   }
 }
 
+impl Range {
+  /// Same range, billing months start at `start_day` instead of the 1st
+  pub fn with_billing_cycle_start_day(self, start_day: u32) -> Range {
+    match self {
+      Range::ThisBillingMonth(_) => Range::ThisBillingMonth(start_day),
+      Range::LastBillingMonth(_) => Range::LastBillingMonth(start_day),
+      range => range,
+    }
+  }
+}
+
+/// First and last date of the billing month containing `today`, shifted by `offset` months.
+/// Billing months start at `start_day` (1-28) and end the day before `start_day` of the next month
+pub fn billing_month(
+  today: NaiveDate,
+  start_day: u32,
+  offset: i32,
+) -> (NaiveDate, NaiveDate) {
+  let start_day = start_day.clamp(1, 28);
+
+  let current_start = if today.day() >= start_day {
+    today.with_day(start_day).unwrap()
+  } else {
+    shift_months(today, -1).with_day(start_day).unwrap()
+  };
+
+  let start = shift_months(current_start, offset);
+  let end = shift_months(start, 1) - Duration::try_days(1).unwrap();
+
+  (start, end)
+}
+
 impl FromStr for Range {
   type Err = anyhow::Error;
 
@@ -198,6 +475,9 @@ impl FromStr for Range {
       "last-week" => Ok(Range::LastWeek),
       "this-month" => Ok(Range::ThisMonth),
       "last-month" => Ok(Range::LastMonth),
+      "all" => Ok(Range::All),
+      "this-billing-month" => Ok(Range::ThisBillingMonth(1)),
+      "last-billing-month" => Ok(Range::LastBillingMonth(1)),
       from_to_or_date => match from_to_or_date.find('|') {
         Some(index) => Ok(Range::FromTo(
           NaiveDate::parse_from_str(&from_to_or_date[..index], "%Y-%m-%d")?,
@@ -225,10 +505,43 @@ impl Display for Range {
   }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
-pub struct Currency {
-  pub currency: Option<String>,
-  pub amount: Option<f64>,
+/// Time entries are referenced by their numeric id or their GUID
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeEntryId {
+  Id(u64),
+  Guid(String),
+}
+
+impl FromStr for TimeEntryId {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.parse::<u64>() {
+      Ok(id) => Ok(TimeEntryId::Id(id)),
+      Err(_) if is_guid(s) => Ok(TimeEntryId::Guid(s.to_lowercase())),
+      Err(_) => Err(anyhow::anyhow!("'{s}' is neither an id nor a GUID")),
+    }
+  }
+}
+
+impl Display for TimeEntryId {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      TimeEntryId::Id(id) => write!(f, "{id}"),
+      TimeEntryId::Guid(guid) => write!(f, "{guid}"),
+    }
+  }
+}
+
+// 8-4-4-4-12 hex digits, e.g. "6b3e2b3a-0c8e-4a6f-9d5b-6a4f1c2d3e4f"
+fn is_guid(s: &str) -> bool {
+  let groups = s
+    .split('-')
+    .map(|group| group.len())
+    .collect::<Vec<usize>>();
+
+  groups == [8, 4, 4, 4, 12]
+    && s.chars().all(|c| c == '-' || c.is_ascii_hexdigit())
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -242,5 +555,48 @@ pub struct ReportTimeEntry {
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ReportDetails {
   pub username: String,
+
+  #[serde(default)]
+  pub project_id: Option<u64>,
+
   pub time_entries: Vec<ReportTimeEntry>,
 }
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ReportTotals {
+  pub seconds: u64,
+
+  #[serde(default)]
+  pub resolution: Option<String>,
+
+  #[serde(default)]
+  pub graph: Vec<ReportGraphPoint>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ReportGraphPoint {
+  pub seconds: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ReportSummary {
+  #[serde(default)]
+  pub groups: Vec<ReportSummaryGroup>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ReportSummaryGroup {
+  pub id: Option<u64>,
+
+  #[serde(default)]
+  pub sub_groups: Vec<ReportSummarySubGroup>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ReportSummarySubGroup {
+  /// Project id, None for time entries without project
+  #[serde(default)]
+  pub id: Option<u64>,
+
+  pub seconds: u64,
+}
@@ -0,0 +1,70 @@
+use crate::model::billing_month;
+use crate::model::TimeEntryId;
+use crate::model::Workspace;
+use chrono::NaiveDate;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test]
+fn test_billing_month() {
+  let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+  assert_eq!(
+    billing_month(date(2021, 11, 20), 1, 0),
+    (date(2021, 11, 1), date(2021, 11, 30))
+  );
+
+  assert_eq!(
+    billing_month(date(2021, 11, 20), 15, 0),
+    (date(2021, 11, 15), date(2021, 12, 14))
+  );
+
+  assert_eq!(
+    billing_month(date(2021, 11, 10), 15, 0),
+    (date(2021, 10, 15), date(2021, 11, 14))
+  );
+
+  assert_eq!(
+    billing_month(date(2021, 1, 10), 15, -1),
+    (date(2020, 11, 15), date(2020, 12, 14))
+  );
+
+  assert_eq!(
+    billing_month(date(2021, 3, 30), 31, 0),
+    (date(2021, 3, 28), date(2021, 4, 27))
+  );
+}
+
+#[test]
+fn test_time_entry_id() -> anyhow::Result<()> {
+  assert_eq!("123".parse::<TimeEntryId>()?, TimeEntryId::Id(123));
+  assert_eq!(
+    "6B3E2B3A-0C8E-4A6F-9D5B-6A4F1C2D3E4F".parse::<TimeEntryId>()?,
+    TimeEntryId::Guid("6b3e2b3a-0c8e-4a6f-9d5b-6a4f1c2d3e4f".to_string())
+  );
+  assert_eq!("fkbr".parse::<TimeEntryId>().is_err(), true);
+  assert_eq!(
+    "6b3e2b3a-0c8e-4a6f-9d5b".parse::<TimeEntryId>().is_err(),
+    true
+  );
+
+  Ok(())
+}
+
+#[test]
+fn test_workspace_role() -> anyhow::Result<()> {
+  let workspace = |value| serde_json::from_value::<Workspace>(value);
+
+  assert_eq!(
+    workspace(json!({ "id": 1, "name": "fkbr", "admin": true }))?.role(),
+    "admin"
+  );
+  assert_eq!(
+    workspace(json!({ "id": 1, "name": "fkbr", "role": "projectlead" }))?
+      .role(),
+    "projectlead"
+  );
+  assert_eq!(workspace(json!({ "id": 1, "name": "fkbr" }))?.role(), "-");
+
+  Ok(())
+}
@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::APP_NAME;
+use crate::config::read_settings;
+
+/// Overrides of the directories below, `[paths]` in settings.toml
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Paths {
+  /// Regenerable data like cached responses (default: $XDG_CACHE_HOME/fbtoggl)
+  pub cache: Option<PathBuf>,
+
+  /// Mutable state like the offline journal (default: $XDG_STATE_HOME/fbtoggl)
+  pub state: Option<PathBuf>,
+}
+
+fn xdg_dirs() -> anyhow::Result<xdg::BaseDirectories> {
+  Ok(xdg::BaseDirectories::with_prefix(APP_NAME)?)
+}
+
+// the settings file is optional, e.g. for 'fbtoggl paths' before 'fbtoggl init'
+fn overrides() -> Paths {
+  read_settings()
+    .map(|settings| settings.paths)
+    .unwrap_or_default()
+}
+
+/// settings.toml in $XDG_CONFIG_HOME/fbtoggl
+pub fn settings_file() -> anyhow::Result<PathBuf> {
+  Ok(xdg_dirs()?.get_config_file("settings.toml"))
+}
+
+pub fn cache_dir() -> anyhow::Result<PathBuf> {
+  match overrides().cache {
+    Some(dir) => Ok(dir),
+    None => Ok(xdg_dirs()?.get_cache_home()),
+  }
+}
+
+pub fn state_dir() -> anyhow::Result<PathBuf> {
+  match overrides().state {
+    Some(dir) => Ok(dir),
+    None => Ok(xdg_dirs()?.get_state_home()),
+  }
+}
+
+/// Journal of operations which failed without network connection
+pub fn journal_file() -> anyhow::Result<PathBuf> {
+  let journal_file = state_dir()?.join("journal.json");
+
+  // earlier versions kept the journal in $XDG_DATA_HOME
+  let legacy_file = xdg_dirs()?.get_data_home().join("journal.json");
+
+  if legacy_file.exists() && !journal_file.exists() {
+    if let Some(dir) = journal_file.parent() {
+      std::fs::create_dir_all(dir)?;
+    }
+
+    std::fs::rename(legacy_file, &journal_file)?;
+  }
+
+  Ok(journal_file)
+}
+
+/// Pending operations which were rejected on replay, for 'fbtoggl sync'
+pub fn failed_journal_file() -> anyhow::Result<PathBuf> {
+  Ok(state_dir()?.join("journal.failed.json"))
+}
+
+/// Previous descriptions of edited time entries, for 'fbtoggl history'
+pub fn history_file() -> anyhow::Result<PathBuf> {
+  Ok(state_dir()?.join("history.jsonl"))
+}
+
+/// Fetched pages of detailed reports for --resume, kept apart from the cache
+/// so clearing it does not restart a report
+pub fn report_checkpoint_dir() -> anyhow::Result<PathBuf> {
+  Ok(state_dir()?.join("reports"))
+}
+
+/// Every file and directory of fbtoggl, for 'fbtoggl paths'
+pub fn all() -> anyhow::Result<Vec<(&'static str, PathBuf)>> {
+  Ok(vec![
+    ("settings", settings_file()?),
+    ("cache", cache_dir()?),
+    ("state", state_dir()?),
+    ("journal", journal_file()?),
+    ("failed journal", failed_journal_file()?),
+    ("history", history_file()?),
+    ("report checkpoints", report_checkpoint_dir()?),
+  ])
+}
@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::cli::ProgressFormat;
+
+/// Set via --progress json
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Progress of long operations for wrappers (e.g. GUIs), one JSON object per
+/// line on stderr
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+  /// A report page was fetched, `rows` in total so far
+  PageFetched { page: u64, rows: usize },
+
+  /// A request failed and is sent again after the delay
+  Retrying {
+    request: String,
+    attempt: u32,
+    attempts: u32,
+    delay_seconds: f64,
+  },
+
+  /// The API asked to slow down, the request is sent again after the delay
+  RateLimited {
+    request: String,
+    attempt: u32,
+    attempts: u32,
+    delay_seconds: f64,
+  },
+
+  /// `position` of `length` items are processed
+  Progress {
+    operation: String,
+    position: u64,
+    length: u64,
+  },
+
+  /// The operation finished
+  Done { operation: String },
+}
+
+pub fn set_progress_format(format: &ProgressFormat) {
+  JSON.store(*format == ProgressFormat::Json, Ordering::Relaxed);
+}
+
+/// Whether progress is reported as events instead of messages and bars
+pub fn is_json() -> bool {
+  JSON.load(Ordering::Relaxed)
+}
+
+pub fn emit(event: &ProgressEvent) {
+  if is_json() {
+    if let Ok(line) = serde_json::to_string(event) {
+      eprintln!("{line}");
+    }
+  }
+}
+
+/// `Retrying` or `RateLimited` event of a delayed attempt
+pub fn retry_event(
+  request: String,
+  rate_limited: bool,
+  attempt: u32,
+  attempts: u32,
+  delay: Duration,
+) -> ProgressEvent {
+  let delay_seconds = delay.as_secs_f64();
+
+  if rate_limited {
+    ProgressEvent::RateLimited {
+      request,
+      attempt,
+      attempts,
+      delay_seconds,
+    }
+  } else {
+    ProgressEvent::Retrying {
+      request,
+      attempt,
+      attempts,
+      delay_seconds,
+    }
+  }
+}
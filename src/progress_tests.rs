@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::progress::{retry_event, ProgressEvent};
+
+#[test]
+fn test_progress_event_json() -> anyhow::Result<()> {
+  assert_eq!(
+    serde_json::to_value(ProgressEvent::PageFetched { page: 1, rows: 50 })?,
+    json!({"event": "page_fetched", "page": 1, "rows": 50})
+  );
+
+  assert_eq!(
+    serde_json::to_value(retry_event(
+      "GET /api/v9/me".to_string(),
+      true,
+      1,
+      4,
+      Duration::from_millis(1500),
+    ))?,
+    json!({
+      "event": "rate_limited",
+      "request": "GET /api/v9/me",
+      "attempt": 1,
+      "attempts": 4,
+      "delay_seconds": 1.5
+    })
+  );
+
+  assert_eq!(
+    serde_json::to_value(ProgressEvent::Done {
+      operation: "report".to_string()
+    })?,
+    json!({"event": "done", "operation": "report"})
+  );
+
+  Ok(())
+}
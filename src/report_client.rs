@@ -1,61 +1,149 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::fmt::Display;
+use std::path::PathBuf;
 
-use crate::config::read_settings;
+use crate::config::Settings;
+use crate::http_client::api_tokens;
+use crate::http_client::bytes_response;
+use crate::http_client::is_rate_limited;
+use crate::http_client::is_transient_error;
+use crate::http_client::json_response;
+use crate::http_client::retry_after;
+use crate::http_client::ApiTokens;
+use crate::http_client::Fixtures;
+use crate::http_client::HttpClient;
+use crate::http_client::StatusError;
 use crate::model::Range;
 use crate::model::ReportDetails;
+use crate::model::ReportSummary;
+use crate::model::ReportTotals;
+use crate::paths;
+use crate::progress;
+use crate::progress::ProgressEvent;
 use anyhow::anyhow;
-use base64::engine::general_purpose::STANDARD;
-use base64::Engine;
-use colored::Colorize;
+use chrono::Duration;
+use chrono::NaiveDate;
+use chronoutil::shift_months;
+use chronoutil::shift_years;
 use minreq::Method;
-use minreq::Request;
-use minreq::Response;
 use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use serde::Serialize;
 use serde_json::json;
+#[cfg(test)]
 use url::Url;
 
 pub struct TogglReportClient {
-  base_url: Url,
-  api_token: String,
+  http_client: HttpClient,
+  retry_delay: std::time::Duration,
+  checkpoint_dir: Option<PathBuf>,
 }
 
-pub const CREATED_WITH: &str = "fbtoggl (https://github.com/icepuma/fbtoggl)";
+/// Attempts per report page before the page counts as failed
+const PAGE_ATTEMPTS: u32 = 3;
 
-const AUTHORIZATION: &str = "Authorization";
+/// Fetched details and the pages which failed even after retrying,
+/// the rest of a range after a failed page is missing as well
+pub struct DetailedReport {
+  pub details: Vec<ReportDetails>,
+  pub failures: Vec<PageFailure>,
+}
+
+/// Progress of an incomplete detailed report, kept in a temporary file
+/// until the report is resumed successfully
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct Checkpoint {
+  details: Vec<ReportDetails>,
+
+  /// Start dates of the completely fetched chunks
+  finished: Vec<NaiveDate>,
+
+  /// First row number of the failed page per chunk start date
+  next_row_numbers: HashMap<NaiveDate, u64>,
+}
+
+#[derive(Debug)]
+pub struct PageFailure {
+  pub start_date: NaiveDate,
+  pub end_date: NaiveDate,
+  pub first_row_number: Option<u64>,
+  pub error: String,
+}
+
+impl Display for PageFailure {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{}|{} from row {}: {}",
+      self.start_date,
+      self.end_date,
+      self.first_row_number.unwrap_or(1),
+      self.error
+    )
+  }
+}
+
+pub const CREATED_WITH: &str = crate::client::CREATED_WITH;
+
+/// Shown before the time entries are evaluated without the Reports API
+pub const PAID_PLAN_FALLBACK: &str =
+  "Reports API requires a paid plan — falling back to /me/time_entries";
+
+/// Workspaces on the free plan are refused by the Reports API
+pub fn requires_paid_plan(err: &anyhow::Error) -> bool {
+  matches!(
+    err.downcast_ref::<StatusError>(),
+    Some(StatusError {
+      status: 402 | 403,
+      ..
+    })
+  )
+}
 
-pub fn init_report_client() -> anyhow::Result<TogglReportClient> {
-  let settings = read_settings()?;
+pub fn init_report_client(
+  fixtures: &Fixtures,
+  settings: &Settings,
+) -> anyhow::Result<TogglReportClient> {
+  let api_tokens = api_tokens(settings);
 
-  TogglReportClient::new(settings.api_token)
+  TogglReportClient::new(api_tokens, fixtures.to_owned())
 }
 
 impl TogglReportClient {
-  pub fn new(api_token: String) -> anyhow::Result<TogglReportClient> {
+  pub fn new(
+    api_tokens: ApiTokens,
+    fixtures: Fixtures,
+  ) -> anyhow::Result<TogglReportClient> {
     let base_url = "https://api.track.toggl.com/reports/api/v3/".parse()?;
 
     Ok(TogglReportClient {
-      base_url,
-      api_token,
+      http_client: HttpClient::new(base_url, api_tokens, fixtures)
+        .read_only()
+        .without_retries(),
+      retry_delay: std::time::Duration::from_secs(1),
+      checkpoint_dir: Some(paths::report_checkpoint_dir()?),
     })
   }
 
-  fn basic_auth(&self) -> (String, String) {
-    (
-      AUTHORIZATION.to_string(),
-      format!(
-        "Basic {}",
-        STANDARD.encode(format!("{}:api_token", &self.api_token))
-      ),
-    )
+  #[cfg(test)]
+  pub fn new_with_base_url(
+    api_token: String,
+    base_url: Url,
+  ) -> anyhow::Result<TogglReportClient> {
+    Ok(TogglReportClient {
+      http_client: HttpClient::new(base_url, api_token.into(), Fixtures::None)
+        .read_only()
+        .without_retries(),
+      retry_delay: std::time::Duration::ZERO,
+      checkpoint_dir: None,
+    })
   }
 
-  fn base_request(&self, method: Method, uri: &str) -> anyhow::Result<Request> {
-    let url = self.base_url.join(uri)?;
-
-    let (key, value) = self.basic_auth();
-
-    Ok(minreq::Request::new(method, url).with_header(key, value))
+  #[cfg(test)]
+  pub fn with_checkpoint_dir(mut self, dir: PathBuf) -> TogglReportClient {
+    self.checkpoint_dir = Some(dir);
+    self
   }
 
   fn request_with_body<D: DeserializeOwned + Debug, S: Serialize + Debug>(
@@ -65,75 +153,443 @@ impl TogglReportClient {
     uri: &str,
     body: S,
   ) -> anyhow::Result<(Option<u64>, D)> {
-    let request = self.base_request(method, uri)?.with_json(&body)?;
-
-    if debug {
-      println!("{}", "Request:".bold().underline());
-      println!("{request:?}");
-      println!();
-      println!("{:?}", &body);
-      println!();
-    }
-
-    let response = request.send()?;
+    let body = serde_json::to_value(&body)?;
+    let response = self.http_client.send(debug, method, uri, Some(body))?;
 
     let next_id = response
-      .headers
-      .get("x-next-row-number")
-      .map(|value| value.to_string())
+      .header("x-next-row-number")
       .and_then(|value| value.parse::<u64>().ok());
 
-    self.response(debug, response).map(|body| (next_id, body))
+    json_response(debug, response).map(|body| (next_id, body))
   }
 
-  fn response<D: DeserializeOwned + Debug>(
+  /// Details of the range, `resume` continues after the pages which were
+  /// fetched before the last attempt failed
+  pub fn detailed(
     &self,
     debug: bool,
-    response: Response,
-  ) -> anyhow::Result<D> {
-    if debug {
-      println!("{}", "Response:".bold().underline());
-      println!("{response:?}");
-      println!();
+    workspace_id: u64,
+    range: &Range,
+    resume: bool,
+  ) -> anyhow::Result<DetailedReport> {
+    let (start, end) = range.as_range()?;
+    let (start, end) = (start.date_naive(), end.date_naive());
+
+    let checkpoint_file = self
+      .checkpoint_dir
+      .as_ref()
+      .map(|dir| dir.join(format!("report-{workspace_id}-{start}-{end}.json")));
+
+    let mut checkpoint = match &checkpoint_file {
+      Some(file) if resume => {
+        let json = std::fs::read_to_string(file).map_err(|_| {
+          anyhow!("No interrupted report to resume for {start}|{end}")
+        })?;
+
+        serde_json::from_str::<Checkpoint>(&json)?
+      }
+      _ if resume => return Err(anyhow!("Resuming reports is not supported")),
+      _ => Checkpoint::default(),
+    };
+
+    let mut report_details = std::mem::take(&mut checkpoint.details);
+    let mut failures = vec![];
+    let mut finished = vec![];
+    let mut next_row_numbers = HashMap::new();
+    let mut page_number = 0;
+
+    for (start_date, end_date) in yearly_chunks(start, end) {
+      if checkpoint.finished.contains(&start_date) {
+        finished.push(start_date);
+        continue;
+      }
+
+      let mut next_row_number =
+        checkpoint.next_row_numbers.get(&start_date).copied();
+
+      loop {
+        let page = self.details_with_retries(
+          debug,
+          workspace_id,
+          start_date,
+          end_date,
+          next_row_number,
+        );
+
+        let (inner_next_row_number, details) = match page {
+          Ok(page) => page,
+          // no other page is going to succeed either
+          Err(err) if requires_paid_plan(&err) => return Err(err),
+          // without the failed page the next row number is unknown
+          Err(err) => {
+            failures.push(PageFailure {
+              start_date,
+              end_date,
+              first_row_number: next_row_number,
+              error: err.to_string(),
+            });
+
+            if let Some(row_number) = next_row_number {
+              next_row_numbers.insert(start_date, row_number);
+            }
+
+            break;
+          }
+        };
+
+        report_details.extend(details);
+
+        page_number += 1;
+        progress::emit(&ProgressEvent::PageFetched {
+          page: page_number,
+          rows: report_details.len(),
+        });
+
+        match inner_next_row_number {
+          Some(row_number) => next_row_number = Some(row_number),
+          None => {
+            finished.push(start_date);
+            break;
+          }
+        }
+      }
     }
 
-    match response.status_code {
-      200 | 201 if debug => match response.json() {
-        Ok(json) => {
-          println!("{}", "Received JSON response:".bold().underline());
-          println!("{json:?}");
-          println!();
+    if let Some(file) = &checkpoint_file {
+      if failures.is_empty() {
+        if file.exists() {
+          std::fs::remove_file(file)?;
+        }
+      } else {
+        let checkpoint = Checkpoint {
+          details: report_details,
+          finished,
+          next_row_numbers,
+        };
 
-          Ok(json)
+        if let Some(dir) = file.parent() {
+          std::fs::create_dir_all(dir)?;
         }
-        Err(err) => Err(anyhow!("Failed to deserialize JSON: {}", err)),
-      },
-      200 | 201 => Ok(response.json()?),
-      status => match response.as_str() {
-        Ok(text) => Err(anyhow!("{} - {}", status, text)),
-        Err(_) => Err(anyhow!("{}", status)),
-      },
+
+        std::fs::write(file, serde_json::to_string(&checkpoint)?)?;
+
+        report_details = checkpoint.details;
+      }
+    }
+
+    progress::emit(&ProgressEvent::Done {
+      operation: "report".to_string(),
+    });
+
+    if report_details.is_empty() {
+      if let Some(failure) = failures.first() {
+        return Err(anyhow!("{}", failure.error));
+      }
     }
+
+    Ok(DetailedReport {
+      details: report_details,
+      failures,
+    })
   }
 
-  pub fn details(
+  fn details_with_retries(
     &self,
     debug: bool,
     workspace_id: u64,
-    range: &Range,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
     first_row_number: Option<u64>,
   ) -> anyhow::Result<(Option<u64>, Vec<ReportDetails>)> {
-    let (start, end) = range.as_range()?;
+    let mut attempt = 1;
+
+    loop {
+      match self.details(
+        debug,
+        workspace_id,
+        start_date,
+        end_date,
+        first_row_number,
+      ) {
+        Err(err) if attempt < PAGE_ATTEMPTS && is_transient_error(&err) => {
+          let delay = retry_after(&err).unwrap_or(self.retry_delay * attempt);
+
+          progress::emit(&progress::retry_event(
+            format!("report page {start_date}|{end_date}"),
+            is_rate_limited(&err),
+            attempt,
+            PAGE_ATTEMPTS - 1,
+            delay,
+          ));
+
+          std::thread::sleep(delay);
+
+          attempt += 1;
+        }
+        page => return page,
+      }
+    }
+  }
 
+  pub fn details(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    first_row_number: Option<u64>,
+  ) -> anyhow::Result<(Option<u64>, Vec<ReportDetails>)> {
     let uri = format!("workspace/{workspace_id}/search/time_entries");
 
     let body = json!({
-      "start_date": start.naive_local().format("%Y-%m-%d").to_string(),
+      "start_date": start_date.format("%Y-%m-%d").to_string(),
       "created_with": CREATED_WITH,
-      "end_date":end.naive_local().format("%Y-%m-%d").to_string(),
+      "end_date": end_date.format("%Y-%m-%d").to_string(),
       "first_row_number": first_row_number,
+      // amounts and rates are never evaluated, so they are not transferred
+      "hide_amounts": true,
     });
 
     self.request_with_body(debug, Method::Post, &uri, body)
   }
+
+  /// Detailed report of the range as CSV file of Toggl
+  pub fn export_csv(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    range: &Range,
+  ) -> anyhow::Result<Vec<u8>> {
+    self.export(debug, workspace_id, range, "csv")
+  }
+
+  /// Detailed report of the range as PDF file in the layout of Toggl
+  pub fn export_pdf(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    range: &Range,
+  ) -> anyhow::Result<Vec<u8>> {
+    self.export(debug, workspace_id, range, "pdf")
+  }
+
+  fn export(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    range: &Range,
+    extension: &str,
+  ) -> anyhow::Result<Vec<u8>> {
+    let (start, end) = range.as_range()?;
+
+    // unlike pages, files of several requests cannot be joined
+    let [(start_date, end_date)] =
+      yearly_chunks(start.date_naive(), end.date_naive())[..]
+    else {
+      return Err(anyhow!("Exports are limited to ranges of one year"));
+    };
+
+    let uri =
+      format!("workspace/{workspace_id}/search/time_entries.{extension}");
+
+    let body = json!({
+      "start_date": start_date.format("%Y-%m-%d").to_string(),
+      "created_with": CREATED_WITH,
+      "end_date": end_date.format("%Y-%m-%d").to_string(),
+    });
+
+    let response =
+      self
+        .http_client
+        .send(debug, Method::Post, &uri, Some(body))?;
+
+    bytes_response(response)
+  }
+
+  /// Logged time per user, only billable time with `billable_only`
+  pub fn summary_by_user(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    user_id: Option<u64>,
+    range: &Range,
+    billable_only: bool,
+  ) -> anyhow::Result<HashMap<u64, Duration>> {
+    let summary = self.project_summary_by_user(
+      debug,
+      workspace_id,
+      user_id,
+      range,
+      billable_only,
+    )?;
+
+    Ok(
+      summary
+        .into_iter()
+        .map(|(user_id, projects)| {
+          (
+            user_id,
+            projects.into_values().fold(Duration::zero(), |a, b| a + b),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  /// Logged time per user and project (None without project), only
+  /// billable time with `billable_only`
+  pub fn project_summary_by_user(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    user_id: Option<u64>,
+    range: &Range,
+    billable_only: bool,
+  ) -> anyhow::Result<HashMap<u64, HashMap<Option<u64>, Duration>>> {
+    let (start, end) = range.as_range()?;
+
+    let mut summary = HashMap::new();
+
+    for (start_date, end_date) in
+      yearly_chunks(start.date_naive(), end.date_naive())
+    {
+      let uri = format!("workspace/{workspace_id}/summary/time_entries");
+
+      let mut body = json!({
+        "start_date": start_date.format("%Y-%m-%d").to_string(),
+        "created_with": CREATED_WITH,
+        "end_date": end_date.format("%Y-%m-%d").to_string(),
+        "grouping": "users",
+        "sub_grouping": "projects",
+        "include_time_entry_ids": false,
+      });
+
+      if billable_only {
+        body["billable"] = json!(true);
+      }
+
+      if let Some(user_id) = user_id {
+        body["user_ids"] = json!([user_id]);
+      }
+
+      let (_, report_summary): (_, ReportSummary) =
+        self.request_with_body(debug, Method::Post, &uri, body)?;
+
+      for group in report_summary.groups {
+        let Some(user_id) = group.id else {
+          continue;
+        };
+
+        let projects = summary.entry(user_id).or_insert_with(HashMap::new);
+
+        for sub_group in group.sub_groups {
+          *projects.entry(sub_group.id).or_insert_with(Duration::zero) +=
+            Duration::try_seconds(sub_group.seconds as i64).unwrap_or_default();
+        }
+      }
+    }
+
+    Ok(summary)
+  }
+
+  /// Logged time per day of one user (or the whole workspace without user),
+  /// None if the API does not answer with a daily resolution
+  pub fn daily_totals(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    user_id: Option<u64>,
+    range: &Range,
+  ) -> anyhow::Result<Option<HashMap<NaiveDate, Duration>>> {
+    let (start, end) = range.as_range()?;
+
+    let mut daily_totals = HashMap::new();
+
+    for (start_date, end_date) in
+      inclusive_monthly_chunks(start.date_naive(), end.date_naive())
+    {
+      let totals =
+        self.totals(debug, workspace_id, user_id, start_date, end_date)?;
+
+      if totals.resolution.as_deref() != Some("day") {
+        return Ok(None);
+      }
+
+      for (date, point) in start_date.iter_days().zip(totals.graph) {
+        if point.seconds > 0 {
+          daily_totals.insert(
+            date,
+            Duration::try_seconds(point.seconds as i64).unwrap_or_default(),
+          );
+        }
+      }
+    }
+
+    Ok(Some(daily_totals))
+  }
+
+  pub fn totals(
+    &self,
+    debug: bool,
+    workspace_id: u64,
+    user_id: Option<u64>,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+  ) -> anyhow::Result<ReportTotals> {
+    let uri = format!("workspace/{workspace_id}/search/time_entries/totals");
+
+    let mut body = json!({
+      "start_date": start_date.format("%Y-%m-%d").to_string(),
+      "created_with": CREATED_WITH,
+      "end_date": end_date.format("%Y-%m-%d").to_string(),
+      "with_graph": true,
+    });
+
+    if let Some(user_id) = user_id {
+      body["user_ids"] = json!([user_id]);
+    }
+
+    self
+      .request_with_body(debug, Method::Post, &uri, body)
+      .map(|(_, totals)| totals)
+  }
+}
+
+// The Reports API rejects ranges longer than one year,
+// therefore longer ranges are split into consecutive yearly chunks
+pub(crate) fn yearly_chunks(
+  start_date: NaiveDate,
+  end_date: NaiveDate,
+) -> Vec<(NaiveDate, NaiveDate)> {
+  chunks(start_date, end_date, |date| shift_years(date, 1))
+}
+
+// Totals come with a daily graph only for ranges up to one month. Unlike
+// `client::monthly_chunks` the chunks start at `start_date` instead of the
+// 1st and the end date of each chunk is inclusive, as the Reports API expects
+pub(crate) fn inclusive_monthly_chunks(
+  start_date: NaiveDate,
+  end_date: NaiveDate,
+) -> Vec<(NaiveDate, NaiveDate)> {
+  chunks(start_date, end_date, |date| shift_months(date, 1))
+}
+
+fn chunks(
+  start_date: NaiveDate,
+  end_date: NaiveDate,
+  next_start: fn(NaiveDate) -> NaiveDate,
+) -> Vec<(NaiveDate, NaiveDate)> {
+  let mut chunks = vec![];
+  let mut chunk_start = start_date;
+
+  while chunk_start <= end_date {
+    let chunk_end =
+      (next_start(chunk_start) - Duration::try_days(1).unwrap()).min(end_date);
+
+    chunks.push((chunk_start, chunk_end));
+
+    chunk_start = chunk_end + Duration::try_days(1).unwrap();
+  }
+
+  chunks
 }
@@ -0,0 +1,587 @@
+use crate::{
+  model::Range,
+  report_client::{
+    inclusive_monthly_chunks, requires_paid_plan, yearly_chunks,
+    TogglReportClient, CREATED_WITH,
+  },
+};
+use chrono::{Duration, NaiveDate};
+use mockito::Matcher;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test]
+fn test_yearly_chunks() {
+  let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+  assert_eq!(
+    yearly_chunks(date(2022, 1, 1), date(2022, 1, 1)),
+    vec![(date(2022, 1, 1), date(2022, 1, 1))]
+  );
+
+  assert_eq!(
+    yearly_chunks(date(2022, 1, 1), date(2022, 12, 31)),
+    vec![(date(2022, 1, 1), date(2022, 12, 31))]
+  );
+
+  assert_eq!(
+    yearly_chunks(date(2022, 1, 1), date(2024, 1, 2)),
+    vec![
+      (date(2022, 1, 1), date(2022, 12, 31)),
+      (date(2023, 1, 1), date(2023, 12, 31)),
+      (date(2024, 1, 1), date(2024, 1, 2)),
+    ]
+  );
+}
+
+#[test]
+fn detailed_splits_long_ranges() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let first_body = json!([
+    {
+      "username": "fkbr",
+      "time_entries": [
+        {
+          "id": 1,
+          "start": "2022-03-01T08:00:00+00:00",
+          "stop": "2022-03-01T09:00:00+00:00",
+          "seconds": 3600
+        }
+      ]
+    }
+  ]);
+
+  let second_body = json!([
+    {
+      "username": "fkbr",
+      "time_entries": [
+        {
+          "id": 2,
+          "start": "2023-03-01T08:00:00+00:00",
+          "stop": "2023-03-01T10:00:00+00:00",
+          "seconds": 7200
+        }
+      ]
+    }
+  ]);
+
+  let first_mock = server
+    .mock("POST", "/workspace/123/search/time_entries")
+    .with_header(
+      "Authorization",
+      "Basic Y2I3YmY3ZWZhNmQ2NTIwNDZhYmQyZjdkODRlZTE4YzE6YXBpX3Rva2Vu",
+    )
+    .match_body(Matcher::Json(json!({
+      "start_date": "2022-01-01",
+      "end_date": "2022-12-31",
+      "created_with": CREATED_WITH,
+      "first_row_number": null,
+      "hide_amounts": true,
+    })))
+    .with_status(200)
+    .with_body(first_body.to_string())
+    .expect(1)
+    .create();
+
+  let second_mock = server
+    .mock("POST", "/workspace/123/search/time_entries")
+    .match_body(Matcher::Json(json!({
+      "start_date": "2023-01-01",
+      "end_date": "2023-06-02",
+      "created_with": CREATED_WITH,
+      "first_row_number": null,
+      "hide_amounts": true,
+    })))
+    .with_status(200)
+    .with_body(second_body.to_string())
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglReportClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let report = client.detailed(
+      false,
+      123,
+      &"2022-01-01|2023-06-01".parse::<Range>()?,
+      false,
+    )?;
+
+    assert!(report.failures.is_empty());
+
+    let ids = report
+      .details
+      .iter()
+      .flat_map(|detail| detail.time_entries.iter().map(|entry| entry.id))
+      .collect::<Vec<u64>>();
+
+    assert_eq!(ids, vec![1, 2]);
+  }
+
+  first_mock.assert();
+  second_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_inclusive_monthly_chunks() {
+  let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+  assert_eq!(
+    inclusive_monthly_chunks(date(2023, 1, 15), date(2023, 3, 1)),
+    vec![
+      (date(2023, 1, 15), date(2023, 2, 14)),
+      (date(2023, 2, 15), date(2023, 3, 1)),
+    ]
+  );
+}
+
+#[test]
+fn daily_totals_from_graph() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let first_mock = server
+    .mock("POST", "/workspace/123/search/time_entries/totals")
+    .match_body(Matcher::Json(json!({
+      "start_date": "2023-01-01",
+      "end_date": "2023-01-31",
+      "created_with": CREATED_WITH,
+      "with_graph": true,
+      "user_ids": [42],
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "seconds": 10800,
+        "resolution": "day",
+        "graph": [
+          { "seconds": 0 },
+          { "seconds": 3600 },
+          { "seconds": 7200 }
+        ]
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let second_mock = server
+    .mock("POST", "/workspace/123/search/time_entries/totals")
+    .match_body(Matcher::PartialJson(json!({
+      "start_date": "2023-02-01",
+      "end_date": "2023-02-02",
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "seconds": 1800,
+        "resolution": "day",
+        "graph": [{ "seconds": 1800 }]
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglReportClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    let totals = client
+      .daily_totals(
+        false,
+        123,
+        Some(42),
+        &"2023-01-01|2023-02-01".parse::<Range>()?,
+      )?
+      .unwrap();
+
+    assert_eq!(totals.len(), 3);
+    assert_eq!(totals[&date(2023, 1, 2)], Duration::try_hours(1).unwrap());
+    assert_eq!(totals[&date(2023, 1, 3)], Duration::try_hours(2).unwrap());
+    assert_eq!(
+      totals[&date(2023, 2, 1)],
+      Duration::try_minutes(30).unwrap()
+    );
+  }
+
+  first_mock.assert();
+  second_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn daily_totals_without_daily_resolution() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("POST", "/workspace/123/search/time_entries/totals")
+    .with_status(200)
+    .with_body(
+      json!({
+        "seconds": 3600,
+        "resolution": "week",
+        "graph": [{ "seconds": 3600 }]
+      })
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglReportClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let totals = client.daily_totals(
+      false,
+      123,
+      None,
+      &"2023-01-01|2023-01-31".parse::<Range>()?,
+    )?;
+
+    assert!(totals.is_none());
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn detailed_retries_failed_pages() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let first_range = Matcher::PartialJson(json!({
+    "start_date": "2022-01-01",
+    "end_date": "2022-12-31",
+  }));
+
+  let second_range = Matcher::PartialJson(json!({
+    "start_date": "2023-01-01",
+    "end_date": "2023-06-02",
+  }));
+
+  let transient_mock = server
+    .mock("POST", "/workspace/123/search/time_entries")
+    .match_body(first_range.clone())
+    .with_status(503)
+    .expect(1)
+    .create();
+
+  let retried_mock = server
+    .mock("POST", "/workspace/123/search/time_entries")
+    .match_body(first_range)
+    .with_status(200)
+    .with_body(
+      json!([
+        {
+          "username": "fkbr",
+          "time_entries": [
+            {
+              "id": 1,
+              "start": "2022-03-01T08:00:00+00:00",
+              "stop": "2022-03-01T09:00:00+00:00",
+              "seconds": 3600
+            }
+          ]
+        }
+      ])
+      .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let failing_mock = server
+    .mock("POST", "/workspace/123/search/time_entries")
+    .match_body(second_range)
+    .with_status(502)
+    .with_body("Bad Gateway")
+    .expect(3)
+    .create();
+
+  {
+    let client = TogglReportClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let report = client.detailed(
+      false,
+      123,
+      &"2022-01-01|2023-06-01".parse::<Range>()?,
+      false,
+    )?;
+
+    let ids = report
+      .details
+      .iter()
+      .flat_map(|detail| detail.time_entries.iter().map(|entry| entry.id))
+      .collect::<Vec<u64>>();
+
+    assert_eq!(ids, vec![1]);
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(
+      report.failures[0].to_string(),
+      "2023-01-01|2023-06-02 from row 1: 502 - Bad Gateway"
+    );
+  }
+
+  transient_mock.assert();
+  retried_mock.assert();
+  failing_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn summary_by_user_sums_sub_groups() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let mock = server
+    .mock("POST", "/workspace/123/summary/time_entries")
+    .match_body(Matcher::Json(json!({
+      "start_date": "2023-01-01",
+      "end_date": "2023-01-08",
+      "created_with": CREATED_WITH,
+      "grouping": "users",
+      "sub_grouping": "projects",
+      "billable": true,
+      "include_time_entry_ids": false,
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "groups": [
+          {
+            "id": 1,
+            "sub_groups": [
+              { "id": 10, "seconds": 3600 },
+              { "id": null, "seconds": 1800 }
+            ]
+          },
+          {
+            "id": 2,
+            "sub_groups": [{ "id": 10, "seconds": 7200 }]
+          }
+        ]
+      })
+      .to_string(),
+    )
+    .expect(2)
+    .create();
+
+  {
+    let client = TogglReportClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let summary = client.summary_by_user(
+      false,
+      123,
+      None,
+      &"2023-01-01|2023-01-07".parse::<Range>()?,
+      true,
+    )?;
+
+    assert_eq!(summary[&1], Duration::try_minutes(90).unwrap());
+    assert_eq!(summary[&2], Duration::try_hours(2).unwrap());
+
+    let projects = client.project_summary_by_user(
+      false,
+      123,
+      None,
+      &"2023-01-01|2023-01-07".parse::<Range>()?,
+      true,
+    )?;
+
+    assert_eq!(projects[&1][&Some(10)], Duration::try_hours(1).unwrap());
+    assert_eq!(projects[&1][&None], Duration::try_minutes(30).unwrap());
+  }
+
+  mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn detailed_resumes_from_checkpoint() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let checkpoint_dir = std::env::temp_dir()
+    .join(format!("fbtoggl-checkpoint-{}", std::process::id()));
+
+  let first_range = Matcher::PartialJson(json!({
+    "start_date": "2022-01-01",
+    "end_date": "2022-12-31",
+  }));
+
+  let second_range = Matcher::PartialJson(json!({
+    "start_date": "2023-01-01",
+    "end_date": "2023-06-02",
+  }));
+
+  let details = |id: u64, start: &str| {
+    json!([
+      {
+        "username": "fkbr",
+        "time_entries": [
+          { "id": id, "start": start, "stop": start, "seconds": 0 }
+        ]
+      }
+    ])
+    .to_string()
+  };
+
+  let first_mock = server
+    .mock("POST", "/workspace/456/search/time_entries")
+    .match_body(first_range)
+    .with_status(200)
+    .with_body(details(1, "2022-03-01T08:00:00+00:00"))
+    .expect(1)
+    .create();
+
+  let failing_mock = server
+    .mock("POST", "/workspace/456/search/time_entries")
+    .match_body(second_range.clone())
+    .with_status(502)
+    .expect(3)
+    .create();
+
+  let resumed_mock = server
+    .mock("POST", "/workspace/456/search/time_entries")
+    .match_body(second_range)
+    .with_status(200)
+    .with_body(details(2, "2023-03-01T08:00:00+00:00"))
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglReportClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?
+    .with_checkpoint_dir(checkpoint_dir.clone());
+
+    let range = "2022-01-01|2023-06-01".parse::<Range>()?;
+
+    let report = client.detailed(false, 456, &range, false)?;
+
+    assert_eq!(report.failures.len(), 1);
+
+    let report = client.detailed(false, 456, &range, true)?;
+
+    let ids = report
+      .details
+      .iter()
+      .flat_map(|detail| detail.time_entries.iter().map(|entry| entry.id))
+      .collect::<Vec<u64>>();
+
+    assert_eq!(ids, vec![1, 2]);
+    assert!(report.failures.is_empty());
+
+    // the checkpoint is gone after the complete report
+    assert!(client.detailed(false, 456, &range, true).is_err());
+  }
+
+  first_mock.assert();
+  failing_mock.assert();
+  resumed_mock.assert();
+
+  std::fs::remove_dir_all(checkpoint_dir)?;
+
+  Ok(())
+}
+
+#[test]
+fn detailed_requires_paid_plan() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  // the remaining chunks are not requested anymore
+  let mock = server
+    .mock("POST", "/workspace/123/search/time_entries")
+    .with_status(402)
+    .with_body("Payment Required")
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglReportClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let err = client
+      .detailed(
+        false,
+        123,
+        &"2022-01-01|2023-06-01".parse::<Range>()?,
+        false,
+      )
+      .err()
+      .unwrap();
+
+    assert!(requires_paid_plan(&err));
+
+    mock.assert();
+  }
+
+  Ok(())
+}
+
+#[test]
+fn export_pdf_keeps_binary_body() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let pdf = b"%PDF-1.4\n\xe2\xe3\xcf\xd3\n".to_vec();
+
+  let mock = server
+    .mock("POST", "/workspace/123/search/time_entries.pdf")
+    .match_body(Matcher::Json(json!({
+      "start_date": "2023-01-01",
+      "end_date": "2023-01-08",
+      "created_with": CREATED_WITH,
+    })))
+    .with_status(200)
+    .with_header("content-type", "application/pdf")
+    .with_body(&pdf)
+    .expect(1)
+    .create();
+
+  {
+    let client = TogglReportClient::new_with_base_url(
+      "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+      server.url().parse()?,
+    )?;
+
+    let range = "2023-01-01|2023-01-07".parse::<Range>()?;
+
+    assert_eq!(client.export_pdf(false, 123, &range)?, pdf);
+
+    let range = "2022-01-01|2023-01-07".parse::<Range>()?;
+
+    assert_eq!(
+      client
+        .export_csv(false, 123, &range)
+        .unwrap_err()
+        .to_string(),
+      "Exports are limited to ranges of one year"
+    );
+  }
+
+  mock.assert();
+
+  Ok(())
+}
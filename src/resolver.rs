@@ -0,0 +1,494 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use dialoguer::Select;
+
+use crate::client::TogglApi;
+use crate::config::is_non_billable_project;
+use crate::config::Aliases;
+use crate::model::Client;
+use crate::model::Project;
+use crate::model::Range;
+use crate::model::Tag;
+use crate::model::TimeEntryId;
+use crate::model::Workspace;
+
+/// Resolves names (or ids) of workspaces, projects, clients, tags and tasks.
+///
+/// Lookups are case-insensitive and accept the aliases from the settings,
+/// every list is fetched at most once per resolver.
+pub struct Resolver<'a> {
+  debug: bool,
+  client: &'a dyn TogglApi,
+  aliases: Aliases,
+  non_billable_projects: Vec<String>,
+  create_missing_projects: bool,
+  default_workspace_id: Option<u64>,
+  workspaces: RefCell<Option<Vec<Workspace>>>,
+  projects: RefCell<HashMap<u64, Vec<Project>>>,
+  clients: RefCell<HashMap<u64, Vec<Client>>>,
+  tags: RefCell<HashMap<u64, Vec<Tag>>>,
+}
+
+impl<'a> Resolver<'a> {
+  pub fn new(debug: bool, client: &'a dyn TogglApi) -> Resolver<'a> {
+    let settings = client.settings();
+
+    let mut resolver =
+      Resolver::with_aliases(debug, client, settings.aliases.clone())
+        .with_non_billable_projects(settings.non_billable_projects.clone())
+        .with_default_workspace_id(settings.workspace_id);
+    resolver.create_missing_projects = settings.create_missing_projects;
+
+    resolver
+  }
+
+  pub fn with_aliases(
+    debug: bool,
+    client: &'a dyn TogglApi,
+    aliases: Aliases,
+  ) -> Resolver<'a> {
+    Resolver {
+      debug,
+      client,
+      aliases,
+      non_billable_projects: vec![],
+      create_missing_projects: false,
+      default_workspace_id: None,
+      workspaces: RefCell::new(None),
+      projects: RefCell::new(HashMap::new()),
+      clients: RefCell::new(HashMap::new()),
+      tags: RefCell::new(HashMap::new()),
+    }
+  }
+
+  pub fn with_non_billable_projects(
+    mut self,
+    non_billable_projects: Vec<String>,
+  ) -> Resolver<'a> {
+    self.non_billable_projects = non_billable_projects;
+    self
+  }
+
+  /// Workspace without --workspace instead of the default workspace of the
+  /// account, see 'workspace_id' in the settings
+  pub fn with_default_workspace_id(
+    mut self,
+    default_workspace_id: Option<u64>,
+  ) -> Resolver<'a> {
+    self.default_workspace_id = default_workspace_id;
+    self
+  }
+
+  /// Whether time entries of the (previously resolved) project are never
+  /// billable, see 'non_billable_projects' in the settings
+  pub fn is_non_billable_project(
+    &self,
+    workspace_id: u64,
+    project_id: u64,
+  ) -> bool {
+    self
+      .projects
+      .borrow()
+      .get(&workspace_id)
+      .and_then(|projects| projects.iter().find(|p| p.id == project_id))
+      .is_some_and(|project| {
+        is_non_billable_project(&self.non_billable_projects, project)
+      })
+  }
+
+  /// Workspace selected via --workspace, otherwise 'workspace_id' of the
+  /// settings or the default workspace of the account
+  pub fn workspace_id(&self) -> anyhow::Result<u64> {
+    let Some(selector) = self.client.workspace() else {
+      return match self.default_workspace_id {
+        Some(workspace_id) => Ok(workspace_id),
+        None => Ok(self.client.get_me(self.debug)?.default_workspace_id),
+      };
+    };
+
+    let selector = unalias(&self.aliases.workspaces, selector);
+
+    if self.workspaces.borrow().is_none() {
+      let workspaces = self.client.get_workspaces(self.debug)?;
+      self.workspaces.replace(Some(workspaces));
+    }
+
+    let workspaces = self.workspaces.borrow();
+    let workspaces = workspaces.as_deref().unwrap_or_default();
+
+    match matching_workspaces(selector, workspaces).as_slice() {
+      [] => Err(anyhow!("Cannot find workspace='{selector}'")),
+      [workspace] => Ok(workspace.id),
+      candidates => {
+        let items = candidates
+          .iter()
+          .enumerate()
+          .map(|(index, workspace)| {
+            format!("{}) {} ({})", index + 1, workspace.name, workspace.id)
+          })
+          .collect::<Vec<String>>();
+
+        let selection = Select::new()
+          .with_prompt(format!("Multiple workspaces match '{selector}'"))
+          .items(&items)
+          .default(0)
+          .interact()?;
+
+        Ok(candidates[selection].id)
+      }
+    }
+  }
+
+  /// Active project by id or name
+  pub fn project_id(
+    &self,
+    workspace_id: u64,
+    selector: &str,
+  ) -> anyhow::Result<u64> {
+    if !self.projects.borrow().contains_key(&workspace_id) {
+      let projects =
+        self
+          .client
+          .get_workspace_projects(self.debug, false, workspace_id)?;
+      self.projects.borrow_mut().insert(workspace_id, projects);
+    }
+
+    let projects = self.projects.borrow();
+    let selector = unalias(&self.aliases.projects, selector);
+
+    resolve(
+      "project",
+      selector,
+      &projects[&workspace_id],
+      |project| project.id,
+      |project| &project.name,
+    )
+  }
+
+  /// Active project by id or name, a project of this name which does not
+  /// exist yet is created (with the client) if `create_missing` or
+  /// 'create_missing_projects' in the settings is set
+  pub fn project_id_or_create(
+    &self,
+    workspace_id: u64,
+    selector: &str,
+    create_missing: bool,
+    client_selector: Option<&str>,
+  ) -> anyhow::Result<u64> {
+    let project_id = self.project_id(workspace_id, selector);
+
+    if project_id.is_ok() || !(create_missing || self.create_missing_projects) {
+      return project_id;
+    }
+
+    let name = unalias(&self.aliases.projects, selector);
+
+    // only missing projects are created, not ambiguous ones (or on errors
+    // before the projects were fetched)
+    let missing =
+      self
+        .projects
+        .borrow()
+        .get(&workspace_id)
+        .is_some_and(|projects| {
+          !projects.iter().any(|project| {
+            project.id.to_string() == name
+              || project.name.to_lowercase() == name.to_lowercase()
+          })
+        });
+
+    if !missing {
+      return project_id;
+    }
+
+    let client_id = client_selector
+      .map(|selector| self.client_id(workspace_id, selector))
+      .transpose()?;
+
+    let project = self.client.create_project(
+      self.debug,
+      workspace_id,
+      name,
+      client_id,
+      None,
+    )?;
+
+    eprintln!("Created project '{}' ({})", project.name, project.id);
+
+    let project_id = project.id;
+
+    self
+      .projects
+      .borrow_mut()
+      .entry(workspace_id)
+      .or_default()
+      .push(project);
+
+    Ok(project_id)
+  }
+
+  /// Active or archived project by id or name
+  pub fn any_project_id(
+    &self,
+    workspace_id: u64,
+    selector: &str,
+  ) -> anyhow::Result<u64> {
+    let projects =
+      self
+        .client
+        .get_workspace_projects(self.debug, true, workspace_id)?;
+    let selector = unalias(&self.aliases.projects, selector);
+
+    resolve(
+      "project",
+      selector,
+      &projects,
+      |project| project.id,
+      |project| &project.name,
+    )
+  }
+
+  /// Project template by id or name
+  pub fn project_template_id(
+    &self,
+    workspace_id: u64,
+    selector: &str,
+  ) -> anyhow::Result<u64> {
+    let templates = self
+      .client
+      .get_project_templates(self.debug, workspace_id)?;
+
+    resolve(
+      "project template",
+      selector,
+      &templates,
+      |template| template.id,
+      |template| &template.name,
+    )
+  }
+
+  /// Active client by id or name
+  pub fn client_id(
+    &self,
+    workspace_id: u64,
+    selector: &str,
+  ) -> anyhow::Result<u64> {
+    if !self.clients.borrow().contains_key(&workspace_id) {
+      let clients = self
+        .client
+        .get_workspace_clients(self.debug, false, workspace_id)?
+        .unwrap_or_default();
+      self.clients.borrow_mut().insert(workspace_id, clients);
+    }
+
+    let clients = self.clients.borrow();
+    let selector = unalias(&self.aliases.clients, selector);
+
+    resolve(
+      "client",
+      selector,
+      &clients[&workspace_id],
+      |client| client.id,
+      |client| &client.name,
+    )
+  }
+
+  /// Active or archived client by id or name
+  pub fn any_client_id(
+    &self,
+    workspace_id: u64,
+    selector: &str,
+  ) -> anyhow::Result<u64> {
+    let clients = self
+      .client
+      .get_workspace_clients(self.debug, true, workspace_id)?
+      .unwrap_or_default();
+    let selector = unalias(&self.aliases.clients, selector);
+
+    resolve(
+      "client",
+      selector,
+      &clients,
+      |client| client.id,
+      |client| &client.name,
+    )
+  }
+
+  /// Existing tag by id or name
+  pub fn tag_id(
+    &self,
+    workspace_id: u64,
+    selector: &str,
+  ) -> anyhow::Result<u64> {
+    self.load_tags(workspace_id)?;
+
+    let tags = self.tags.borrow();
+    let selector = unalias(&self.aliases.tags, selector);
+
+    resolve(
+      "tag",
+      selector,
+      &tags[&workspace_id],
+      |tag| tag.id,
+      |tag| &tag.name,
+    )
+  }
+
+  /// Active task of the project by id or name
+  pub fn task_id(
+    &self,
+    workspace_id: u64,
+    project_id: u64,
+    selector: &str,
+  ) -> anyhow::Result<u64> {
+    let tasks = self
+      .client
+      .get_project_tasks(self.debug, workspace_id, project_id)?
+      .into_iter()
+      .filter(|task| task.active)
+      .collect::<Vec<_>>();
+
+    resolve("task", selector, &tasks, |task| task.id, |task| &task.name)
+  }
+
+  fn load_tags(&self, workspace_id: u64) -> anyhow::Result<()> {
+    if !self.tags.borrow().contains_key(&workspace_id) {
+      let workspace_tags =
+        self.client.get_workspace_tags(self.debug, workspace_id)?;
+      self.tags.borrow_mut().insert(workspace_id, workspace_tags);
+    }
+
+    Ok(())
+  }
+
+  /// Spelling of existing tags, unknown tags are kept as they are and
+  /// created by Toggl
+  pub fn tags(
+    &self,
+    workspace_id: u64,
+    tags: &Option<Vec<String>>,
+  ) -> anyhow::Result<Option<Vec<String>>> {
+    let Some(tags) = tags else {
+      return Ok(None);
+    };
+
+    self.load_tags(workspace_id)?;
+
+    let workspace_tags = self.tags.borrow();
+
+    let resolved = tags
+      .iter()
+      .map(|tag| {
+        let tag = unalias(&self.aliases.tags, tag);
+
+        workspace_tags[&workspace_id]
+          .iter()
+          .find(|workspace_tag| workspace_tag.name.eq_ignore_ascii_case(tag))
+          .map(|workspace_tag| workspace_tag.name.to_owned())
+          .unwrap_or_else(|| tag.to_string())
+      })
+      .collect();
+
+    Ok(Some(resolved))
+  }
+
+  /// Numeric id of a time entry, GUIDs are only searched in this and last
+  /// month to not download the whole history
+  pub fn time_entry_id(
+    &self,
+    time_entry_id: &TimeEntryId,
+  ) -> anyhow::Result<u64> {
+    let guid = match time_entry_id {
+      TimeEntryId::Id(id) => return Ok(*id),
+      TimeEntryId::Guid(guid) => guid,
+    };
+
+    for range in [Range::ThisMonth, Range::LastMonth] {
+      let time_entries = self.client.get_time_entries(self.debug, &range)?;
+
+      if let Some(time_entry) = time_entries.iter().find(|time_entry| {
+        time_entry
+          .guid
+          .as_ref()
+          .is_some_and(|entry_guid| entry_guid.eq_ignore_ascii_case(guid))
+      }) {
+        return Ok(time_entry.id);
+      }
+    }
+
+    Err(anyhow!(
+      "Cannot find time entry with GUID='{guid}' in this or last month, \
+       use its numeric id instead"
+    ))
+  }
+}
+
+fn unalias<'b>(
+  aliases: &'b HashMap<String, String>,
+  selector: &'b str,
+) -> &'b str {
+  aliases
+    .iter()
+    .find(|(alias, _)| alias.eq_ignore_ascii_case(selector))
+    .map(|(_, name)| name.as_str())
+    .unwrap_or(selector)
+}
+
+// Exact id or name matches win over case-insensitive name matches,
+// the latter must be unique
+pub(crate) fn resolve<T>(
+  kind: &str,
+  selector: &str,
+  items: &[T],
+  id: fn(&T) -> u64,
+  name: fn(&T) -> &str,
+) -> anyhow::Result<u64> {
+  if let Some(item) = items
+    .iter()
+    .find(|item| id(item).to_string() == selector || name(item) == selector)
+  {
+    return Ok(id(item));
+  }
+
+  let matches = items
+    .iter()
+    .filter(|item| name(item).to_lowercase() == selector.to_lowercase())
+    .collect::<Vec<&T>>();
+
+  match matches.as_slice() {
+    [] => Err(anyhow!("Cannot find {kind}='{selector}'")),
+    [item] => Ok(id(item)),
+    _ => Err(anyhow!(
+      "Multiple {kind}s match '{selector}': {}",
+      matches
+        .iter()
+        .map(|item| format!("{} ({})", name(item), id(item)))
+        .collect::<Vec<String>>()
+        .join(", ")
+    )),
+  }
+}
+
+// Exact id or name matches win over (case-insensitive) partial name matches,
+// so scripts never run into the selection prompt
+pub(crate) fn matching_workspaces<'b>(
+  selector: &str,
+  workspaces: &'b [Workspace],
+) -> Vec<&'b Workspace> {
+  let exact_match = workspaces.iter().find(|workspace| {
+    workspace.id.to_string() == selector || workspace.name == selector
+  });
+
+  if let Some(workspace) = exact_match {
+    return vec![workspace];
+  }
+
+  let selector = selector.to_lowercase();
+
+  workspaces
+    .iter()
+    .filter(|workspace| workspace.name.to_lowercase().contains(&selector))
+    .collect()
+}
@@ -0,0 +1,314 @@
+use crate::{
+  client::TogglClient,
+  config::Aliases,
+  model::{Project, TimeEntryId, Workspace},
+  resolver::{matching_workspaces, resolve, Resolver},
+};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn workspaces() -> Vec<Workspace> {
+  vec![
+    Workspace {
+      id: 1,
+      name: "ACME Corp".to_string(),
+      ..Default::default()
+    },
+    Workspace {
+      id: 2,
+      name: "ACME Labs".to_string(),
+      ..Default::default()
+    },
+    Workspace {
+      id: 3,
+      name: "fkbr".to_string(),
+      ..Default::default()
+    },
+  ]
+}
+
+fn matching_ids(selector: &str) -> Vec<u64> {
+  matching_workspaces(selector, &workspaces())
+    .iter()
+    .map(|workspace| workspace.id)
+    .collect()
+}
+
+#[test]
+fn test_matching_workspaces() {
+  assert_eq!(matching_ids("2"), vec![2]);
+  assert_eq!(matching_ids("ACME Labs"), vec![2]);
+  assert_eq!(matching_ids("acme"), vec![1, 2]);
+  assert_eq!(matching_ids("KBR"), vec![3]);
+  assert_eq!(matching_ids("unknown"), Vec::<u64>::new());
+}
+
+#[test]
+fn test_resolve() {
+  let workspaces = vec![
+    Workspace {
+      id: 1,
+      name: "fkbr".to_string(),
+      ..Default::default()
+    },
+    Workspace {
+      id: 2,
+      name: "FKBR".to_string(),
+      ..Default::default()
+    },
+    Workspace {
+      id: 3,
+      name: "sxoe".to_string(),
+      ..Default::default()
+    },
+  ];
+
+  let resolve_id = |selector: &str| {
+    resolve(
+      "workspace",
+      selector,
+      &workspaces,
+      |workspace| workspace.id,
+      |workspace| &workspace.name,
+    )
+  };
+
+  assert_eq!(resolve_id("3").unwrap(), 3);
+  assert_eq!(resolve_id("FKBR").unwrap(), 2);
+  assert_eq!(resolve_id("SXOE").unwrap(), 3);
+  assert_eq!(
+    resolve_id("Fkbr").unwrap_err().to_string(),
+    "Multiple workspaces match 'Fkbr': fkbr (1), FKBR (2)"
+  );
+  assert_eq!(
+    resolve_id("unknown").unwrap_err().to_string(),
+    "Cannot find workspace='unknown'"
+  );
+}
+
+#[test]
+fn test_resolver_caches_and_unaliases() -> anyhow::Result<()> {
+  let projects: Vec<Project> = serde_json::from_value(json!([
+    {
+      "id": 456,
+      "wid": 123,
+      "name": "beta male gmbh",
+      "status": "active"
+    }
+  ]))?;
+
+  let mut server = mockito::Server::new();
+
+  let projects_mock = server
+    .mock("GET", "/workspaces/123/projects?active=true")
+    .with_status(200)
+    .with_body(serde_json::to_string(&projects)?)
+    .expect(1)
+    .create();
+
+  let tags_mock = server
+    .mock("GET", "/workspaces/123/tags")
+    .with_status(200)
+    .with_body(json!([{ "id": 1, "name": "Meeting" }]).to_string())
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let aliases = Aliases {
+    projects: HashMap::from([("bm".to_string(), "beta male gmbh".to_string())]),
+    tags: HashMap::from([("mtg".to_string(), "meeting".to_string())]),
+    ..Default::default()
+  };
+
+  let resolver = Resolver::with_aliases(false, &client, aliases);
+
+  assert_eq!(resolver.project_id(123, "Beta Male GmbH")?, 456);
+  assert_eq!(resolver.project_id(123, "BM")?, 456);
+  assert_eq!(
+    resolver.tags(123, &Some(vec!["mtg".to_string(), "new".to_string()]))?,
+    Some(vec!["Meeting".to_string(), "new".to_string()])
+  );
+
+  projects_mock.assert();
+  tags_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_resolver_non_billable_projects() -> anyhow::Result<()> {
+  let projects: Vec<Project> = serde_json::from_value(json!([
+    { "id": 456, "wid": 123, "name": "Internal", "status": "active" },
+    { "id": 789, "wid": 123, "name": "fkbr.org", "status": "active" }
+  ]))?;
+
+  let mut server = mockito::Server::new();
+
+  let projects_mock = server
+    .mock("GET", "/workspaces/123/projects?active=true")
+    .with_status(200)
+    .with_body(serde_json::to_string(&projects)?)
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let resolver = Resolver::with_aliases(false, &client, Aliases::default())
+    .with_non_billable_projects(vec!["internal".to_string()]);
+
+  assert_eq!(resolver.project_id(123, "Internal")?, 456);
+  assert_eq!(resolver.project_id(123, "fkbr.org")?, 789);
+
+  assert!(resolver.is_non_billable_project(123, 456));
+  assert!(!resolver.is_non_billable_project(123, 789));
+
+  projects_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_resolver_creates_missing_project() -> anyhow::Result<()> {
+  let projects: Vec<Project> = serde_json::from_value(json!([
+    { "id": 456, "wid": 123, "name": "Internal", "status": "active" },
+    { "id": 789, "wid": 123, "name": "ACME", "status": "active" },
+    { "id": 790, "wid": 123, "name": "acme", "status": "active" }
+  ]))?;
+
+  let mut server = mockito::Server::new();
+
+  let projects_mock = server
+    .mock("GET", "/workspaces/123/projects?active=true")
+    .with_status(200)
+    .with_body(serde_json::to_string(&projects)?)
+    .expect(1)
+    .create();
+
+  let clients_mock = server
+    .mock("GET", "/workspaces/123/clients?status=active")
+    .with_status(200)
+    .with_body(
+      json!([{ "id": 11, "wid": 123, "name": "fkbr", "archived": false }])
+        .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let create_mock = server
+    .mock("POST", "/workspaces/123/projects")
+    .match_body(mockito::Matcher::PartialJson(json!({
+      "name": "New gig",
+      "client_id": 11
+    })))
+    .with_status(200)
+    .with_body(
+      json!({ "id": 999, "wid": 123, "name": "New gig", "status": "active" })
+        .to_string(),
+    )
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let resolver = Resolver::with_aliases(false, &client, Aliases::default());
+
+  assert!(resolver
+    .project_id_or_create(123, "New gig", false, None)
+    .is_err());
+  assert_eq!(
+    resolver.project_id_or_create(123, "internal", true, None)?,
+    456
+  );
+  assert_eq!(
+    resolver.project_id_or_create(123, "New gig", true, Some("fkbr"))?,
+    999
+  );
+  assert_eq!(resolver.project_id(123, "new gig")?, 999);
+
+  // ambiguous names are no missing projects
+  assert!(resolver
+    .project_id_or_create(123, "Acme", true, None)
+    .is_err());
+
+  projects_mock.assert();
+  clients_mock.assert();
+  create_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_resolver_prefers_configured_workspace() -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let me_mock = server
+    .mock("GET", "/me")
+    .with_status(200)
+    .with_body(json!({ "default_workspace_id": 123 }).to_string())
+    .expect(1)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let resolver = Resolver::with_aliases(false, &client, Aliases::default());
+  assert_eq!(resolver.workspace_id()?, 123);
+
+  let resolver = Resolver::with_aliases(false, &client, Aliases::default())
+    .with_default_workspace_id(Some(456));
+  assert_eq!(resolver.workspace_id()?, 456);
+
+  me_mock.assert();
+
+  Ok(())
+}
+
+#[test]
+fn test_resolver_time_entry_guid_only_searches_recent_months(
+) -> anyhow::Result<()> {
+  let mut server = mockito::Server::new();
+
+  let time_entries_mock = server
+    .mock(
+      "GET",
+      mockito::Matcher::Regex("^/me/time_entries".to_string()),
+    )
+    .with_status(200)
+    .with_body("[]")
+    .expect(2)
+    .create();
+
+  let client = TogglClient::new_with_base_url(
+    "cb7bf7efa6d652046abd2f7d84ee18c1".to_string(),
+    server.url().parse()?,
+  )?;
+
+  let resolver = Resolver::new(false, &client);
+
+  assert_eq!(resolver.time_entry_id(&TimeEntryId::Id(42))?, 42);
+  assert_eq!(
+    resolver
+      .time_entry_id(&TimeEntryId::Guid("fkbr".to_string()))
+      .unwrap_err()
+      .to_string(),
+    "Cannot find time entry with GUID='fkbr' in this or last month, use its \
+     numeric id instead"
+  );
+
+  time_entries_mock.assert();
+
+  Ok(())
+}
@@ -0,0 +1,78 @@
+use anyhow::anyhow;
+use chrono::Duration;
+
+use crate::{cli::RoundingMode, config, model::TimeEntry};
+
+/// Rounding of durations to multiples of `increment`, e.g. 15 minutes for
+/// billing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DurationRounding {
+  pub increment: Duration,
+  pub mode: RoundingMode,
+}
+
+impl DurationRounding {
+  pub fn round(&self, duration: Duration) -> Duration {
+    let increment = self.increment.num_seconds();
+    let seconds = duration.num_seconds();
+
+    if increment <= 0 {
+      return duration;
+    }
+
+    let lower = seconds - seconds.rem_euclid(increment);
+
+    let rounded = match self.mode {
+      RoundingMode::Down => lower,
+      RoundingMode::Up if lower == seconds => lower,
+      RoundingMode::Up => lower + increment,
+      RoundingMode::Nearest if (seconds - lower) * 2 >= increment => {
+        lower + increment
+      }
+      RoundingMode::Nearest => lower,
+    };
+
+    Duration::seconds(rounded)
+  }
+
+  /// Stopped time entry with its rounded duration (same start), running
+  /// time entries have no duration to round
+  pub fn round_time_entry(&self, time_entry: &TimeEntry) -> Option<TimeEntry> {
+    if time_entry.duration.is_negative() {
+      return None;
+    }
+
+    let duration = self.round(Duration::seconds(time_entry.duration));
+
+    Some(TimeEntry {
+      duration: duration.num_seconds(),
+      stop: Some(time_entry.start + duration),
+      ..time_entry.clone()
+    })
+  }
+}
+
+/// --round and --round-mode, the [rounding] section of the settings is the
+/// default of both
+pub fn rounding(
+  round: Option<Duration>,
+  round_mode: Option<RoundingMode>,
+  settings: &config::Rounding,
+) -> anyhow::Result<Option<DurationRounding>> {
+  let increment = match (round, &settings.increment) {
+    (Some(round), _) => round,
+    (None, Some(increment)) => humantime::parse_duration(increment)
+      .map_err(|err| anyhow!("Invalid rounding increment '{increment}': {err}"))
+      .and_then(|increment| Ok(Duration::from_std(increment)?))?,
+    (None, None) => return Ok(None),
+  };
+
+  if increment <= Duration::zero() {
+    return Err(anyhow!("The rounding increment has to be positive"));
+  }
+
+  Ok(Some(DurationRounding {
+    increment,
+    mode: round_mode.unwrap_or(settings.mode),
+  }))
+}
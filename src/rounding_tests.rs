@@ -0,0 +1,122 @@
+use crate::{
+  cli::RoundingMode,
+  config,
+  model::TimeEntry,
+  rounding::{rounding, DurationRounding},
+};
+use chrono::Duration;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn minutes(
+  rounding: &DurationRounding,
+  hours: i64,
+  minutes: i64,
+  seconds: i64,
+) -> i64 {
+  rounding
+    .round(
+      Duration::hours(hours)
+        + Duration::minutes(minutes)
+        + Duration::seconds(seconds),
+    )
+    .num_minutes()
+}
+
+#[test]
+fn test_round() {
+  let mut rounding = DurationRounding {
+    increment: Duration::minutes(15),
+    mode: RoundingMode::Nearest,
+  };
+
+  assert_eq!(minutes(&rounding, 0, 7, 29), 0);
+  assert_eq!(minutes(&rounding, 0, 7, 30), 15);
+  assert_eq!(minutes(&rounding, 1, 53, 0), 120);
+  assert_eq!(minutes(&rounding, 1, 45, 0), 105);
+
+  rounding.mode = RoundingMode::Up;
+
+  assert_eq!(minutes(&rounding, 0, 0, 1), 15);
+  assert_eq!(minutes(&rounding, 1, 45, 0), 105);
+  assert_eq!(minutes(&rounding, 0, 0, 0), 0);
+
+  rounding.mode = RoundingMode::Down;
+
+  assert_eq!(minutes(&rounding, 0, 14, 59), 0);
+  assert_eq!(minutes(&rounding, 1, 52, 0), 105);
+}
+
+#[test]
+fn test_round_time_entry() -> anyhow::Result<()> {
+  let rounding = DurationRounding {
+    increment: Duration::minutes(15),
+    mode: RoundingMode::Up,
+  };
+
+  let time_entry: TimeEntry = serde_json::from_value(json!({
+    "id": 1,
+    "wid": 1234567,
+    "start": "2021-11-22T08:00:00Z",
+    "stop": "2021-11-22T08:52:10Z",
+    "duration": 3130
+  }))?;
+
+  let rounded = rounding.round_time_entry(&time_entry).unwrap();
+
+  assert_eq!(rounded.start, time_entry.start);
+  assert_eq!(rounded.duration, 3600);
+  assert_eq!(
+    rounded.stop.map(|stop| stop.to_rfc3339()),
+    Some("2021-11-22T09:00:00+00:00".to_string())
+  );
+
+  let running = TimeEntry {
+    duration: -1,
+    stop: None,
+    ..time_entry
+  };
+
+  assert!(rounding.round_time_entry(&running).is_none());
+
+  Ok(())
+}
+
+#[test]
+fn test_rounding_defaults_from_settings() -> anyhow::Result<()> {
+  let settings = config::Rounding {
+    increment: Some("15m".to_string()),
+    mode: RoundingMode::Up,
+  };
+
+  assert_eq!(rounding(None, None, &config::Rounding::default())?, None);
+  assert_eq!(
+    rounding(None, None, &settings)?,
+    Some(DurationRounding {
+      increment: Duration::minutes(15),
+      mode: RoundingMode::Up,
+    })
+  );
+  assert_eq!(
+    rounding(
+      Some(Duration::minutes(6)),
+      Some(RoundingMode::Down),
+      &settings
+    )?,
+    Some(DurationRounding {
+      increment: Duration::minutes(6),
+      mode: RoundingMode::Down,
+    })
+  );
+  assert!(rounding(
+    None,
+    None,
+    &config::Rounding {
+      increment: Some("fkbr".to_string()),
+      mode: RoundingMode::Nearest,
+    }
+  )
+  .is_err());
+
+  Ok(())
+}
@@ -0,0 +1,57 @@
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Local;
+use chrono::NaiveTime;
+use chrono::Weekday;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Project of `fbtoggl time-entries start` without --project, the first rule
+/// whose conditions all match wins and a rule without conditions always matches
+///
+/// ```toml
+/// [[project_rules]]
+/// before = "10:00"
+/// project = "Standup"
+///
+/// [[project_rules]]
+/// weekdays = ["fri"]
+/// project = "Maintenance"
+/// ```
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectRule {
+  pub project: String,
+
+  /// Matches when started before this time of day
+  #[serde(default)]
+  pub before: Option<NaiveTime>,
+
+  /// Matches when started at or after this time of day
+  #[serde(default)]
+  pub after: Option<NaiveTime>,
+
+  /// Matches on these weekdays ('mon', 'tuesday', ...), every day if empty
+  #[serde(default)]
+  pub weekdays: Vec<Weekday>,
+}
+
+impl ProjectRule {
+  pub fn matches(&self, now: DateTime<Local>) -> bool {
+    let time = now.time();
+
+    self.before.is_none_or(|before| time < before)
+      && self.after.is_none_or(|after| time >= after)
+      && (self.weekdays.is_empty() || self.weekdays.contains(&now.weekday()))
+  }
+}
+
+pub fn routed_project(
+  rules: &[ProjectRule],
+  now: DateTime<Local>,
+) -> Option<&str> {
+  rules
+    .iter()
+    .find(|rule| rule.matches(now))
+    .map(|rule| rule.project.as_str())
+}
@@ -0,0 +1,65 @@
+use crate::routing::{routed_project, ProjectRule};
+use chrono::{DateTime, Local};
+use pretty_assertions::assert_eq;
+use std::str::FromStr;
+
+#[derive(serde::Deserialize)]
+struct Settings {
+  project_rules: Vec<ProjectRule>,
+}
+
+const RULES: &str = r#"
+[[project_rules]]
+before = "10:00"
+project = "Standup"
+
+[[project_rules]]
+weekdays = ["fri", "Saturday"]
+project = "Maintenance"
+
+[[project_rules]]
+after = "18:00"
+weekdays = ["mon"]
+project = "Ops"
+
+[[project_rules]]
+project = "fkbr.org"
+"#;
+
+#[test]
+fn test_routed_project() -> anyhow::Result<()> {
+  let rules = toml::from_str::<Settings>(RULES)?.project_rules;
+
+  let at = |time: &str| DateTime::<Local>::from_str(time).unwrap();
+
+  // Monday
+  assert_eq!(
+    routed_project(&rules, at("2021-11-22T09:59:00+01:00")),
+    Some("Standup")
+  );
+  assert_eq!(
+    routed_project(&rules, at("2021-11-22T10:00:00+01:00")),
+    Some("fkbr.org")
+  );
+  assert_eq!(
+    routed_project(&rules, at("2021-11-22T18:00:00+01:00")),
+    Some("Ops")
+  );
+
+  // Friday, the earlier rule wins before 10:00
+  assert_eq!(
+    routed_project(&rules, at("2021-11-26T08:00:00+01:00")),
+    Some("Standup")
+  );
+  assert_eq!(
+    routed_project(&rules, at("2021-11-26T14:00:00+01:00")),
+    Some("Maintenance")
+  );
+
+  assert_eq!(
+    routed_project(&rules[..3], at("2021-11-23T14:00:00+01:00")),
+    None
+  );
+
+  Ok(())
+}
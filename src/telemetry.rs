@@ -0,0 +1,70 @@
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use crate::cli::APP_NAME;
+
+/// Exports the spans of API calls via OTLP (HTTP) until dropped
+pub struct Telemetry {
+  provider: SdkTracerProvider,
+}
+
+impl Drop for Telemetry {
+  fn drop(&mut self) {
+    if let Err(err) = self.provider.shutdown() {
+      eprintln!("Failed to export traces: {err}");
+    }
+  }
+}
+
+/// Traces are only exported if an endpoint is configured via the standard
+/// variables OTEL_EXPORTER_OTLP_ENDPOINT or OTEL_EXPORTER_OTLP_TRACES_ENDPOINT,
+/// a broken setup never stops a command
+pub fn init() -> Option<Telemetry> {
+  let configured = [
+    "OTEL_EXPORTER_OTLP_ENDPOINT",
+    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+  ]
+  .iter()
+  .any(|key| std::env::var_os(key).is_some());
+
+  if !configured {
+    return None;
+  }
+
+  match try_init() {
+    Ok(telemetry) => Some(telemetry),
+    Err(err) => {
+      eprintln!("Failed to set up tracing: {err}");
+      None
+    }
+  }
+}
+
+fn try_init() -> anyhow::Result<Telemetry> {
+  let exporter = SpanExporter::builder()
+    .with_http()
+    .with_protocol(opentelemetry_otlp::Protocol::HttpBinary)
+    .build()?;
+
+  // OTEL_SERVICE_NAME wins over the default service name
+  let resource = match std::env::var_os("OTEL_SERVICE_NAME") {
+    Some(_) => Resource::builder().build(),
+    None => Resource::builder().with_service_name(APP_NAME).build(),
+  };
+
+  let provider = SdkTracerProvider::builder()
+    .with_batch_exporter(exporter)
+    .with_resource(resource)
+    .build();
+
+  tracing_subscriber::registry()
+    .with(tracing_opentelemetry::layer().with_tracer(provider.tracer(APP_NAME)))
+    .try_init()?;
+
+  Ok(Telemetry { provider })
+}
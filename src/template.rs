@@ -0,0 +1,81 @@
+use std::process::Command;
+
+use anyhow::anyhow;
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Local;
+
+use crate::cli::DateFormat;
+
+/// Expands the placeholders {date}, {weekday}, {week} and {branch}
+/// (git branch of the current directory), '{{' and '}}' escape braces
+pub fn expand_description(
+  description: &Option<String>,
+  date: DateTime<Local>,
+  date_format: &DateFormat,
+) -> anyhow::Result<Option<String>> {
+  description
+    .as_ref()
+    .map(|description| expand(description, date, date_format, git_branch))
+    .transpose()
+}
+
+pub(crate) fn expand(
+  template: &str,
+  date: DateTime<Local>,
+  date_format: &DateFormat,
+  branch: fn() -> anyhow::Result<String>,
+) -> anyhow::Result<String> {
+  let mut expanded = String::new();
+  let mut chars = template.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    match c {
+      '{' if chars.peek() == Some(&'{') => {
+        chars.next();
+        expanded.push('{');
+      }
+      '}' if chars.peek() == Some(&'}') => {
+        chars.next();
+        expanded.push('}');
+      }
+      '{' => {
+        let placeholder =
+          chars.by_ref().take_while(|c| *c != '}').collect::<String>();
+
+        let value = match placeholder.as_str() {
+          "date" => date_format.date(&date.date_naive()),
+          "weekday" => date_format.weekday(&date.date_naive()),
+          "week" => date.iso_week().week().to_string(),
+          "branch" => branch()?,
+          unknown => {
+            return Err(anyhow!(
+              "Unknown placeholder '{{{unknown}}}' in '{template}', use {{date}}, {{weekday}}, {{week}} or {{branch}}"
+            ))
+          }
+        };
+
+        expanded.push_str(&value);
+      }
+      c => expanded.push(c),
+    }
+  }
+
+  Ok(expanded)
+}
+
+fn git_branch() -> anyhow::Result<String> {
+  let output = Command::new("git")
+    .args(["rev-parse", "--abbrev-ref", "HEAD"])
+    .output()
+    .map_err(|err| anyhow!("Cannot run git for {{branch}}: {err}"))?;
+
+  if !output.status.success() {
+    return Err(anyhow!(
+      "Cannot find git branch for {{branch}}: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    ));
+  }
+
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,34 @@
+use crate::{cli::DateFormat, template::expand};
+use chrono::{DateTime, Local};
+use pretty_assertions::assert_eq;
+use std::str::FromStr;
+
+fn branch() -> anyhow::Result<String> {
+  Ok("feature/fkbr".to_string())
+}
+
+#[test]
+fn test_expand() -> anyhow::Result<()> {
+  let date = DateTime::<Local>::from_str("2021-11-22T09:00:00+01:00")?;
+  let date_format = DateFormat::default();
+
+  assert_eq!(
+    expand("Standup {date} {weekday}", date, &date_format, branch)?,
+    "Standup 2021-11-22 Monday"
+  );
+  assert_eq!(
+    expand("Week {week}", date, &date_format, branch)?,
+    "Week 47"
+  );
+  assert_eq!(
+    expand("Review {branch}", date, &date_format, branch)?,
+    "Review feature/fkbr"
+  );
+  assert_eq!(expand("{{date}}", date, &date_format, branch)?, "{date}");
+  assert_eq!(
+    expand("{unknown}", date, &date_format, branch).is_err(),
+    true
+  );
+
+  Ok(())
+}